@@ -1,4 +1,13 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser, ValueEnum};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Rendered statusline with ANSI colors
+    #[default]
+    Ansi,
+    /// Collected segment data as JSON
+    Json,
+}
 
 #[derive(Parser, Debug)]
 #[command(name = "ccline")]
@@ -20,6 +29,18 @@ pub struct Cli {
     #[arg(long = "init")]
     pub init: bool,
 
+    /// Export the resolved theme (config file or --theme) to a .toml or .json file
+    #[arg(long = "export-theme", value_name = "PATH")]
+    pub export_theme: Option<std::path::PathBuf>,
+
+    /// Import a theme file into ~/.claude/ccline/themes/
+    #[arg(long = "import-theme", value_name = "PATH")]
+    pub import_theme: Option<std::path::PathBuf>,
+
+    /// With --import-theme, allow replacing a built-in theme of the same name
+    #[arg(long = "force", requires = "import_theme")]
+    pub force: bool,
+
     /// Check configuration
     #[arg(long = "check")]
     pub check: bool,
@@ -28,13 +49,71 @@ pub struct Cli {
     #[arg(short = 'u', long = "update")]
     pub update: bool,
 
+    /// With --update, only report whether a newer release exists
+    #[arg(long = "check-only", requires = "update")]
+    pub check_only: bool,
+
+    /// Fetch the latest release into the cached update state (started by the update segment)
+    #[arg(long = "refresh-update-state", hide = true)]
+    pub refresh_update_state: bool,
+
+    /// Read Claude Code's JSON from stdin and report which fields parsed
+    #[arg(long = "validate-input")]
+    pub validate_input: bool,
+
+    /// Render the statusline from built-in sample input instead of stdin
+    #[arg(long = "preview")]
+    pub preview: bool,
+
+    /// Output format for the statusline
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Ansi)]
+    pub format: OutputFormat,
+
+    /// Disable ANSI colors (also honored via the NO_COLOR environment variable)
+    #[arg(long = "no-color")]
+    pub no_color: bool,
+
+    /// Enable only these segments for this run, comma-separated (e.g. directory,model)
+    #[arg(long = "segments", value_delimiter = ',', value_name = "ID")]
+    pub segments: Vec<String>,
+
+    /// Disable these segments for this run, comma-separated (e.g. git)
+    #[arg(long = "disable", value_delimiter = ',', value_name = "ID")]
+    pub disable: Vec<String>,
+
+    /// Print a shell completion script to stdout
+    #[arg(long = "completions", value_name = "SHELL", value_enum)]
+    pub completions: Option<clap_complete::Shell>,
+
     /// Patch Claude Code cli.js to disable context warnings
     #[arg(long = "patch")]
     pub patch: Option<String>,
+
+    /// Patches applied by --patch, comma-separated (default: all except auto-compact-notice)
+    #[arg(
+        long = "patches",
+        value_enum,
+        value_delimiter = ',',
+        requires = "patch",
+        value_name = "PATCH"
+    )]
+    pub patches: Vec<crate::utils::PatchKind>,
 }
 
 impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
     }
+
+    /// Write the completion script for `shell` to stdout
+    pub fn print_completions(shell: clap_complete::Shell) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let mut command = Self::command();
+        let name = command.get_name().to_string();
+        // Generate into a buffer: clap_complete panics if writing to a closed pipe fails
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut command, name, &mut script);
+        std::io::stdout().write_all(&script)
+    }
 }
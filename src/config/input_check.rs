@@ -0,0 +1,261 @@
+use super::types::InputData;
+use serde_json::Value;
+use std::fmt;
+
+/// Give up removing bad fields after this many, and fall back to empty input
+const MAX_DROPPED_FIELDS: usize = 16;
+
+/// A field dropped from Claude Code's input because its value didn't fit, and why
+pub struct DroppedField {
+    pub path: String,
+    pub error: String,
+}
+
+impl InputData {
+    /// Deserialize, dropping fields whose values have the wrong type so one schema
+    /// change costs a segment rather than the whole statusline. Missing fields take
+    /// their defaults; input that isn't a JSON object yields `InputData::default()`.
+    pub fn from_value_lenient(mut value: Value) -> (Self, Vec<DroppedField>) {
+        let mut dropped = Vec::new();
+
+        while dropped.len() < MAX_DROPPED_FIELDS {
+            let error = match serde_path_to_error::deserialize::<_, InputData>(&value) {
+                Ok(input) => return (input, dropped),
+                Err(e) => e,
+            };
+
+            let keys: Vec<String> = error
+                .path()
+                .iter()
+                .map(|segment| match segment {
+                    serde_path_to_error::Segment::Map { key } => Some(key.clone()),
+                    _ => None,
+                })
+                .collect::<Option<_>>()
+                .unwrap_or_default();
+            let Some((last, parents)) = keys.split_last() else {
+                break;
+            };
+            let removed = parents
+                .iter()
+                .try_fold(&mut value, |value, key| value.get_mut(key))
+                .and_then(|parent| parent.as_object_mut())
+                .and_then(|parent| parent.remove(last));
+            if removed.is_none() {
+                break;
+            }
+
+            dropped.push(DroppedField {
+                path: error.path().to_string(),
+                error: error.into_inner().to_string(),
+            });
+        }
+
+        (InputData::default(), dropped)
+    }
+}
+
+/// How segments fare when an input field is missing
+#[derive(Clone, Copy, PartialEq)]
+enum Need {
+    /// Segments using the field go blank
+    Expected,
+    /// Segments using the field hide, which is normal for older Claude Code builds
+    Optional,
+    /// A stand-in is used, described by the text
+    Defaulted(&'static str),
+}
+
+/// Input fields the statusline reads
+const KNOWN_FIELDS: [(&str, Need); 12] = [
+    ("model.id", Need::Expected),
+    ("model.display_name", Need::Expected),
+    ("workspace.current_dir", Need::Defaulted("cwd")),
+    ("transcript_path", Need::Expected),
+    ("session_id", Need::Optional),
+    ("version", Need::Optional),
+    ("cost.total_cost_usd", Need::Optional),
+    ("cost.total_duration_ms", Need::Optional),
+    ("cost.total_api_duration_ms", Need::Optional),
+    ("cost.total_lines_added", Need::Optional),
+    ("cost.total_lines_removed", Need::Optional),
+    ("output_style.name", Need::Optional),
+];
+
+/// Values longer than this are cut in the report
+const MAX_VALUE_WIDTH: usize = 48;
+
+/// Result of `--validate-input`: which fields Claude Code sent and which had to be dropped
+pub struct InputReport {
+    /// `(path, need, value)`; `value` is `None` when the field is missing
+    fields: Vec<(&'static str, Need, Option<String>)>,
+    /// Fields with values of the wrong type, ignored when rendering
+    dropped: Vec<DroppedField>,
+    /// Top-level keys the statusline doesn't read
+    ignored: Vec<String>,
+    /// JSON syntax error; nothing else is reported then
+    syntax_error: Option<String>,
+}
+
+impl InputReport {
+    pub fn from_json(raw: &str) -> Self {
+        let value: Value = match serde_json::from_str(raw) {
+            Ok(value) => value,
+            Err(e) => {
+                return Self {
+                    fields: Vec::new(),
+                    dropped: Vec::new(),
+                    ignored: Vec::new(),
+                    syntax_error: Some(e.to_string()),
+                }
+            }
+        };
+
+        let fields = KNOWN_FIELDS
+            .iter()
+            .map(|(path, need)| {
+                let found = path
+                    .split('.')
+                    .try_fold(&value, |value, key| value.get(key))
+                    .filter(|value| !value.is_null())
+                    .map(Self::describe);
+                (*path, *need, found)
+            })
+            .collect();
+
+        let ignored = value
+            .as_object()
+            .map(|object| {
+                object
+                    .keys()
+                    .filter(|key| {
+                        !KNOWN_FIELDS
+                            .iter()
+                            .any(|(path, _)| path.split('.').next() == Some(key.as_str()))
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (_, dropped) = InputData::from_value_lenient(value);
+
+        Self {
+            fields,
+            dropped,
+            ignored,
+            syntax_error: None,
+        }
+    }
+
+    /// Parsed without dropping anything and with every expected field present
+    pub fn is_valid(&self) -> bool {
+        self.syntax_error.is_none()
+            && self.dropped.is_empty()
+            && self
+                .fields
+                .iter()
+                .all(|(_, need, found)| *need != Need::Expected || found.is_some())
+    }
+
+    /// Whether dropping `dropped` also lost the field at `path`
+    fn covers(dropped: &str, path: &str) -> bool {
+        path == dropped
+            || path
+                .strip_prefix(dropped)
+                .is_some_and(|rest| rest.starts_with('.'))
+    }
+
+    fn describe(value: &Value) -> String {
+        let text = value.to_string();
+        if text.chars().count() > MAX_VALUE_WIDTH {
+            let cut: String = text.chars().take(MAX_VALUE_WIDTH - 3).collect();
+            format!("{}...", cut)
+        } else {
+            text
+        }
+    }
+}
+
+impl fmt::Display for InputReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(error) = &self.syntax_error {
+            return write!(f, "✗ Input is not valid JSON: {}", error);
+        }
+
+        let width = self
+            .fields
+            .iter()
+            .map(|(path, _, _)| path.len())
+            .max()
+            .unwrap_or(0);
+
+        for (path, need, found) in &self.fields {
+            let dropped = self
+                .dropped
+                .iter()
+                .find(|field| Self::covers(&field.path, path));
+            match (found, dropped, need) {
+                (_, Some(field), _) => writeln!(f, "✗ {:width$}  ignored: {}", path, field.error)?,
+                (Some(value), None, _) => writeln!(f, "✓ {:width$}  {}", path, value)?,
+                (None, None, Need::Expected) => {
+                    writeln!(f, "✗ {:width$}  missing (left empty)", path)?
+                }
+                (None, None, Need::Optional) => {
+                    writeln!(f, "- {:width$}  missing (optional)", path)?
+                }
+                (None, None, Need::Defaulted(stand_in)) => {
+                    writeln!(f, "- {:width$}  missing (defaulted to {})", path, stand_in)?
+                }
+            }
+        }
+
+        // Bad values outside the fields listed above
+        for field in &self.dropped {
+            if !self
+                .fields
+                .iter()
+                .any(|(path, _, _)| Self::covers(&field.path, path))
+            {
+                writeln!(f, "✗ {}  ignored: {}", field.path, field.error)?;
+            }
+        }
+
+        if !self.ignored.is_empty() {
+            writeln!(f, "Ignored fields: {}", self.ignored.join(", "))?;
+        }
+
+        if self.is_valid() {
+            write!(f, "✓ Input valid")
+        } else {
+            write!(
+                f,
+                "✗ Input invalid: segments relying on the fields marked ✗ will be blank"
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_current_dir_is_defaulted() {
+        let report = InputReport::from_json(
+            r#"{"model": {"id": "m", "display_name": "M"}, "transcript_path": "/t"}"#,
+        );
+        assert!(report.is_valid());
+        let text = report.to_string();
+        assert!(text.contains("missing (defaulted to cwd)"));
+        assert!(text.ends_with("✓ Input valid"));
+    }
+
+    #[test]
+    fn verdict_matches_validity() {
+        let report = InputReport::from_json(r#"{"model": 5}"#);
+        assert!(!report.is_valid());
+        assert!(report.to_string().ends_with("will be blank"));
+        assert!(report.to_string().contains("✗ Input invalid"));
+    }
+}
@@ -1,7 +1,13 @@
-use super::types::Config;
+use super::types::{Config, SegmentConfig, SegmentId};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Per-project config file, merged over the global config
+const PROJECT_CONFIG_FILE: &str = ".ccline.toml";
+
+/// Segment options shown as text, which get `${VAR}` expansion when rendering
+const DISPLAY_TEXT_OPTIONS: [&str; 2] = ["prefix", "separator"];
+
 /// Result of config initialization
 #[derive(Debug)]
 pub enum InitResult {
@@ -41,6 +47,7 @@ impl ConfigLoader {
             "powerline-light",
             "powerline-rose-pine",
             "powerline-tokyo-night",
+            "ascii",
         ];
         let mut created_any = false;
 
@@ -95,6 +102,7 @@ impl ConfigLoader {
             "powerline-light",
             "powerline-rose-pine",
             "powerline-tokyo-night",
+            "ascii",
         ];
 
         for theme_name in &builtin_themes {
@@ -123,11 +131,117 @@ impl Config {
             return Ok(Config::default());
         }
 
+        // ${VAR} references stay as written here so a config saved back (e.g. by the
+        // TUI) keeps them; rendering uses an expanded copy from `with_env_expanded`
         let content = fs::read_to_string(config_path)?;
         let config: Config = toml::from_str(&content)?;
         Ok(config)
     }
 
+    /// Copy for rendering with `${VAR}` references expanded in display text: icons, the
+    /// style separator and truncate marker, and the `prefix`/`separator` segment options.
+    /// Never save the result, or the expanded values would replace the references.
+    pub fn with_env_expanded(mut self) -> Config {
+        let blank_missing = self.blank_missing_env;
+        let expand = |text: &mut String| *text = expand_env(text, blank_missing);
+
+        expand(&mut self.style.separator);
+        expand(&mut self.style.truncate_marker);
+        for segment in &mut self.segments {
+            expand(&mut segment.icon.plain);
+            expand(&mut segment.icon.nerd_font);
+            for key in DISPLAY_TEXT_OPTIONS {
+                if let Some(serde_json::Value::String(text)) = segment.options.get_mut(key) {
+                    expand(text);
+                }
+            }
+        }
+        self
+    }
+
+    /// Merge the nearest `.ccline.toml` found walking up from `dir` over this config.
+    ///
+    /// Precedence, lowest to highest: global config (or `--theme`), the project file's
+    /// `theme` preset if it names one, then the project file's own settings. Segments
+    /// are merged by `id`; unknown ids are appended. A malformed project file is
+    /// reported on stderr and ignored.
+    pub fn with_project_overrides(self, dir: &str) -> Config {
+        let Some(path) = Path::new(dir)
+            .ancestors()
+            .map(|ancestor| ancestor.join(PROJECT_CONFIG_FILE))
+            .find(|path| path.is_file())
+        else {
+            return self;
+        };
+
+        match self.clone().merge_project_file(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("ccline: ignoring {}: {}", path.display(), e);
+                self
+            }
+        }
+    }
+
+    /// Override which segments are enabled for a single run: with `only`, just those ids
+    /// are enabled, then the ids in `disable` are turned off. An id that no configured
+    /// segment has is an error listing the valid ones.
+    pub fn with_segment_selection(
+        mut self,
+        only: &[String],
+        disable: &[String],
+    ) -> Result<Config, Box<dyn std::error::Error>> {
+        let parse = |id: &String| -> Result<SegmentId, Box<dyn std::error::Error>> {
+            parse_segment_id(id)
+                .filter(|id| self.segments.iter().any(|s| s.id == *id))
+                .ok_or_else(|| {
+                    let mut valid: Vec<String> = Vec::new();
+                    for segment in &self.segments {
+                        if let Ok(serde_json::Value::String(name)) =
+                            serde_json::to_value(segment.id)
+                        {
+                            if !valid.contains(&name) {
+                                valid.push(name);
+                            }
+                        }
+                    }
+                    format!(
+                        "Unknown segment id '{}'; valid ids: {}",
+                        id,
+                        valid.join(", ")
+                    )
+                    .into()
+                })
+        };
+        let only = only.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        let disable = disable.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+
+        for segment in &mut self.segments {
+            if !only.is_empty() {
+                segment.enabled = only.contains(&segment.id);
+            }
+            if disable.contains(&segment.id) {
+                segment.enabled = false;
+            }
+        }
+        Ok(self)
+    }
+
+    fn merge_project_file(self, path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let overrides: toml::Value = toml::from_str(&content)?;
+
+        // A project theme swaps the base before its own settings apply
+        let base = match overrides.get("theme").and_then(|v| v.as_str()) {
+            Some(theme) if theme != self.theme => crate::ui::themes::ThemePresets::get_theme(theme),
+            _ => self,
+        };
+
+        let mut merged = toml::Value::try_from(&base)?;
+        merge_toml(&mut merged, overrides);
+        Ok(merged.try_into()?)
+    }
+
     /// Save configuration to default location
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let config_path = Self::get_config_path();
@@ -173,6 +287,19 @@ impl Config {
         }
     }
 
+    /// Segments in render order: the `order` list when set, otherwise as configured
+    pub fn ordered_segments(&self) -> Vec<&SegmentConfig> {
+        let Some(ref order) = self.order else {
+            return self.segments.iter().collect();
+        };
+
+        order
+            .iter()
+            .filter_map(|id| parse_segment_id(id))
+            .flat_map(|id| self.segments.iter().filter(move |s| s.id == id))
+            .collect()
+    }
+
     /// Validate configuration
     pub fn check(&self) -> Result<(), Box<dyn std::error::Error>> {
         // Basic validation
@@ -180,14 +307,379 @@ impl Config {
             return Err("No segments configured".into());
         }
 
-        // Validate segment IDs are unique
+        // Validate segment IDs are unique; custom text, command and env segments may repeat
         let mut seen_ids = std::collections::HashSet::new();
         for segment in &self.segments {
-            if !seen_ids.insert(segment.id) {
+            let repeatable = matches!(
+                segment.id,
+                SegmentId::Custom | SegmentId::Command | SegmentId::Env
+            );
+            if !repeatable && !seen_ids.insert(segment.id) {
                 return Err(format!("Duplicate segment ID: {:?}", segment.id).into());
             }
         }
 
+        // Validate the segment order lists known ids, each once
+        if let Some(ref order) = self.order {
+            let mut listed = std::collections::HashSet::new();
+            for id in order {
+                if parse_segment_id(id).is_none() {
+                    return Err(format!("Unknown segment id in order: {:?}", id).into());
+                }
+                if !listed.insert(id) {
+                    return Err(format!("Segment id listed twice in order: {:?}", id).into());
+                }
+            }
+        }
+
+        if !(1..=100).contains(&self.truncate_percent) {
+            return Err(format!(
+                "truncate_percent must be between 1 and 100, got {}",
+                self.truncate_percent
+            )
+            .into());
+        }
+        // reserved_width is unsigned, so a negative value already fails to parse
+
+        // The marker is measured as plain text, so it can't carry its own escapes
+        if self.style.truncate_marker.chars().any(|ch| ch.is_control()) {
+            return Err("truncate_marker must not contain escape or control characters".into());
+        }
+
+        // Validate theme rules name known themes with well-formed conditions
+        use crate::ui::themes::ThemePresets;
+        for rule in &self.theme_rules {
+            let built_in = ThemePresets::get_available_themes()
+                .iter()
+                .any(|(name, _)| *name == rule.theme);
+            if !built_in {
+                ThemePresets::load_theme_from_file(&rule.theme)
+                    .map_err(|e| format!("theme_rules: {}", e))?;
+            }
+            if let Some(between) = &rule.between {
+                if super::types::ThemeRule::time_range(between).is_none() {
+                    return Err(format!(
+                        "theme_rules: between must look like \"07:00-19:00\", got {:?}",
+                        between
+                    )
+                    .into());
+                }
+            }
+            if let Some(background) = &rule.background {
+                if background != "light" && background != "dark" {
+                    return Err(format!(
+                        "theme_rules: background must be \"light\" or \"dark\", got {:?}",
+                        background
+                    )
+                    .into());
+                }
+            }
+        }
+
+        // Validate decimals for abbreviated numbers such as "1.2k"
+        use crate::utils::format::MAX_PRECISION;
+        for segment in &self.segments {
+            if let Some(precision) = segment.options.get("precision") {
+                if precision.as_u64().is_none_or(|p| p > MAX_PRECISION as u64) {
+                    return Err(format!(
+                        "precision for {:?} must be between 0 and {}, got {}",
+                        segment.id, MAX_PRECISION, precision
+                    )
+                    .into());
+                }
+            }
+        }
+
+        // Validate level color overrides (e.g. warning_color) parse as colors
+        for segment in &self.segments {
+            for (key, value) in &segment.options {
+                if key.ends_with("_color")
+                    && serde_json::from_value::<super::types::AnsiColor>(value.clone()).is_err()
+                {
+                    return Err(
+                        format!("Invalid color for {:?} option {:?}", segment.id, key).into(),
+                    );
+                }
+            }
+        }
+
+        // Validate per-segment separator, alignment and padding overrides
+        for segment in &self.segments {
+            if let Some(separator) = segment.options.get("separator") {
+                if !separator.is_string() {
+                    return Err(format!("{:?} separator must be a string", segment.id).into());
+                }
+            }
+            if let Some(align) = segment.options.get("align") {
+                if !matches!(align.as_str(), Some("left" | "right")) {
+                    return Err(
+                        format!("{:?} align must be \"left\" or \"right\"", segment.id).into(),
+                    );
+                }
+            }
+            for key in ["left_pad", "right_pad"] {
+                if let Some(pad) = segment.options.get(key) {
+                    if pad.as_u64().is_none() {
+                        return Err(format!(
+                            "{:?} {} must be a non-negative integer",
+                            segment.id, key
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
+
+        // Validate segment conditions
+        use crate::core::condition::Condition;
+        for segment in &self.segments {
+            if let Err(e) = Condition::from_options(segment) {
+                return Err(format!("Invalid condition for {:?}: {}", segment.id, e).into());
+            }
+        }
+
+        // Validate directory display mode
+        use crate::core::segments::DirectoryDisplay;
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::Directory)
+        {
+            if let Some(display) = segment.options.get("display").and_then(|v| v.as_str()) {
+                if DirectoryDisplay::parse(display).is_none() {
+                    return Err(format!("Invalid directory display: {:?}", display).into());
+                }
+            }
+        }
+
+        // Validate load display mode
+        use crate::core::segments::LoadDisplay;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Load) {
+            if let Some(display) = segment.options.get("display").and_then(|v| v.as_str()) {
+                if LoadDisplay::parse(display).is_none() {
+                    return Err(format!("Invalid load display: {:?}", display).into());
+                }
+            }
+        }
+
+        // Validate runtimes probed by the runtime segment
+        use crate::core::segments::runtime::DEFAULT_RUNTIMES;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Runtime) {
+            if let Some(runtimes) = segment.options.get("runtimes").and_then(|v| v.as_array()) {
+                for runtime in runtimes {
+                    if !runtime
+                        .as_str()
+                        .is_some_and(|name| DEFAULT_RUNTIMES.contains(&name))
+                    {
+                        return Err(format!("Unknown runtime: {}", runtime).into());
+                    }
+                }
+            }
+        }
+
+        // Validate manifests searched by the package segment
+        use crate::core::segments::package::DEFAULT_MANIFESTS;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Package) {
+            if let Some(manifests) = segment.options.get("manifests").and_then(|v| v.as_array()) {
+                for manifest in manifests {
+                    if !manifest
+                        .as_str()
+                        .is_some_and(|name| DEFAULT_MANIFESTS.contains(&name))
+                    {
+                        return Err(format!("Unknown package manifest: {}", manifest).into());
+                    }
+                }
+            }
+        }
+
+        // Validate environments that trigger the environment segment
+        use crate::core::segments::environment::ENVIRONMENTS;
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::Environment)
+        {
+            if let Some(environments) = segment
+                .options
+                .get("environments")
+                .and_then(|v| v.as_array())
+            {
+                for environment in environments {
+                    if !environment
+                        .as_str()
+                        .is_some_and(|name| ENVIRONMENTS.contains(&name))
+                    {
+                        return Err(format!("Unknown environment: {}", environment).into());
+                    }
+                }
+            }
+        }
+
+        // Validate which VCS segment wins in colocated jj/git repositories
+        use crate::core::segments::jujutsu::VCS_PREFERENCES;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Jujutsu) {
+            if let Some(prefer) = segment.options.get("prefer") {
+                if !prefer
+                    .as_str()
+                    .is_some_and(|name| VCS_PREFERENCES.contains(&name))
+                {
+                    return Err(format!("Unknown jujutsu prefer value: {}", prefer).into());
+                }
+            }
+        }
+
+        // Validate disk free space thresholds
+        use crate::core::segments::DiskThreshold;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Disk) {
+            for key in ["show_below", "critical_below"] {
+                if let Some(value) = segment.options.get(key).and_then(|v| v.as_str()) {
+                    if DiskThreshold::parse(value).is_none() {
+                        return Err(format!("Invalid disk {}: {:?}", key, value).into());
+                    }
+                }
+            }
+        }
+
+        // Validate kubernetes dangerous context patterns
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::Kubernetes)
+        {
+            if let Some(patterns) = segment
+                .options
+                .get("dangerous_contexts")
+                .and_then(|v| v.as_array())
+            {
+                for pattern in patterns.iter().filter_map(|p| p.as_str()) {
+                    if let Err(e) = regex::Regex::new(pattern) {
+                        return Err(format!(
+                            "Invalid dangerous_contexts pattern {:?}: {}",
+                            pattern, e
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
+
+        // Validate terraform warning pattern
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::Terraform)
+        {
+            if let Some(pattern) = segment
+                .options
+                .get("warning_pattern")
+                .and_then(|v| v.as_str())
+            {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    return Err(format!("Invalid warning_pattern {:?}: {}", pattern, e).into());
+                }
+            }
+        }
+
+        // Validate clock format and timezone options
+        use crate::core::segments::ClockSegment;
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Clock) {
+            if let Some(format) = segment.options.get("format").and_then(|v| v.as_str()) {
+                if !ClockSegment::is_valid_format(format) {
+                    return Err(format!("Invalid clock format: {:?}", format).into());
+                }
+            }
+            if let Some(timezone) = segment.options.get("timezone").and_then(|v| v.as_str()) {
+                if ClockSegment::parse_timezone(timezone).is_none() {
+                    return Err(format!("Invalid clock timezone: {:?}", timezone).into());
+                }
+            }
+        }
+
+        // Validate budget limit, thresholds and day-boundary timezone
+        for segment in self.segments.iter().filter(|s| s.id == SegmentId::Budget) {
+            if let Some(limit) = segment.options.get("daily_limit") {
+                if limit.as_f64().is_none_or(|limit| limit <= 0.0) {
+                    return Err(format!("Invalid budget daily_limit: {}", limit).into());
+                }
+            }
+            for key in ["warning_percent", "critical_percent"] {
+                if let Some(percent) = segment.options.get(key) {
+                    if percent.as_f64().is_none_or(|percent| percent < 0.0) {
+                        return Err(format!("Invalid budget {}: {}", key, percent).into());
+                    }
+                }
+            }
+            if let Some(timezone) = segment.options.get("timezone").and_then(|v| v.as_str()) {
+                if ClockSegment::parse_timezone(timezone).is_none() {
+                    return Err(format!("Invalid budget timezone: {:?}", timezone).into());
+                }
+            }
+        }
+
+        // Validate last commit age thresholds
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::LastCommit)
+        {
+            for key in ["warning_minutes", "critical_minutes"] {
+                if let Some(minutes) = segment.options.get(key) {
+                    if minutes.as_u64().is_none() {
+                        return Err(
+                            format!("Last commit {} must be a non-negative integer", key).into(),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Validate git identity patterns map to a label or a label/level table
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::GitIdentity)
+        {
+            if let Some(identities) = segment.options.get("identities") {
+                let Some(identities) = identities.as_object() else {
+                    return Err("Git identity identities must be a table".into());
+                };
+                for (pattern, style) in identities {
+                    let valid = match style {
+                        serde_json::Value::String(_) => true,
+                        serde_json::Value::Object(style) => style.iter().all(|(key, value)| {
+                            matches!(key.as_str(), "label" | "level") && value.is_string()
+                        }),
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err(format!(
+                            "Git identity {:?} must be a label or a table of label and level",
+                            pattern
+                        )
+                        .into());
+                    }
+                }
+            }
+            if let Some(default) = segment.options.get("default_identity") {
+                if !default.is_string() {
+                    return Err("Git identity default_identity must be a string".into());
+                }
+            }
+        }
+
+        // Validate daily cost day-boundary timezone
+        for segment in self
+            .segments
+            .iter()
+            .filter(|s| s.id == SegmentId::DailyCost)
+        {
+            if let Some(timezone) = segment.options.get("timezone").and_then(|v| v.as_str()) {
+                if ClockSegment::parse_timezone(timezone).is_none() {
+                    return Err(format!("Invalid daily cost timezone: {:?}", timezone).into());
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -198,3 +690,246 @@ impl Config {
         Ok(())
     }
 }
+
+/// Parse a segment id as written in config, e.g. "context_window"
+fn parse_segment_id(id: &str) -> Option<SegmentId> {
+    serde_json::from_value(serde_json::Value::String(id.to_string())).ok()
+}
+
+/// Deep-merge `overlay` into `base`: tables merge key by key, the `segments`
+/// array merges entries by `id`, anything else is replaced
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) if key == "segments" => merge_segments(existing, value),
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Merge overlay segments into base ones by `id`. Ids that may repeat (custom, command,
+/// env) pair up by position: the overlay's second `custom` entry merges into the base's
+/// second `custom` entry, and extra ones are appended.
+fn merge_segments(base: &mut toml::Value, overlay: toml::Value) {
+    let (toml::Value::Array(base), toml::Value::Array(overlay)) = (&mut *base, overlay) else {
+        return;
+    };
+
+    let mut seen: Vec<toml::Value> = Vec::new();
+    for segment in overlay {
+        let Some(id) = segment.get("id").cloned() else {
+            base.push(segment);
+            continue;
+        };
+        let nth = seen.iter().filter(|seen_id| **seen_id == id).count();
+        seen.push(id.clone());
+        match base
+            .iter_mut()
+            .filter(|existing| existing.get("id") == Some(&id))
+            .nth(nth)
+        {
+            Some(existing) => merge_toml(existing, segment),
+            None => base.push(segment),
+        }
+    }
+}
+
+/// Expand `${VAR}` tokens like `Config::with_env_expanded`, but return `None` when any
+/// referenced variable is unset or empty
+pub fn expand_env_strict(input: &str) -> Option<String> {
+    let (output, all_set) = expand_env_tracking(input, true);
+    all_set.then_some(output)
+}
+
+/// Expand `${VAR}` tokens against the process environment; `$$` is a literal `$`
+fn expand_env(input: &str, blank_missing: bool) -> String {
+    expand_env_tracking(input, blank_missing).0
+}
+
+/// Expand like `expand_env`, also reporting whether every variable had a non-empty value
+fn expand_env_tracking(input: &str, blank_missing: bool) -> (String, bool) {
+    let mut output = String::with_capacity(input.len());
+    let mut all_set = true;
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        output.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(after_escape) = after.strip_prefix('$') {
+            output.push('$');
+            rest = after_escape;
+        } else if let Some(end) = after.strip_prefix('{').and_then(|body| body.find('}')) {
+            let name = &after[1..end + 1];
+            match std::env::var(name) {
+                Ok(val) => {
+                    all_set &= !val.is_empty();
+                    output.push_str(&val);
+                }
+                Err(_) if blank_missing => all_set = false,
+                Err(_) => {
+                    all_set = false;
+                    output.push_str(&rest[pos..pos + end + 3]);
+                }
+            }
+            rest = &after[end + 2..];
+        } else {
+            // A lone `$` or an unterminated `${` is kept as written
+            output.push('$');
+            rest = after;
+        }
+    }
+
+    output.push_str(rest);
+    (output, all_set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_set_variables() {
+        std::env::set_var("CCLINE_TEST_USER", "ada");
+        std::env::set_var("CCLINE_TEST_HOST", "box");
+        assert_eq!(
+            expand_env("${CCLINE_TEST_USER}@${CCLINE_TEST_HOST}", false),
+            "ada@box"
+        );
+    }
+
+    #[test]
+    fn nested_references_are_not_expanded_again() {
+        std::env::set_var("CCLINE_TEST_OUTER", "${CCLINE_TEST_INNER}");
+        std::env::set_var("CCLINE_TEST_INNER", "inner");
+        assert_eq!(
+            expand_env("${CCLINE_TEST_OUTER}", false),
+            "${CCLINE_TEST_INNER}"
+        );
+        // A reference inside braces isn't a nested lookup: the name ends at the first `}`
+        assert_eq!(
+            expand_env("${${CCLINE_TEST_INNER}}", false),
+            "${${CCLINE_TEST_INNER}}"
+        );
+    }
+
+    #[test]
+    fn missing_variables_kept_or_blanked() {
+        std::env::remove_var("CCLINE_TEST_MISSING");
+        assert_eq!(
+            expand_env("a${CCLINE_TEST_MISSING}b", false),
+            "a${CCLINE_TEST_MISSING}b"
+        );
+        assert_eq!(expand_env("a${CCLINE_TEST_MISSING}b", true), "ab");
+        assert_eq!(expand_env_strict("${CCLINE_TEST_MISSING}"), None);
+    }
+
+    #[test]
+    fn double_dollar_is_a_literal_dollar() {
+        std::env::set_var("CCLINE_TEST_PRICE", "5");
+        assert_eq!(expand_env("$$${CCLINE_TEST_PRICE}", false), "$5");
+        assert_eq!(
+            expand_env("$${CCLINE_TEST_PRICE}", false),
+            "${CCLINE_TEST_PRICE}"
+        );
+        assert_eq!(
+            expand_env("cost: $ 3, ${unterminated", false),
+            "cost: $ 3, ${unterminated"
+        );
+    }
+
+    #[test]
+    fn expansion_leaves_the_loaded_config_and_non_display_options_alone() {
+        std::env::set_var("CCLINE_TEST_PREFIX", "env:");
+        let mut config = Config::default();
+        let segment = &mut config.segments[0];
+        segment.options.insert(
+            "prefix".to_string(),
+            serde_json::json!("${CCLINE_TEST_PREFIX}"),
+        );
+        segment.options.insert(
+            "command".to_string(),
+            serde_json::json!("echo ${CCLINE_TEST_PREFIX}"),
+        );
+
+        let expanded = config.clone().with_env_expanded();
+        assert_eq!(expanded.segments[0].options["prefix"], "env:");
+        assert_eq!(
+            expanded.segments[0].options["command"],
+            "echo ${CCLINE_TEST_PREFIX}"
+        );
+        // What would be saved still holds the reference
+        assert!(toml::to_string(&config)
+            .unwrap()
+            .contains("${CCLINE_TEST_PREFIX}"));
+    }
+
+    #[test]
+    fn repeated_segments_merge_by_position() {
+        let mut base: toml::Value = toml::from_str(
+            r#"
+            [[segments]]
+            id = "custom"
+            options = { text = "first" }
+
+            [[segments]]
+            id = "model"
+            enabled = true
+
+            [[segments]]
+            id = "custom"
+            options = { text = "second" }
+            "#,
+        )
+        .unwrap();
+        let overlay: toml::Value = toml::from_str(
+            r#"
+            [[segments]]
+            id = "model"
+            enabled = false
+
+            [[segments]]
+            id = "custom"
+
+            [[segments]]
+            id = "custom"
+            options = { text = "second override" }
+
+            [[segments]]
+            id = "custom"
+            options = { text = "third" }
+            "#,
+        )
+        .unwrap();
+
+        merge_toml(&mut base, overlay);
+
+        let segments = base["segments"].as_array().unwrap();
+        let texts: Vec<_> = segments
+            .iter()
+            .filter(|s| s["id"].as_str() == Some("custom"))
+            .map(|s| s["options"]["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, ["first", "second override", "third"]);
+        assert_eq!(segments[1]["enabled"].as_bool(), Some(false));
+    }
+
+    #[test]
+    fn unknown_selected_segment_is_named_plainly() {
+        let config = crate::ui::themes::ThemePresets::get_default();
+        let error = config
+            .with_segment_selection(&["bogus".to_string()], &[])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.starts_with("Unknown segment id 'bogus'; valid ids: model, directory"));
+    }
+}
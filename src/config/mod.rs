@@ -1,8 +1,10 @@
 pub mod defaults;
+pub mod input_check;
 pub mod loader;
 pub mod models;
 pub mod types;
 
-pub use loader::{ConfigLoader, InitResult};
+pub use input_check::{DroppedField, InputReport};
+pub use loader::{expand_env_strict, ConfigLoader, InitResult};
 pub use models::*;
 pub use types::*;
@@ -7,6 +7,39 @@ pub struct Config {
     pub style: StyleConfig,
     pub segments: Vec<SegmentConfig>,
     pub theme: String,
+    /// Expand unknown `${VAR}` references to an empty string instead of leaving them as written
+    #[serde(default)]
+    pub blank_missing_env: bool,
+    /// Segment ids to render, in this sequence; segments not listed are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<Vec<String>>,
+    /// Cut each statusline line to fit the terminal; off by default so nothing is lost
+    #[serde(default)]
+    pub truncate: bool,
+    /// Widest the statusline may grow, as a percentage of the terminal width
+    #[serde(default = "default_truncate_percent")]
+    pub truncate_percent: usize,
+    /// Columns left free for Claude Code's own indicators beside the statusline
+    #[serde(default = "default_reserved_width")]
+    pub reserved_width: usize,
+    /// Pick a theme at render time; the first rule whose conditions all match wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub theme_rules: Vec<ThemeRule>,
+}
+
+/// `{ theme = "powerline-light", between = "07:00-19:00" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeRule {
+    pub theme: String,
+    /// Local time range "HH:MM-HH:MM", which may wrap past midnight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub between: Option<String>,
+    /// "light" or "dark", judged from the background color in `COLORFGBG`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Substring of `TERM`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
 }
 
 // Default implementation moved to ui/themes/presets.rs
@@ -15,6 +48,42 @@ pub struct Config {
 pub struct StyleConfig {
     pub mode: StyleMode,
     pub separator: String,
+    /// Appended when the statusline is cut to fit the terminal
+    #[serde(default = "default_truncate_marker")]
+    pub truncate_marker: String,
+    /// Whether the terminal font has Nerd Font glyphs; when false, segments use their
+    /// plain icons and powerline arrows and glyph separators fall back to a plain " | "
+    #[serde(default = "default_nerd_font")]
+    pub nerd_font: bool,
+    /// Emit ANSI colors and styles; false renders plain text like `--no-color`
+    #[serde(default = "default_color")]
+    pub color: bool,
+    /// Spaces added before each segment; a segment's `left_pad` option overrides it
+    #[serde(default)]
+    pub left_pad: usize,
+    /// Spaces added after each segment; a segment's `right_pad` option overrides it
+    #[serde(default)]
+    pub right_pad: usize,
+}
+
+pub fn default_truncate_percent() -> usize {
+    60
+}
+
+pub fn default_reserved_width() -> usize {
+    40
+}
+
+pub fn default_truncate_marker() -> String {
+    "...".to_string()
+}
+
+fn default_nerd_font() -> bool {
+    true
+}
+
+fn default_color() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -54,13 +123,62 @@ pub struct TextStyleConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(untagged, try_from = "AnsiColorRepr")]
 pub enum AnsiColor {
     Color16 { c16: u8 },
     Color256 { c256: u8 },
     Rgb { r: u8, g: u8, b: u8 },
 }
 
+/// Accepted color spellings in config files: the table forms above or a `"#rrggbb"` string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnsiColorRepr {
+    Color16 { c16: u8 },
+    Color256 { c256: u8 },
+    Rgb { r: u8, g: u8, b: u8 },
+    Hex(String),
+}
+
+impl TryFrom<AnsiColorRepr> for AnsiColor {
+    type Error = String;
+
+    fn try_from(repr: AnsiColorRepr) -> Result<Self, Self::Error> {
+        match repr {
+            AnsiColorRepr::Color16 { c16 } => Ok(AnsiColor::Color16 { c16 }),
+            AnsiColorRepr::Color256 { c256 } => Ok(AnsiColor::Color256 { c256 }),
+            AnsiColorRepr::Rgb { r, g, b } => Ok(AnsiColor::Rgb { r, g, b }),
+            AnsiColorRepr::Hex(hex) => AnsiColor::from_hex(&hex)
+                .ok_or_else(|| format!("invalid hex color {:?}, expected \"#rrggbb\"", hex)),
+        }
+    }
+}
+
+impl AnsiColor {
+    /// Parse `#rrggbb` or the `#rgb` shorthand into a truecolor value
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.trim().strip_prefix('#')?;
+        if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        match digits.len() {
+            6 => Some(AnsiColor::Rgb {
+                r: channel(&digits[0..2])?,
+                g: channel(&digits[2..4])?,
+                b: channel(&digits[4..6])?,
+            }),
+            3 => Some(AnsiColor::Rgb {
+                r: channel(&digits[0..1])? * 17,
+                g: channel(&digits[1..2])? * 17,
+                b: channel(&digits[2..3])? * 17,
+            }),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SegmentId {
@@ -73,6 +191,55 @@ pub enum SegmentId {
     Session,
     OutputStyle,
     Update,
+    Battery,
+    #[serde(alias = "time")]
+    Clock,
+    SessionDuration,
+    Hostname,
+    User,
+    PythonEnv,
+    Conda,
+    Node,
+    Rust,
+    Go,
+    Java,
+    Dotnet,
+    Kubernetes,
+    Docker,
+    Terraform,
+    Aws,
+    Custom,
+    Gcloud,
+    Load,
+    Disk,
+    Version,
+    LinesChanged,
+    MessageCount,
+    Todo,
+    Mcp,
+    BurnRate,
+    ApiDuration,
+    Command,
+    Runtime,
+    Env,
+    Package,
+    Nix,
+    Direnv,
+    Ssh,
+    Environment,
+    Shell,
+    GithubPr,
+    CiStatus,
+    Jujutsu,
+    Hg,
+    Connectivity,
+    GitDiff,
+    SessionId,
+    LastPrompt,
+    Budget,
+    DailyCost,
+    LastCommit,
+    GitIdentity,
 }
 
 // Legacy compatibility structure
@@ -84,19 +251,35 @@ pub struct SegmentsConfig {
     // pub usage: bool,
 }
 
-// Data structures compatible with existing main.rs
-#[derive(Deserialize)]
+// Data structures compatible with existing main.rs.
+// Every field has a default so a schema change in Claude Code degrades single segments
+// instead of failing the whole statusline; unknown keys are ignored.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Model {
     pub id: String,
     pub display_name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Workspace {
     pub current_dir: String,
 }
 
-#[derive(Deserialize)]
+/// Claude Code runs the statusline command from the workspace, so the process
+/// directory stands in when `current_dir` is missing
+impl Default for Workspace {
+    fn default() -> Self {
+        let current_dir = std::env::current_dir()
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| ".".to_string());
+        Self { current_dir }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Cost {
     pub total_cost_usd: Option<f64>,
     pub total_duration_ms: Option<u64>,
@@ -105,18 +288,51 @@ pub struct Cost {
     pub total_lines_removed: Option<u32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OutputStyle {
     pub name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InputData {
+    pub session_id: Option<String>,
     pub model: Model,
     pub workspace: Workspace,
     pub transcript_path: String,
     pub cost: Option<Cost>,
     pub output_style: Option<OutputStyle>,
+    /// Claude Code version; absent in older builds
+    pub version: Option<String>,
+}
+
+impl InputData {
+    /// Representative input for `--preview`, rooted at the current directory so
+    /// workspace segments (git, languages, project overrides) still show real data.
+    /// No session id, transcript or version, so session and version state is left untouched.
+    pub fn sample() -> Self {
+        Self {
+            session_id: None,
+            model: Model {
+                id: "claude-sonnet-4-5".to_string(),
+                display_name: "Sonnet 4.5".to_string(),
+            },
+            workspace: Workspace::default(),
+            transcript_path: String::new(),
+            cost: Some(Cost {
+                total_cost_usd: Some(0.42),
+                total_duration_ms: Some(1_800_000),
+                total_api_duration_ms: Some(420_000),
+                total_lines_added: Some(156),
+                total_lines_removed: Some(23),
+            }),
+            output_style: Some(OutputStyle {
+                name: "Explanatory".to_string(),
+            }),
+            version: None,
+        }
+    }
 }
 
 // OpenAI-style nested token details
@@ -294,6 +510,59 @@ impl Config {
     }
 }
 
+impl ThemeRule {
+    /// Parse `between` into start and end times
+    pub fn time_range(between: &str) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let (start, end) = between.split_once('-')?;
+        let parse = |time: &str| chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        Some((parse(start)?, parse(end)?))
+    }
+
+    /// Background brightness from `COLORFGBG` ("15;0" is light text on black); the
+    /// background is the last field, with 0-6 and 8 counted as dark
+    pub fn terminal_background(colorfgbg: &str) -> Option<&'static str> {
+        let background: u8 = colorfgbg.rsplit(';').next()?.trim().parse().ok()?;
+        Some(match background {
+            0..=6 | 8 => "dark",
+            _ => "light",
+        })
+    }
+
+    /// Whether every condition the rule sets holds; a rule with none always matches
+    pub fn matches(&self, now: chrono::NaiveTime) -> bool {
+        if let Some(between) = &self.between {
+            let Some((start, end)) = Self::time_range(between) else {
+                return false;
+            };
+            let within = if start <= end {
+                start <= now && now < end
+            } else {
+                now >= start || now < end
+            };
+            if !within {
+                return false;
+            }
+        }
+
+        if let Some(background) = &self.background {
+            let detected = std::env::var("COLORFGBG")
+                .ok()
+                .and_then(|value| Self::terminal_background(&value));
+            if detected != Some(background.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(term) = &self.term {
+            if !std::env::var("TERM").is_ok_and(|value| value.contains(term.as_str())) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 impl PartialEq for AnsiColor {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -413,4 +682,5 @@ pub struct TranscriptEntry {
     #[serde(rename = "parentUuid")]
     pub parent_uuid: Option<String>,
     pub summary: Option<String>,
+    pub timestamp: Option<String>,
 }
@@ -0,0 +1,88 @@
+use crate::config::InputData;
+use crate::core::segments::{Segment, SegmentData};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+const CACHE_FILE: &str = ".segment_cache.json";
+
+/// A collected result (`None` when the segment had nothing to show) and when it goes stale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SegmentCacheEntry {
+    data: Option<SegmentData>,
+    expires_at: DateTime<Utc>,
+}
+
+/// Results of segments that opt in through `Segment::cache_key`, shared by the
+/// threads of one `collect_all_segments` run and written back once at the end.
+/// The cache file is only read when a segment asks for it.
+#[derive(Default)]
+pub struct SegmentCache {
+    entries: OnceLock<HashMap<String, SegmentCacheEntry>>,
+    updates: Mutex<HashMap<String, SegmentCacheEntry>>,
+}
+
+impl SegmentCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> &HashMap<String, SegmentCacheEntry> {
+        self.entries.get_or_init(|| {
+            StateFile::new(CACHE_FILE)
+                .map(|file| file.load())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Reuse a fresh result for the segment's cache key, or collect and remember it
+    pub fn collect<S: Segment>(&self, segment: &S, input: &InputData) -> Option<SegmentData> {
+        let Some(key) = segment.cache_key(input) else {
+            return segment.collect(input);
+        };
+        let key = format!("{:?}\0{}", segment.id(), key);
+
+        let now = Utc::now();
+        if let Some(entry) = self.entries().get(&key) {
+            if now < entry.expires_at {
+                return entry.data.clone();
+            }
+        }
+
+        let data = segment.collect(input);
+        let ttl = chrono::Duration::from_std(segment.cache_ttl()).unwrap_or(chrono::Duration::MAX);
+        if let Ok(mut updates) = self.updates.lock() {
+            updates.insert(
+                key,
+                SegmentCacheEntry {
+                    data: data.clone(),
+                    expires_at: now
+                        .checked_add_signed(ttl)
+                        .unwrap_or(DateTime::<Utc>::MAX_UTC),
+                },
+            );
+        }
+
+        data
+    }
+
+    /// Write new results back, dropping expired ones; a no-op when nothing was recollected.
+    /// Results other processes saved since the cache was read are kept.
+    pub fn save(self) {
+        let updates = self.updates.into_inner().unwrap_or_default();
+        if updates.is_empty() {
+            return;
+        }
+        let Some(file) = StateFile::new(CACHE_FILE) else {
+            return;
+        };
+
+        let now = Utc::now();
+        file.update(|cache: &mut HashMap<String, SegmentCacheEntry>| {
+            cache.extend(updates);
+            cache.retain(|_, entry| entry.expires_at > now);
+        });
+    }
+}
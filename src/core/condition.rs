@@ -0,0 +1,162 @@
+use crate::config::{InputData, SegmentConfig, SegmentId};
+use crate::core::segments::SegmentData;
+use serde_json::Value;
+
+/// Comparison used by a segment's `condition` option
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Comparator {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "==" | "eq" => Some(Comparator::Eq),
+            "!=" | "ne" => Some(Comparator::Ne),
+            ">" | "gt" => Some(Comparator::Gt),
+            ">=" | "ge" => Some(Comparator::Ge),
+            "<" | "lt" => Some(Comparator::Lt),
+            "<=" | "le" => Some(Comparator::Le),
+            _ => None,
+        }
+    }
+}
+
+/// `condition = { field = "cost.total_cost_usd", op = ">", value = 1.0 }`
+///
+/// `field` names a metadata key of the segment itself, `<segment id>.<key>` for
+/// another segment's metadata, or a dotted path into the Claude Code input.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub field: String,
+    pub comparator: Comparator,
+    pub value: Value,
+}
+
+impl Condition {
+    /// Read the `condition` option; `Err` describes a malformed one
+    pub fn from_options(segment: &SegmentConfig) -> Result<Option<Self>, String> {
+        let Some(condition) = segment.options.get("condition") else {
+            return Ok(None);
+        };
+
+        let field = condition
+            .get("field")
+            .and_then(|v| v.as_str())
+            .filter(|field| !field.is_empty())
+            .ok_or("condition needs a field")?;
+        let op = condition
+            .get("op")
+            .and_then(|v| v.as_str())
+            .ok_or("condition needs an op")?;
+        let comparator =
+            Comparator::parse(op).ok_or_else(|| format!("unknown condition op {:?}", op))?;
+        let value = condition
+            .get("value")
+            .filter(|v| !v.is_null())
+            .cloned()
+            .ok_or("condition needs a value")?;
+
+        Ok(Some(Self {
+            field: field.to_string(),
+            comparator,
+            value,
+        }))
+    }
+
+    /// A field that resolves to nothing never matches
+    pub fn matches(
+        &self,
+        own: &SegmentData,
+        collected: &[(SegmentConfig, SegmentData)],
+        input: &Value,
+    ) -> bool {
+        self.resolve(own, collected, input)
+            .is_some_and(|actual| self.compare(&actual))
+    }
+
+    fn resolve(
+        &self,
+        own: &SegmentData,
+        collected: &[(SegmentConfig, SegmentData)],
+        input: &Value,
+    ) -> Option<Value> {
+        if let Some(value) = own.metadata.get(&self.field) {
+            return Some(Value::String(value.clone()));
+        }
+
+        if let Some((id, key)) = self.field.split_once('.') {
+            let id: Option<SegmentId> = serde_json::from_value(Value::String(id.to_string())).ok();
+            if let Some(value) = collected
+                .iter()
+                .filter(|(config, _)| Some(config.id) == id)
+                .find_map(|(_, data)| data.metadata.get(key))
+            {
+                return Some(Value::String(value.clone()));
+            }
+        }
+
+        self.field
+            .split('.')
+            .try_fold(input, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+            .cloned()
+    }
+
+    /// Numbers (including numeric strings) compare numerically, anything else as text
+    fn compare(&self, actual: &Value) -> bool {
+        let as_number = |value: &Value| match value {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        };
+        let as_text = |value: &Value| match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+
+        let ordering = match (as_number(actual), as_number(&self.value)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => Some(as_text(actual).cmp(&as_text(&self.value))),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self.comparator {
+            Comparator::Eq => ordering.is_eq(),
+            Comparator::Ne => ordering.is_ne(),
+            Comparator::Gt => ordering.is_gt(),
+            Comparator::Ge => ordering.is_ge(),
+            Comparator::Lt => ordering.is_lt(),
+            Comparator::Le => ordering.is_le(),
+        }
+    }
+}
+
+/// Drop segments whose condition does not hold. Runs after collection so conditions
+/// can refer to any segment's metadata; a malformed condition leaves the segment shown.
+pub fn apply_conditions(
+    collected: Vec<(SegmentConfig, SegmentData)>,
+    input: &InputData,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    let input = serde_json::to_value(input).unwrap_or(Value::Null);
+    let keep: Vec<bool> = collected
+        .iter()
+        .map(|(config, data)| match Condition::from_options(config) {
+            Ok(Some(condition)) => condition.matches(data, &collected, &input),
+            _ => true,
+        })
+        .collect();
+
+    collected
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(segment, keep)| keep.then_some(segment))
+        .collect()
+}
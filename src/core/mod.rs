@@ -1,4 +1,7 @@
+pub mod cache;
+pub mod condition;
 pub mod segments;
+pub mod state;
 pub mod statusline;
 
 pub use statusline::{collect_all_segments, StatusLineGenerator};
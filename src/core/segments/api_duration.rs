@@ -0,0 +1,107 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Cached totals, pruned after this many days without an update
+const CACHE_RETENTION_DAYS: i64 = 7;
+
+/// The last API total seen for a session and how much the latest turn added to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApiDurationEntry {
+    total_ms: u64,
+    delta_ms: Option<u64>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct ApiDurationSegment;
+
+impl ApiDurationSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Store `total_ms` and return the latest turn's delta. The statusline renders many
+    /// times per turn, so the delta is kept until the total moves again; the first
+    /// render of a session has nothing to compare against and yields no delta.
+    fn track_delta(cache_key: String, total_ms: u64) -> Option<u64> {
+        let file = StateFile::new(".api_duration_cache.json")?;
+        let cache: HashMap<String, ApiDurationEntry> = file.load();
+        if let Some(entry) = cache
+            .get(&cache_key)
+            .filter(|entry| entry.total_ms == total_ms)
+        {
+            return entry.delta_ms;
+        }
+
+        file.update(|cache: &mut HashMap<String, ApiDurationEntry>| {
+            let previous = cache.get(&cache_key);
+            if let Some(entry) = previous.filter(|entry| entry.total_ms == total_ms) {
+                return entry.delta_ms;
+            }
+
+            let delta_ms = previous
+                .and_then(|entry| total_ms.checked_sub(entry.total_ms))
+                .filter(|delta| *delta > 0);
+
+            let now = Utc::now();
+            let cutoff = now - Duration::days(CACHE_RETENTION_DAYS);
+            cache.retain(|_, entry| entry.updated_at > cutoff);
+            cache.insert(
+                cache_key,
+                ApiDurationEntry {
+                    total_ms,
+                    delta_ms,
+                    updated_at: now,
+                },
+            );
+
+            delta_ms
+        })
+    }
+
+    /// 850ms, 8.3s, 4m 12s, 1h 5m
+    fn format_duration(ms: u64) -> String {
+        if ms < 1000 {
+            format!("{}ms", ms)
+        } else if ms < 60_000 {
+            format!("{:.1}s", ms as f64 / 1000.0)
+        } else if ms < 3_600_000 {
+            format!("{}m {}s", ms / 60_000, (ms % 60_000) / 1000)
+        } else {
+            format!("{}h {}m", ms / 3_600_000, (ms % 3_600_000) / 60_000)
+        }
+    }
+}
+
+impl Segment for ApiDurationSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let total_ms = input.cost.as_ref()?.total_api_duration_ms?;
+        let cache_key = input
+            .session_id
+            .clone()
+            .unwrap_or_else(|| input.transcript_path.clone());
+        let delta_ms = Self::track_delta(cache_key, total_ms);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("api_duration_ms".to_string(), total_ms.to_string());
+        if let Some(delta_ms) = delta_ms {
+            metadata.insert("delta_ms".to_string(), delta_ms.to_string());
+        }
+
+        Some(SegmentData {
+            primary: Self::format_duration(total_ms),
+            secondary: delta_ms
+                .map(|delta| format!("+{}", Self::format_duration(delta)))
+                .unwrap_or_default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::ApiDuration
+    }
+}
@@ -0,0 +1,184 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::ini::{parse_ini, IniSections};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Credentials expiring sooner than this render in the critical color
+const EXPIRY_CRITICAL_MINUTES: i64 = 10;
+
+pub struct AwsSegment {
+    always_show: bool,
+    show_expiry: bool,
+    show_region: bool,
+    prefix: String,
+}
+
+impl Default for AwsSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AwsSegment {
+    pub fn new() -> Self {
+        Self {
+            always_show: false,
+            show_expiry: true,
+            show_region: true,
+            prefix: "aws:".to_string(),
+        }
+    }
+
+    /// Show the "default" profile even when no profile env var is set
+    pub fn with_always_show(mut self, always_show: bool) -> Self {
+        self.always_show = always_show;
+        self
+    }
+
+    pub fn with_expiry(mut self, show_expiry: bool) -> Self {
+        self.show_expiry = show_expiry;
+        self
+    }
+
+    pub fn with_region(mut self, show_region: bool) -> Self {
+        self.show_region = show_region;
+        self
+    }
+
+    /// Label rendered before the profile, "aws:" by default; "" drops it
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn env_value(names: &[&str]) -> Option<String> {
+        names
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+    }
+
+    fn aws_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".aws"))
+    }
+
+    fn load_config() -> IniSections {
+        let path = Self::env_value(&["AWS_CONFIG_FILE"])
+            .map(PathBuf::from)
+            .or_else(|| Self::aws_dir().map(|dir| dir.join("config")));
+
+        path.and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| parse_ini(&content))
+            .unwrap_or_default()
+    }
+
+    /// ~/.aws/config names sections "[default]" and "[profile <name>]"
+    fn profile_section<'a>(
+        config: &'a IniSections,
+        profile: &str,
+    ) -> Option<&'a HashMap<String, String>> {
+        if profile == "default" {
+            config
+                .get("default")
+                .or_else(|| config.get("profile default"))
+        } else {
+            config.get(&format!("profile {}", profile))
+        }
+    }
+
+    /// Expiry from AWS_SESSION_EXPIRATION, else the SSO token cached for the profile's start URL
+    fn expiration(config: &IniSections, profile: &str) -> Option<DateTime<Utc>> {
+        if let Some(expiration) = Self::env_value(&["AWS_SESSION_EXPIRATION"]) {
+            return DateTime::parse_from_rfc3339(&expiration)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc));
+        }
+
+        let section = Self::profile_section(config, profile)?;
+        let start_url = match section.get("sso_session") {
+            Some(session) => config
+                .get(&format!("sso-session {}", session))?
+                .get("sso_start_url")?,
+            None => section.get("sso_start_url")?,
+        };
+
+        let cache_dir = Self::aws_dir()?.join("sso").join("cache");
+        fs::read_dir(cache_dir)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+            .filter_map(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .filter(|token| token.get("startUrl").and_then(|v| v.as_str()) == Some(start_url))
+            .filter_map(|token| {
+                let expires_at = token.get("expiresAt")?.as_str()?;
+                DateTime::parse_from_rfc3339(expires_at)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            })
+            .max()
+    }
+
+    fn format_remaining(remaining: chrono::Duration) -> String {
+        let minutes = remaining.num_minutes();
+        if minutes <= 0 {
+            "expired".to_string()
+        } else if minutes < 60 {
+            format!("{}m", minutes)
+        } else {
+            format!("{}h{:02}m", minutes / 60, minutes % 60)
+        }
+    }
+}
+
+impl Segment for AwsSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        // aws-vault exports the profile it assumed as AWS_VAULT
+        let profile = match Self::env_value(&["AWS_PROFILE", "AWS_DEFAULT_PROFILE", "AWS_VAULT"]) {
+            Some(profile) => profile,
+            None if self.always_show => "default".to_string(),
+            None => return None,
+        };
+
+        let config = Self::load_config();
+        let region = Self::env_value(&["AWS_REGION", "AWS_DEFAULT_REGION"]).or_else(|| {
+            Self::profile_section(&config, &profile)
+                .and_then(|section| section.get("region").cloned())
+        });
+
+        let mut metadata = HashMap::new();
+        metadata.insert("profile".to_string(), profile.clone());
+        if let Some(ref region) = region {
+            metadata.insert("region".to_string(), region.clone());
+        }
+
+        let mut secondary_parts = Vec::new();
+        if let Some(region) = region.filter(|_| self.show_region) {
+            secondary_parts.push(region);
+        }
+
+        if self.show_expiry {
+            if let Some(expiration) = Self::expiration(&config, &profile) {
+                let remaining = expiration.signed_duration_since(Utc::now());
+                metadata.insert("expires_at".to_string(), expiration.to_rfc3339());
+                if remaining.num_minutes() < EXPIRY_CRITICAL_MINUTES {
+                    metadata.insert("level".to_string(), "critical".to_string());
+                }
+                secondary_parts.push(Self::format_remaining(remaining));
+            }
+        }
+
+        Some(SegmentData {
+            primary: format!("{}{}", self.prefix, profile),
+            secondary: secondary_parts.join(" "),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Aws
+    }
+}
@@ -0,0 +1,200 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatteryInfo {
+    pub percentage: u8,
+    pub state: BatteryState,
+}
+
+/// Battery reading shared by every caller within a single invocation
+static BATTERY_INFO: OnceLock<Option<BatteryInfo>> = OnceLock::new();
+
+pub struct BatterySegment {
+    warning_threshold: u8,
+    critical_threshold: u8,
+}
+
+impl Default for BatterySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatterySegment {
+    pub fn new() -> Self {
+        Self {
+            warning_threshold: 20,
+            critical_threshold: 10,
+        }
+    }
+
+    /// Percentages below these render in the warning/critical color while discharging
+    pub fn with_thresholds(mut self, warning_threshold: u8, critical_threshold: u8) -> Self {
+        self.warning_threshold = warning_threshold;
+        self.critical_threshold = critical_threshold;
+        self
+    }
+
+    /// Read the battery state once per process, returns None on machines without a battery
+    pub fn battery_info() -> Option<BatteryInfo> {
+        BATTERY_INFO.get_or_init(Self::read_battery_info).clone()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_battery_info() -> Option<BatteryInfo> {
+        let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let supply_type = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            if supply_type.trim() != "Battery" {
+                continue;
+            }
+
+            let percentage = std::fs::read_to_string(path.join("capacity"))
+                .ok()
+                .and_then(|s| s.trim().parse::<u8>().ok())?;
+            let status = std::fs::read_to_string(path.join("status")).unwrap_or_default();
+
+            return Some(BatteryInfo {
+                percentage: percentage.min(100),
+                state: Self::parse_state(status.trim()),
+            });
+        }
+
+        None
+    }
+
+    #[cfg(target_os = "macos")]
+    fn read_battery_info() -> Option<BatteryInfo> {
+        use std::process::Command;
+
+        // Example line: " -InternalBattery-0 (id=1234)	82%; charging; 1:23 remaining present: true"
+        let output = Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let line = text.lines().find(|line| line.contains("InternalBattery"))?;
+        let mut fields = line.split('\t').nth(1)?.split(';').map(str::trim);
+
+        let percentage = fields.next()?.trim_end_matches('%').parse::<u8>().ok()?;
+        let state = Self::parse_state(fields.next().unwrap_or(""));
+
+        Some(BatteryInfo {
+            percentage: percentage.min(100),
+            state,
+        })
+    }
+
+    #[cfg(windows)]
+    fn read_battery_info() -> Option<BatteryInfo> {
+        #[repr(C)]
+        #[derive(Default)]
+        struct SystemPowerStatus {
+            ac_line_status: u8,
+            battery_flag: u8,
+            battery_life_percent: u8,
+            system_status_flag: u8,
+            battery_life_time: u32,
+            battery_full_life_time: u32,
+        }
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+        }
+
+        const BATTERY_FLAG_CHARGING: u8 = 8;
+        const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+        const UNKNOWN: u8 = 255;
+
+        let mut status = SystemPowerStatus::default();
+        // SAFETY: `status` is a valid, properly laid out SYSTEM_POWER_STATUS
+        if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+            return None;
+        }
+
+        if status.battery_flag == UNKNOWN
+            || status.battery_flag & BATTERY_FLAG_NO_BATTERY != 0
+            || status.battery_life_percent == UNKNOWN
+        {
+            return None;
+        }
+
+        let percentage = status.battery_life_percent.min(100);
+        let state = if status.battery_flag & BATTERY_FLAG_CHARGING != 0 {
+            BatteryState::Charging
+        } else if status.ac_line_status == 1 && percentage == 100 {
+            BatteryState::Full
+        } else if status.ac_line_status == 0 {
+            BatteryState::Discharging
+        } else {
+            BatteryState::Unknown
+        };
+
+        Some(BatteryInfo { percentage, state })
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    fn read_battery_info() -> Option<BatteryInfo> {
+        None
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn parse_state(status: &str) -> BatteryState {
+        match status.to_lowercase().as_str() {
+            "charging" | "ac attached" => BatteryState::Charging,
+            "discharging" | "not charging" => BatteryState::Discharging,
+            "full" | "charged" => BatteryState::Full,
+            _ => BatteryState::Unknown,
+        }
+    }
+}
+
+impl Segment for BatterySegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let info = Self::battery_info()?;
+
+        let secondary = match info.state {
+            BatteryState::Charging => "⚡".to_string(),
+            _ => String::new(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("percentage".to_string(), info.percentage.to_string());
+        metadata.insert("state".to_string(), format!("{:?}", info.state));
+        if matches!(
+            info.state,
+            BatteryState::Discharging | BatteryState::Unknown
+        ) {
+            if info.percentage < self.critical_threshold {
+                metadata.insert("level".to_string(), "critical".to_string());
+            } else if info.percentage < self.warning_threshold {
+                metadata.insert("level".to_string(), "warning".to_string());
+            }
+        }
+
+        Some(SegmentData {
+            primary: format!("{}%", info.percentage),
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Battery
+    }
+}
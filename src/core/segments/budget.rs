@@ -0,0 +1,91 @@
+use super::cost_ledger::CostLedger;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+/// Today's spend across all sessions against a daily budget
+pub struct BudgetSegment {
+    daily_limit: f64,
+    warning_percent: f64,
+    critical_percent: f64,
+    timezone: String,
+}
+
+impl Default for BudgetSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BudgetSegment {
+    pub fn new() -> Self {
+        Self {
+            daily_limit: 10.0,
+            warning_percent: 80.0,
+            critical_percent: 100.0,
+            timezone: "local".to_string(),
+        }
+    }
+
+    /// Budget in USD per calendar day
+    pub fn with_daily_limit(mut self, daily_limit: f64) -> Self {
+        self.daily_limit = daily_limit;
+        self
+    }
+
+    /// Shares of the budget, in percent, at which `warning_color` and `critical_color` apply
+    pub fn with_thresholds(mut self, warning_percent: f64, critical_percent: f64) -> Self {
+        self.warning_percent = warning_percent;
+        self.critical_percent = critical_percent;
+        self
+    }
+
+    /// Timezone whose midnight starts a new day; same values as the clock segment's
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    fn format_usd(amount: f64) -> String {
+        if amount.fract() == 0.0 {
+            format!("${:.0}", amount)
+        } else {
+            format!("${:.2}", amount)
+        }
+    }
+}
+
+impl Segment for BudgetSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        if self.daily_limit <= 0.0 {
+            return None;
+        }
+        let total = input.cost.as_ref()?.total_cost_usd?;
+        let session = CostLedger::session_key(input)?;
+        let today = CostLedger::today(&self.timezone)?;
+
+        let spent = CostLedger::record(session, total, today)?.spent_on(today);
+        let percent = spent / self.daily_limit * 100.0;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("spent".to_string(), format!("{:.4}", spent));
+        metadata.insert("limit".to_string(), self.daily_limit.to_string());
+        metadata.insert("percent".to_string(), format!("{:.0}", percent));
+        metadata.insert("date".to_string(), today.to_string());
+        if percent >= self.critical_percent {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if percent >= self.warning_percent {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: format!("${:.2}/{}", spent, Self::format_usd(self.daily_limit)),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Budget
+    }
+}
@@ -0,0 +1,131 @@
+use super::context_window::parse_transcript_usage;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Sessions without a new sample for this long are dropped from the state file
+const STATE_RETENTION_HOURS: i64 = 24;
+
+/// Token totals observed per session, oldest first
+type BurnRateState = HashMap<String, Vec<(DateTime<Utc>, u32)>>;
+
+pub struct BurnRateSegment {
+    window: Duration,
+    warning_threshold: f64,
+    critical_threshold: f64,
+}
+
+impl Default for BurnRateSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BurnRateSegment {
+    pub fn new() -> Self {
+        Self {
+            window: Duration::minutes(10),
+            warning_threshold: 5000.0,
+            critical_threshold: 10000.0,
+        }
+    }
+
+    /// Length of the sliding window the rate is averaged over
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Cutoffs in tokens per minute
+    pub fn with_thresholds(mut self, warning_threshold: f64, critical_threshold: f64) -> Self {
+        self.warning_threshold = warning_threshold;
+        self.critical_threshold = critical_threshold;
+        self
+    }
+
+    /// Record `tokens` and return tokens per minute over the window. Samples are only
+    /// stored when the total changes, so the total at any moment is the latest sample
+    /// before it; one sample older than the window is kept as its starting point.
+    fn record_and_rate(&self, session: String, tokens: u32, now: DateTime<Utc>) -> Option<f64> {
+        let window_start = now - self.window;
+        let retention_cutoff = now - Duration::hours(STATE_RETENTION_HOURS);
+
+        let (start_at, start_tokens) =
+            StateFile::new(".burn_rate.json")?.update(|state: &mut BurnRateState| {
+                state.retain(|_, samples| {
+                    samples.last().is_some_and(|(at, _)| *at > retention_cutoff)
+                });
+
+                let samples = state.entry(session).or_default();
+                match samples.last() {
+                    // The context shrank (compaction or /clear): start measuring afresh
+                    Some((_, last)) if tokens < *last => {
+                        samples.clear();
+                        samples.push((now, tokens));
+                    }
+                    Some((_, last)) if tokens == *last => {}
+                    _ => samples.push((now, tokens)),
+                }
+
+                let first_in_window = samples
+                    .iter()
+                    .position(|(at, _)| *at > window_start)
+                    .unwrap_or(samples.len());
+                samples.drain(..first_in_window.saturating_sub(1));
+
+                samples.first().copied()
+            })?;
+
+        let elapsed = now - start_at.max(window_start);
+        // Less than a minute of history gives a meaningless rate
+        if elapsed < Duration::minutes(1) {
+            return None;
+        }
+
+        let minutes = elapsed.num_seconds() as f64 / 60.0;
+        Some((tokens - start_tokens) as f64 / minutes)
+    }
+
+    fn format_rate(rate: f64) -> String {
+        if rate >= 1000.0 {
+            format!("{:.1}k/min", rate / 1000.0)
+        } else {
+            format!("{:.0}/min", rate)
+        }
+    }
+}
+
+impl Segment for BurnRateSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let tokens = parse_transcript_usage(&input.transcript_path)?;
+        let session = input
+            .session_id
+            .clone()
+            .unwrap_or_else(|| input.transcript_path.clone());
+        let rate = self.record_and_rate(session, tokens, Utc::now())?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("tokens_per_minute".to_string(), format!("{:.0}", rate));
+        metadata.insert(
+            "window_minutes".to_string(),
+            self.window.num_minutes().to_string(),
+        );
+        if rate >= self.critical_threshold {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if rate >= self.warning_threshold {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: Self::format_rate(rate),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::BurnRate
+    }
+}
@@ -0,0 +1,188 @@
+use super::git::GIT_TIMEOUT;
+use super::{GitSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Runs fetched per commit; enough to cover re-runs of a handful of workflows
+const RUN_LIMIT: &str = "50";
+
+/// One GitHub Actions run as reported by `gh run list`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkflowRun {
+    workflow_name: String,
+    status: String,
+    #[serde(default)]
+    conclusion: String,
+}
+
+/// Outcome of the latest CI runs for HEAD, from `gh run list`
+pub struct CiStatusSegment {
+    workflows: Vec<String>,
+    timeout: Duration,
+    cache_ttl: Duration,
+}
+
+impl Default for CiStatusSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CiStatusSegment {
+    pub fn new() -> Self {
+        Self {
+            workflows: Vec::new(),
+            timeout: Duration::from_millis(1500),
+            cache_ttl: Duration::from_secs(120),
+        }
+    }
+
+    /// Only consider runs of these workflows (by name); empty considers all
+    pub fn with_workflows(mut self, workflows: Vec<String>) -> Self {
+        self.workflows = workflows;
+        self
+    }
+
+    /// `gh` is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long the runs for a commit, including a failed lookup, are reused
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    fn head_sha(repo_root: &Path) -> Option<String> {
+        let output = Command::new("git")
+            .args(["--no-optional-locks", "rev-parse", "HEAD"])
+            .current_dir(repo_root)
+            .output_with_timeout(GIT_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (!sha.is_empty()).then_some(sha)
+    }
+
+    fn query(&self, repo_root: &Path, sha: &str) -> Option<Vec<WorkflowRun>> {
+        let output = Command::new("gh")
+            .args([
+                "run",
+                "list",
+                "--commit",
+                sha,
+                "--limit",
+                RUN_LIMIT,
+                "--json",
+                "workflowName,status,conclusion",
+            ])
+            .current_dir(repo_root)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        serde_json::from_slice(&output.stdout).ok()
+    }
+}
+
+impl Segment for CiStatusSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (root, _) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let sha = Self::head_sha(&root)?;
+        let runs = self.query(&root, &sha)?;
+
+        // Runs come newest first, so the first of each workflow is its latest attempt
+        let mut latest: Vec<&WorkflowRun> = Vec::new();
+        for run in &runs {
+            let wanted = self.workflows.is_empty() || self.workflows.contains(&run.workflow_name);
+            if wanted
+                && !latest
+                    .iter()
+                    .any(|seen| seen.workflow_name == run.workflow_name)
+            {
+                latest.push(run);
+            }
+        }
+        if latest.is_empty() {
+            return None;
+        }
+
+        let failed: Vec<&str> = latest
+            .iter()
+            .filter(|run| {
+                run.status == "completed"
+                    && matches!(
+                        run.conclusion.as_str(),
+                        "failure"
+                            | "cancelled"
+                            | "timed_out"
+                            | "action_required"
+                            | "startup_failure"
+                    )
+            })
+            .map(|run| run.workflow_name.as_str())
+            .collect();
+        let running = latest.iter().any(|run| run.status != "completed");
+
+        // Failures show as soon as they happen, even while other workflows still run
+        let (state, label, level, icon) = if !failed.is_empty() {
+            ("failure", "failed", "critical", "\u{f0159}")
+        } else if running {
+            ("in_progress", "running", "warning", "\u{f0996}")
+        } else {
+            ("success", "passed", "success", "\u{f05e0}")
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("state".to_string(), state.to_string());
+        metadata.insert("level".to_string(), level.to_string());
+        metadata.insert("dynamic_icon".to_string(), icon.to_string());
+        metadata.insert("sha".to_string(), sha);
+        metadata.insert("workflows".to_string(), latest.len().to_string());
+        if !failed.is_empty() {
+            metadata.insert("failed".to_string(), failed.join(","));
+        }
+
+        Some(SegmentData {
+            primary: label.to_string(),
+            secondary: failed
+                .first()
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::CiStatus
+    }
+
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        if self.cache_ttl.is_zero() {
+            return None;
+        }
+        let (root, _) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let sha = Self::head_sha(&root)?;
+        Some(format!(
+            "{}\0{}\0{}",
+            root.to_string_lossy(),
+            sha,
+            self.workflows.join("\0")
+        ))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
@@ -0,0 +1,100 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{FixedOffset, Local, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+/// Timezone a clock segment renders in
+#[derive(Debug, Clone, Copy)]
+pub enum ClockTimezone {
+    Local,
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+pub struct ClockSegment {
+    format: String,
+    timezone: String,
+}
+
+impl Default for ClockSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockSegment {
+    pub fn new() -> Self {
+        Self {
+            format: "%H:%M".to_string(),
+            timezone: "local".to_string(),
+        }
+    }
+
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = format.into();
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Check that a strftime format string contains no invalid specifiers
+    pub fn is_valid_format(format: &str) -> bool {
+        !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+    }
+
+    /// Parse a timezone option: "local", "utc", a fixed offset like "+08:00"
+    /// or an IANA name like "Europe/Berlin"
+    pub fn parse_timezone(timezone: &str) -> Option<ClockTimezone> {
+        match timezone.to_lowercase().as_str() {
+            "" | "local" => Some(ClockTimezone::Local),
+            "utc" | "z" => FixedOffset::east_opt(0).map(ClockTimezone::Fixed),
+            _ => {
+                if let Ok(offset) = timezone.parse::<FixedOffset>() {
+                    Some(ClockTimezone::Fixed(offset))
+                } else {
+                    timezone.parse::<Tz>().ok().map(ClockTimezone::Named)
+                }
+            }
+        }
+    }
+}
+
+impl Segment for ClockSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        // Never render garbage (or panic inside chrono) for a bad format
+        if !Self::is_valid_format(&self.format) {
+            return None;
+        }
+
+        let primary = match Self::parse_timezone(&self.timezone)? {
+            ClockTimezone::Local => Local::now().format(&self.format).to_string(),
+            ClockTimezone::Fixed(offset) => Utc::now()
+                .with_timezone(&offset)
+                .format(&self.format)
+                .to_string(),
+            ClockTimezone::Named(tz) => Utc::now()
+                .with_timezone(&tz)
+                .format(&self.format)
+                .to_string(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("format".to_string(), self.format.clone());
+        metadata.insert("timezone".to_string(), self.timezone.clone());
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Clock
+    }
+}
@@ -0,0 +1,107 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::Duration;
+
+/// Shows the first line of an external program's output
+pub struct CustomCommandSegment {
+    command: String,
+    args: Vec<String>,
+    timeout: Duration,
+    cache_ttl: Duration,
+}
+
+impl Default for CustomCommandSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomCommandSegment {
+    pub fn new() -> Self {
+        Self {
+            command: String::new(),
+            args: Vec::new(),
+            timeout: Duration::from_millis(500),
+            cache_ttl: Duration::ZERO,
+        }
+    }
+
+    pub fn with_command(mut self, command: impl Into<String>, args: Vec<String>) -> Self {
+        self.command = command.into();
+        self.args = args;
+        self
+    }
+
+    /// The command is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long a result is reused across invocations (zero runs the command every time)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// First non-empty stdout line, or `None` on spawn failure, timeout or non-zero exit
+    fn run(&self, working_dir: &str) -> Option<String> {
+        let output = Command::new(&self.command)
+            .args(&self.args)
+            .current_dir(working_dir)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    }
+}
+
+impl Segment for CustomCommandSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        if self.command.trim().is_empty() {
+            return None;
+        }
+
+        let primary = self.run(&input.workspace.current_dir)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("command".to_string(), self.command.clone());
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Command
+    }
+
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        if self.cache_ttl.is_zero() {
+            return None;
+        }
+        Some(format!(
+            "{}\0{}\0{}",
+            input.workspace.current_dir,
+            self.command,
+            self.args.join("\0")
+        ))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
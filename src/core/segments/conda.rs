@@ -0,0 +1,61 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+
+#[derive(Default)]
+pub struct CondaSegment {
+    show_base: bool,
+}
+
+impl CondaSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_base(mut self, show_base: bool) -> Self {
+        self.show_base = show_base;
+        self
+    }
+
+    /// Prefix of the active conda environment, if any
+    pub fn active_prefix() -> Option<PathBuf> {
+        env::var_os("CONDA_PREFIX")
+            .filter(|prefix| !prefix.is_empty())
+            .map(PathBuf::from)
+    }
+}
+
+impl Segment for CondaSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let name = env::var("CONDA_DEFAULT_ENV")
+            .ok()
+            .filter(|name| !name.is_empty())?;
+
+        if name == "base" && !self.show_base {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        if let Some(prefix) = Self::active_prefix() {
+            metadata.insert("env_path".to_string(), prefix.display().to_string());
+        }
+
+        // Envs activated by path report the full prefix as their name
+        let primary = PathBuf::from(&name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or(name);
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Conda
+    }
+}
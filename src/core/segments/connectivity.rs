@@ -0,0 +1,122 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Warns when the Claude API can't be reached, e.g. after a VPN drops
+pub struct ConnectivitySegment {
+    target: String,
+    timeout: Duration,
+    interval: Duration,
+    always_show: bool,
+}
+
+impl Default for ConnectivitySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectivitySegment {
+    pub fn new() -> Self {
+        Self {
+            target: "api.anthropic.com:443".to_string(),
+            timeout: Duration::from_millis(200),
+            interval: Duration::from_secs(30),
+            always_show: false,
+        }
+    }
+
+    /// `host:port` to open a TCP connection to
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// Time allowed for name resolution and the connection together
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long a probe result is reused before probing again
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Show "online" too instead of hiding the segment while reachable
+    pub fn with_always_show(mut self, always_show: bool) -> Self {
+        self.always_show = always_show;
+        self
+    }
+
+    /// Connect time when reachable. Resolution has no timeout of its own and can hang
+    /// for seconds without a network, so the probe runs on a thread that is abandoned
+    /// once `timeout` passes.
+    fn probe(&self) -> Option<Duration> {
+        let (sender, receiver) = mpsc::channel();
+        let target = self.target.clone();
+        let timeout = self.timeout;
+
+        thread::spawn(move || {
+            let started = Instant::now();
+            let connected = target.to_socket_addrs().ok().and_then(|addrs| {
+                addrs.into_iter().find_map(|addr| {
+                    let remaining = timeout.checked_sub(started.elapsed())?;
+                    TcpStream::connect_timeout(&addr, remaining).ok()
+                })
+            });
+            let _ = sender.send(connected.map(|_| started.elapsed()));
+        });
+
+        receiver.recv_timeout(self.timeout).ok().flatten()
+    }
+}
+
+impl Segment for ConnectivitySegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let latency = self.probe();
+        if latency.is_some() && !self.always_show {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("target".to_string(), self.target.clone());
+        metadata.insert("online".to_string(), latency.is_some().to_string());
+
+        let primary = match latency {
+            Some(latency) => {
+                metadata.insert("latency_ms".to_string(), latency.as_millis().to_string());
+                "online".to_string()
+            }
+            None => {
+                metadata.insert("level".to_string(), "critical".to_string());
+                metadata.insert("dynamic_icon".to_string(), "⚠".to_string());
+                "offline".to_string()
+            }
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Connectivity
+    }
+
+    /// Probing on every render would add its timeout to each render while offline
+    fn cache_key(&self, _input: &InputData) -> Option<String> {
+        Some(format!("{}\0{}", self.target, self.always_show))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.interval
+    }
+}
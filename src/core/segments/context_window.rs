@@ -1,16 +1,30 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, ModelConfig, SegmentId, TranscriptEntry};
+use crate::utils::abbreviate_number;
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-#[derive(Default)]
-pub struct ContextWindowSegment;
+pub struct ContextWindowSegment {
+    precision: usize,
+}
+
+impl Default for ContextWindowSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ContextWindowSegment {
     pub fn new() -> Self {
-        Self
+        Self { precision: 1 }
+    }
+
+    /// Most decimals in abbreviated token counts such as "12.5k" (0-2)
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
     }
 
     /// Get context limit for the specified model
@@ -25,54 +39,37 @@ impl Segment for ContextWindowSegment {
         // Dynamically determine context limit based on current model ID
         let context_limit = Self::get_context_limit_for_model(&input.model.id);
 
-        let context_used_token_opt = parse_transcript_usage(&input.transcript_path);
-
-        let (percentage_display, tokens_display) = match context_used_token_opt {
-            Some(context_used_token) => {
-                let context_used_rate = (context_used_token as f64 / context_limit as f64) * 100.0;
+        // A zero limit (e.g. misconfigured models.toml) would divide by zero
+        if context_limit == 0 {
+            return None;
+        }
 
-                let percentage = if context_used_rate.fract() == 0.0 {
-                    format!("{:.0}%", context_used_rate)
-                } else {
-                    format!("{:.1}%", context_used_rate)
-                };
+        // Without usage data there is nothing to show
+        let context_used_token = parse_transcript_usage(&input.transcript_path)?;
 
-                let tokens = if context_used_token >= 1000 {
-                    let k_value = context_used_token as f64 / 1000.0;
-                    if k_value.fract() == 0.0 {
-                        format!("{}k", k_value as u32)
-                    } else {
-                        format!("{:.1}k", k_value)
-                    }
-                } else {
-                    context_used_token.to_string()
-                };
+        // Clamp at 100% so overflowing transcripts don't render nonsense like 134%
+        let context_used_rate =
+            ((context_used_token as f64 / context_limit as f64) * 100.0).min(100.0);
 
-                (percentage, tokens)
-            }
-            None => {
-                // No usage data available
-                ("-".to_string(), "-".to_string())
-            }
+        let percentage_display = if context_used_rate.fract() == 0.0 {
+            format!("{:.0}%", context_used_rate)
+        } else {
+            format!("{:.1}%", context_used_rate)
         };
+        let tokens_display = abbreviate_number(context_used_token as f64, self.precision);
+        let limit_display = abbreviate_number(context_limit as f64, self.precision);
 
         let mut metadata = HashMap::new();
-        match context_used_token_opt {
-            Some(context_used_token) => {
-                let context_used_rate = (context_used_token as f64 / context_limit as f64) * 100.0;
-                metadata.insert("tokens".to_string(), context_used_token.to_string());
-                metadata.insert("percentage".to_string(), context_used_rate.to_string());
-            }
-            None => {
-                metadata.insert("tokens".to_string(), "-".to_string());
-                metadata.insert("percentage".to_string(), "-".to_string());
-            }
-        }
-        metadata.insert("limit".to_string(), context_limit.to_string());
+        metadata.insert("used_tokens".to_string(), context_used_token.to_string());
+        metadata.insert("max_tokens".to_string(), context_limit.to_string());
+        metadata.insert("percent".to_string(), context_used_rate.to_string());
         metadata.insert("model".to_string(), input.model.id.clone());
 
         Some(SegmentData {
-            primary: format!("{} · {} tokens", percentage_display, tokens_display),
+            primary: format!(
+                "{} ({}/{})",
+                percentage_display, tokens_display, limit_display
+            ),
             secondary: String::new(),
             metadata,
         })
@@ -83,7 +80,7 @@ impl Segment for ContextWindowSegment {
     }
 }
 
-fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Option<u32> {
+pub(crate) fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Option<u32> {
     let path = transcript_path.as_ref();
 
     // Try to parse from current transcript file
@@ -270,3 +267,44 @@ fn try_find_usage_from_project_history(transcript_path: &Path) -> Option<u32> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_without_usage_data() {
+        let input = InputData {
+            transcript_path: "/nonexistent/ccline-test/transcript.jsonl".to_string(),
+            ..InputData::default()
+        };
+        assert!(ContextWindowSegment::new().collect(&input).is_none());
+    }
+
+    #[test]
+    fn shows_used_and_max_tokens() {
+        let dir = std::env::temp_dir().join(format!("ccline-context-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let transcript = dir.join("transcript.jsonl");
+        fs::write(
+            &transcript,
+            r#"{"type":"assistant","message":{"usage":{"input_tokens":100,"cache_read_input_tokens":1000,"output_tokens":5}}}"#,
+        )
+        .unwrap();
+        let input = InputData {
+            transcript_path: transcript.to_string_lossy().to_string(),
+            ..InputData::default()
+        };
+
+        let data = ContextWindowSegment::new().collect(&input).unwrap();
+        let max_tokens = &data.metadata["max_tokens"];
+        assert_eq!(data.metadata["used_tokens"], "1105");
+        assert!(data.metadata.contains_key("percent"));
+        assert!(!data.metadata.contains_key("percentage"));
+        assert!(data.primary.ends_with(&format!(
+            " (1.1k/{})",
+            abbreviate_number(max_tokens.parse().unwrap(), 1)
+        )));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
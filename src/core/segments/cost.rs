@@ -1,13 +1,36 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use crate::utils::abbreviate_number;
 use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct CostSegment;
+pub struct CostSegment {
+    show_cents: bool,
+    precision: usize,
+}
+
+impl Default for CostSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl CostSegment {
     pub fn new() -> Self {
-        Self
+        Self {
+            show_cents: true,
+            precision: 1,
+        }
+    }
+
+    pub fn with_cents(mut self, show_cents: bool) -> Self {
+        self.show_cents = show_cents;
+        self
+    }
+
+    /// Most decimals once costs of $1000 or more are abbreviated, e.g. "$1.2k" (0-2)
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
     }
 }
 
@@ -19,8 +42,12 @@ impl Segment for CostSegment {
         let primary = if let Some(cost) = cost_data.total_cost_usd {
             if cost == 0.0 || cost < 0.01 {
                 "$0".to_string()
-            } else {
+            } else if cost >= 1000.0 {
+                format!("${}", abbreviate_number(cost, self.precision))
+            } else if self.show_cents {
                 format!("${:.2}", cost)
+            } else {
+                format!("${:.0}", cost.round())
             }
         } else {
             return None;
@@ -0,0 +1,98 @@
+use super::clock::{ClockSegment, ClockTimezone};
+use crate::config::InputData;
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Days of spend and idle sessions kept in the ledger
+const RETENTION_DAYS: i64 = 7;
+
+/// Last running total seen for a session, so each render only adds the new spend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SessionTotal {
+    total: f64,
+    updated_at: DateTime<Utc>,
+}
+
+/// Spend of every Claude Code session, persisted so segments can sum it per day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostLedger {
+    sessions: HashMap<String, SessionTotal>,
+    /// Spend per calendar day and session; keeping sessions apart means a session
+    /// rendered by several panes is never counted twice
+    days: BTreeMap<NaiveDate, HashMap<String, f64>>,
+}
+
+impl CostLedger {
+    /// Identifies the session in the ledger: its id, else its transcript path
+    pub fn session_key(input: &InputData) -> Option<String> {
+        input
+            .session_id
+            .clone()
+            .filter(|id| !id.is_empty())
+            .or_else(|| Some(input.transcript_path.clone()))
+            .filter(|key| !key.is_empty())
+    }
+
+    /// The current date in `timezone`, which takes the clock segment's values
+    pub fn today(timezone: &str) -> Option<NaiveDate> {
+        Some(match ClockSegment::parse_timezone(timezone)? {
+            ClockTimezone::Local => Local::now().date_naive(),
+            ClockTimezone::Fixed(offset) => Utc::now().with_timezone(&offset).date_naive(),
+            ClockTimezone::Named(tz) => Utc::now().with_timezone(&tz).date_naive(),
+        })
+    }
+
+    /// Add the session's spend since its last render to `today` and return the updated
+    /// ledger. When the lock can't be had in time the ledger is only read, never written.
+    pub fn record(session: String, total: f64, today: NaiveDate) -> Option<Self> {
+        let ledger = StateFile::new(".cost_ledger.json")?.update(|ledger: &mut Self| {
+            let now = Utc::now();
+
+            // A lower total means Claude Code restarted the count; all of it is new
+            let previous = ledger.sessions.get(&session).map_or(0.0, |seen| seen.total);
+            let delta = if total >= previous {
+                total - previous
+            } else {
+                total
+            };
+            if delta > 0.0 {
+                *ledger
+                    .days
+                    .entry(today)
+                    .or_default()
+                    .entry(session.clone())
+                    .or_insert(0.0) += delta;
+            }
+            ledger.sessions.insert(
+                session,
+                SessionTotal {
+                    total,
+                    updated_at: now,
+                },
+            );
+
+            let cutoff = Duration::days(RETENTION_DAYS);
+            ledger.days.retain(|day, _| today - *day < cutoff);
+            ledger
+                .sessions
+                .retain(|_, seen| now.signed_duration_since(seen.updated_at) < cutoff);
+
+            ledger.clone()
+        });
+
+        Some(ledger)
+    }
+
+    /// Spend per session on `day`
+    pub fn sessions_on(&self, day: NaiveDate) -> Option<&HashMap<String, f64>> {
+        self.days.get(&day)
+    }
+
+    /// Spend of all sessions together on `day`
+    pub fn spent_on(&self, day: NaiveDate) -> f64 {
+        self.sessions_on(day)
+            .map_or(0.0, |sessions| sessions.values().sum())
+    }
+}
@@ -0,0 +1,48 @@
+use super::{Segment, SegmentData};
+use crate::config::{expand_env_strict, InputData, SegmentId};
+use std::collections::HashMap;
+
+/// A fixed label, e.g. a machine name or project tag. `${VAR}` references are
+/// expanded when collected; an unset or empty variable hides the segment.
+pub struct CustomTextSegment {
+    text: String,
+}
+
+impl Default for CustomTextSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CustomTextSegment {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+        }
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+}
+
+impl Segment for CustomTextSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let text = expand_env_strict(&self.text)?;
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+
+        Some(SegmentData {
+            primary: text.to_string(),
+            secondary: String::new(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Custom
+    }
+}
@@ -0,0 +1,60 @@
+use super::cost_ledger::CostLedger;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+/// Today's spend summed over every Claude Code session, from the shared cost ledger
+pub struct DailyCostSegment {
+    timezone: String,
+}
+
+impl Default for DailyCostSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DailyCostSegment {
+    pub fn new() -> Self {
+        Self {
+            timezone: "local".to_string(),
+        }
+    }
+
+    /// Timezone whose midnight starts a new day; same values as the clock segment's
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+}
+
+impl Segment for DailyCostSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let total = input.cost.as_ref()?.total_cost_usd?;
+        let session = CostLedger::session_key(input)?;
+        let today = CostLedger::today(&self.timezone)?;
+
+        let ledger = CostLedger::record(session, total, today)?;
+        let sessions = ledger.sessions_on(today).cloned().unwrap_or_default();
+        let spent: f64 = sessions.values().sum();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("spent".to_string(), format!("{:.4}", spent));
+        metadata.insert("date".to_string(), today.to_string());
+        metadata.insert("sessions".to_string(), sessions.len().to_string());
+        // One `session.<id>` entry per session that spent something today
+        for (session, amount) in sessions {
+            metadata.insert(format!("session.{}", session), format!("{:.4}", amount));
+        }
+
+        Some(SegmentData {
+            primary: format!("${:.2}", spent),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::DailyCost
+    }
+}
@@ -1,9 +1,32 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
 use std::collections::HashMap;
+use std::path::Path;
+
+/// How the directory segment renders the working directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirectoryDisplay {
+    #[default]
+    Basename,
+    FullPath,
+    /// Path relative to the enclosing git repository, e.g. "src/core/segments"
+    RepoRelative,
+}
+
+impl DirectoryDisplay {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "basename" => Some(Self::Basename),
+            "full_path" => Some(Self::FullPath),
+            "repo_relative" => Some(Self::RepoRelative),
+            _ => None,
+        }
+    }
+}
 
 pub struct DirectorySegment {
-    show_full_path: bool,
+    display: DirectoryDisplay,
+    max_path_components: usize,
 }
 
 impl Default for DirectorySegment {
@@ -14,14 +37,107 @@ impl Default for DirectorySegment {
 
 impl DirectorySegment {
     pub fn new() -> Self {
-        Self { show_full_path: false }
+        Self {
+            display: DirectoryDisplay::Basename,
+            max_path_components: 0,
+        }
     }
 
     pub fn with_full_path(mut self, show_full_path: bool) -> Self {
-        self.show_full_path = show_full_path;
+        self.display = if show_full_path {
+            DirectoryDisplay::FullPath
+        } else {
+            DirectoryDisplay::Basename
+        };
+        self
+    }
+
+    pub fn with_display(mut self, display: DirectoryDisplay) -> Self {
+        self.display = display;
         self
     }
 
+    /// Abbreviate middle components once a path has more than this many (0 disables)
+    pub fn with_max_path_components(mut self, max_path_components: usize) -> Self {
+        self.max_path_components = max_path_components;
+        self
+    }
+
+    /// Replace a leading home directory with "~", the home directory itself becomes "~"
+    fn collapse_home(path: &str) -> String {
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_default();
+        let home = home.trim_end_matches(['/', '\\']);
+
+        if !home.is_empty() {
+            if let Some(rest) = path.strip_prefix(home) {
+                if rest.is_empty() || rest.starts_with(['/', '\\']) {
+                    return format!("~{}", rest);
+                }
+            }
+        }
+
+        path.to_string()
+    }
+
+    /// Past `max_components`, shorten every component but the first and last
+    /// to its first character: "~/p/c/s/segments"
+    fn shorten_path(path: &str, max_components: usize) -> String {
+        let separator = if path.contains('\\') && !path.contains('/') {
+            '\\'
+        } else {
+            '/'
+        };
+        let components: Vec<&str> = path.split(separator).collect();
+        if max_components == 0 || components.len() <= max_components {
+            return path.to_string();
+        }
+
+        let last = components.len() - 1;
+        components
+            .iter()
+            .enumerate()
+            .map(|(i, component)| {
+                if i == 0 || i == last {
+                    component.to_string()
+                } else {
+                    // Keep the dot of hidden directories so ".config" becomes ".c"
+                    let take = if component.starts_with('.') { 2 } else { 1 };
+                    component.chars().take(take).collect()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&separator.to_string())
+    }
+
+    /// Path relative to the nearest ancestor containing `.git`, None outside a repository
+    fn repo_relative_path(path: &str) -> Option<String> {
+        let path = Path::new(path);
+        let root = path.ancestors().find(|dir| dir.join(".git").exists())?;
+        let relative = path.strip_prefix(root).ok()?;
+
+        if relative.as_os_str().is_empty() {
+            // At the repository root itself, show the repository name
+            root.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        } else {
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        }
+    }
+
+    /// Drop the Windows extended-length prefix from canonicalized paths:
+    /// `\\?\C:\proj` becomes `C:\proj` and `\\?\UNC\server\share` becomes `\\server\share`
+    fn strip_verbatim_prefix(path: &str) -> String {
+        if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+            format!(r"\\{}", rest)
+        } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+            rest.to_string()
+        } else {
+            path.to_string()
+        }
+    }
+
     /// Extract directory name from path, handling both Unix and Windows separators
     fn extract_directory_name(path: &str) -> String {
         // Handle Windows drive root (e.g., "D:", "D:/", "D:\")
@@ -56,18 +172,20 @@ impl DirectorySegment {
 
 impl Segment for DirectorySegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let current_dir = &input.workspace.current_dir;
+        let current_dir = &Self::strip_verbatim_prefix(&input.workspace.current_dir);
 
-        // Use full path or just directory name based on config
-        let dir_name = if self.show_full_path {
-            current_dir.clone()
-        } else {
-            Self::extract_directory_name(current_dir)
+        let dir_name = match self.display {
+            DirectoryDisplay::FullPath => {
+                Self::shorten_path(&Self::collapse_home(current_dir), self.max_path_components)
+            }
+            DirectoryDisplay::Basename => Self::extract_directory_name(current_dir),
+            DirectoryDisplay::RepoRelative => Self::repo_relative_path(current_dir)
+                .unwrap_or_else(|| Self::extract_directory_name(current_dir)),
         };
 
         // Store the full path in metadata for potential use
         let mut metadata = HashMap::new();
-        metadata.insert("full_path".to_string(), current_dir.clone());
+        metadata.insert("full_path".to_string(), input.workspace.current_dir.clone());
 
         Some(SegmentData {
             primary: dir_name,
@@ -80,3 +198,60 @@ impl Segment for DirectorySegment {
         SegmentId::Directory
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Workspace;
+
+    fn render(segment: DirectorySegment, current_dir: &str) -> String {
+        let input = InputData {
+            workspace: Workspace {
+                current_dir: current_dir.to_string(),
+            },
+            ..InputData::default()
+        };
+        segment.collect(&input).unwrap().primary
+    }
+
+    #[test]
+    fn verbatim_drive_paths() {
+        assert_eq!(
+            render(DirectorySegment::new(), r"\\?\C:\Users\me\proj"),
+            "proj"
+        );
+        assert_eq!(render(DirectorySegment::new(), r"\\?\D:\"), r"D:\");
+        assert_eq!(render(DirectorySegment::new(), r"D:\"), r"D:\");
+        assert_eq!(
+            render(
+                DirectorySegment::new().with_full_path(true),
+                r"\\?\C:\Users\me\proj"
+            ),
+            r"C:\Users\me\proj"
+        );
+    }
+
+    #[test]
+    fn verbatim_unc_paths() {
+        assert_eq!(
+            render(DirectorySegment::new(), r"\\?\UNC\server\share\dir"),
+            "dir"
+        );
+        assert_eq!(
+            render(
+                DirectorySegment::new().with_full_path(true),
+                r"\\?\UNC\server\share\dir"
+            ),
+            r"\\server\share\dir"
+        );
+    }
+
+    #[test]
+    fn unc_paths() {
+        assert_eq!(
+            render(DirectorySegment::new(), r"\\server\share\dir"),
+            "dir"
+        );
+        assert_eq!(render(DirectorySegment::new(), r"\\server\share"), "share");
+    }
+}
@@ -0,0 +1,147 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether the workspace's `.envrc` is active in the environment Claude Code runs in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DirenvState {
+    /// direnv exported this `.envrc`
+    Loaded,
+    /// Allowed, but not loaded into this environment
+    Unloaded,
+    /// Never allowed, denied, or edited since it was allowed
+    Blocked,
+}
+
+impl DirenvState {
+    fn as_str(self) -> &'static str {
+        match self {
+            DirenvState::Loaded => "loaded",
+            DirenvState::Unloaded => "unloaded",
+            DirenvState::Blocked => "blocked",
+        }
+    }
+
+    fn level(self) -> Option<&'static str> {
+        match self {
+            DirenvState::Loaded => None,
+            DirenvState::Unloaded => Some("warning"),
+            DirenvState::Blocked => Some("critical"),
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            DirenvState::Loaded => "\u{f05e0}",
+            DirenvState::Unloaded => "\u{f03e4}",
+            DirenvState::Blocked => "\u{f0028}",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DirenvSegment;
+
+impl DirenvSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// direnv picks the nearest `.envrc` at or above the directory
+    fn find_envrc(working_dir: &str) -> Option<PathBuf> {
+        Path::new(working_dir)
+            .ancestors()
+            .map(|dir| dir.join(".envrc"))
+            .find(|path| path.is_file())
+    }
+
+    /// direnv keeps its allow/deny lists under the XDG data dir on every platform
+    fn data_dir() -> Option<PathBuf> {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("share")))
+            .map(|dir| dir.join("direnv"))
+    }
+
+    fn sha256_hex(data: &[u8]) -> String {
+        Sha256::digest(data)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// An allow entry is named by sha256("<path>\n<content>"), so editing the file revokes it;
+    /// a deny entry by sha256("<path>\n")
+    fn is_allowed(envrc: &Path) -> bool {
+        let Some(data_dir) = Self::data_dir() else {
+            return false;
+        };
+        let Ok(content) = fs::read(envrc) else {
+            return false;
+        };
+
+        let path_line = format!("{}\n", envrc.display());
+        if data_dir
+            .join("deny")
+            .join(Self::sha256_hex(path_line.as_bytes()))
+            .is_file()
+        {
+            return false;
+        }
+
+        let mut hashed = path_line.into_bytes();
+        hashed.extend_from_slice(&content);
+        data_dir
+            .join("allow")
+            .join(Self::sha256_hex(&hashed))
+            .is_file()
+    }
+
+    /// DIRENV_FILE names the loaded `.envrc`; DIRENV_DIR is its directory prefixed with "-"
+    fn is_loaded(envrc: &Path) -> bool {
+        let loaded_file = env::var_os("DIRENV_FILE").map(PathBuf::from);
+        let loaded_dir = env::var("DIRENV_DIR")
+            .ok()
+            .map(|dir| PathBuf::from(dir.trim_start_matches('-')));
+
+        loaded_file.as_deref() == Some(envrc)
+            || (loaded_file.is_none() && loaded_dir.as_deref() == envrc.parent())
+    }
+}
+
+impl Segment for DirenvSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let envrc = Self::find_envrc(&input.workspace.current_dir)?;
+
+        let state = if Self::is_loaded(&envrc) {
+            DirenvState::Loaded
+        } else if Self::is_allowed(&envrc) {
+            DirenvState::Unloaded
+        } else {
+            DirenvState::Blocked
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("state".to_string(), state.as_str().to_string());
+        metadata.insert("envrc".to_string(), envrc.to_string_lossy().to_string());
+        metadata.insert("dynamic_icon".to_string(), state.icon().to_string());
+        if let Some(level) = state.level() {
+            metadata.insert("level".to_string(), level.to_string());
+        }
+
+        Some(SegmentData {
+            primary: state.as_str().to_string(),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Direnv
+    }
+}
@@ -0,0 +1,178 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::path::Path;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Free space cutoff, either absolute ("10G", "512M") or relative to volume size ("10%")
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskThreshold {
+    Bytes(u64),
+    Percent(f64),
+}
+
+impl DiskThreshold {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(percent) = value.strip_suffix('%') {
+            let percent: f64 = percent.trim().parse().ok()?;
+            return (0.0..=100.0)
+                .contains(&percent)
+                .then_some(DiskThreshold::Percent(percent));
+        }
+
+        let upper = value.to_ascii_uppercase();
+        let number = upper
+            .trim_end_matches("IB")
+            .trim_end_matches('B')
+            .trim_end();
+        let (digits, multiplier) = match number.chars().last()? {
+            'K' => (&number[..number.len() - 1], 1024.0),
+            'M' => (&number[..number.len() - 1], 1024.0 * 1024.0),
+            'G' => (&number[..number.len() - 1], GIB),
+            'T' => (&number[..number.len() - 1], GIB * 1024.0),
+            _ => (number, 1.0),
+        };
+        let amount: f64 = digits.trim().parse().ok()?;
+        (amount >= 0.0).then_some(DiskThreshold::Bytes((amount * multiplier) as u64))
+    }
+
+    fn is_below(&self, free: u64, total: u64) -> bool {
+        match *self {
+            DiskThreshold::Bytes(bytes) => free < bytes,
+            DiskThreshold::Percent(percent) => {
+                total > 0 && (free as f64 / total as f64 * 100.0) < percent
+            }
+        }
+    }
+}
+
+pub struct DiskSegment {
+    show_below: DiskThreshold,
+    critical_below: DiskThreshold,
+    always_show: bool,
+}
+
+impl Default for DiskSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiskSegment {
+    pub fn new() -> Self {
+        Self {
+            show_below: DiskThreshold::Bytes(10 * GIB as u64),
+            critical_below: DiskThreshold::Bytes(2 * GIB as u64),
+            always_show: false,
+        }
+    }
+
+    /// The segment stays hidden until free space drops below this
+    pub fn with_show_below(mut self, show_below: DiskThreshold) -> Self {
+        self.show_below = show_below;
+        self
+    }
+
+    /// Below this the segment renders in the critical color
+    pub fn with_critical_below(mut self, critical_below: DiskThreshold) -> Self {
+        self.critical_below = critical_below;
+        self
+    }
+
+    pub fn with_always_show(mut self, always_show: bool) -> Self {
+        self.always_show = always_show;
+        self
+    }
+
+    /// Free (available to unprivileged users) and total bytes of the volume holding `path`
+    #[cfg(unix)]
+    fn disk_space(path: &Path) -> Option<(u64, u64)> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: c_path is NUL-terminated and stat is a valid out pointer
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+
+        let block_size = stat.f_frsize as u64;
+        Some((
+            stat.f_bavail as u64 * block_size,
+            stat.f_blocks as u64 * block_size,
+        ))
+    }
+
+    #[cfg(windows)]
+    fn disk_space(path: &Path) -> Option<(u64, u64)> {
+        use std::os::windows::ffi::OsStrExt;
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetDiskFreeSpaceExW(
+                directory: *const u16,
+                free_to_caller: *mut u64,
+                total: *mut u64,
+                total_free: *mut u64,
+            ) -> i32;
+        }
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let (mut free, mut total, mut total_free) = (0u64, 0u64, 0u64);
+        // SAFETY: wide is NUL-terminated and all out pointers are valid
+        let ok =
+            unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut free, &mut total, &mut total_free) };
+        (ok != 0).then_some((free, total))
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn disk_space(_path: &Path) -> Option<(u64, u64)> {
+        None
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        let units = ["B", "K", "M", "G", "T", "P"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < units.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 || value >= 100.0 {
+            format!("{:.0}{}", value, units[unit])
+        } else {
+            format!("{:.1}{}", value, units[unit])
+        }
+    }
+}
+
+impl Segment for DiskSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (free, total) = Self::disk_space(Path::new(&input.workspace.current_dir))?;
+
+        if !self.always_show && !self.show_below.is_below(free, total) {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("free_bytes".to_string(), free.to_string());
+        metadata.insert("total_bytes".to_string(), total.to_string());
+        if self.critical_below.is_below(free, total) {
+            metadata.insert("level".to_string(), "critical".to_string());
+        }
+
+        Some(SegmentData {
+            primary: Self::format_bytes(free),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Disk
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files that mark a directory as a container project
+const CONTAINER_FILES: [&str; 5] = [
+    "Dockerfile",
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+pub struct DockerSegment {
+    only_when_nondefault: bool,
+    project_marker: String,
+}
+
+impl Default for DockerSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DockerSegment {
+    pub fn new() -> Self {
+        Self {
+            only_when_nondefault: false,
+            project_marker: "📦".to_string(),
+        }
+    }
+
+    /// Hide the segment while the "default" context is active
+    pub fn with_only_when_nondefault(mut self, only_when_nondefault: bool) -> Self {
+        self.only_when_nondefault = only_when_nondefault;
+        self
+    }
+
+    pub fn with_project_marker(mut self, project_marker: impl Into<String>) -> Self {
+        self.project_marker = project_marker.into();
+        self
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        match env::var_os("DOCKER_CONFIG").filter(|v| !v.is_empty()) {
+            Some(dir) => Some(PathBuf::from(dir).join("config.json")),
+            None => dirs::home_dir().map(|home| home.join(".docker").join("config.json")),
+        }
+    }
+
+    /// Active context: $DOCKER_CONTEXT, then `currentContext` in config.json, then "default"
+    fn current_context() -> String {
+        if let Ok(context) = env::var("DOCKER_CONTEXT") {
+            if !context.is_empty() {
+                return context;
+            }
+        }
+
+        Self::config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| {
+                json.get("currentContext")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            })
+            .filter(|context| !context.is_empty())
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    fn is_container_project(dir: &Path) -> bool {
+        CONTAINER_FILES.iter().any(|file| dir.join(file).is_file())
+    }
+}
+
+impl Segment for DockerSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let context = Self::current_context();
+        if self.only_when_nondefault && context == "default" {
+            return None;
+        }
+
+        let is_project = Self::is_container_project(Path::new(&input.workspace.current_dir));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("context".to_string(), context.clone());
+        metadata.insert("container_project".to_string(), is_project.to_string());
+
+        let secondary = if is_project {
+            self.project_marker.clone()
+        } else {
+            String::new()
+        };
+
+        Some(SegmentData {
+            primary: context,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Docker
+    }
+}
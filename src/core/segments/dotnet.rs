@@ -0,0 +1,99 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+const DOTNET_TIMEOUT: Duration = Duration::from_millis(300);
+
+#[derive(Default)]
+pub struct DotnetSegment;
+
+impl DotnetSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Kind of .NET project in the directory, shown when the SDK can't be determined
+    fn project_type(dir: &Path) -> Option<&'static str> {
+        if dir.join("global.json").is_file() {
+            return Some("global.json");
+        }
+
+        let entries = fs::read_dir(dir).ok()?;
+        let mut project_type = None;
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.ends_with(".sln") {
+                // A solution describes the workspace better than any single project
+                return Some("solution");
+            }
+            if name.ends_with(".csproj") {
+                project_type = Some("C# project");
+            }
+        }
+        project_type
+    }
+
+    /// SDK version pinned by `sdk.version` in global.json
+    fn pinned_sdk(dir: &Path) -> Option<String> {
+        let content = fs::read_to_string(dir.join("global.json")).ok()?;
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let version = json.get("sdk")?.get("version")?.as_str()?.trim();
+        if version.is_empty() {
+            None
+        } else {
+            Some(version.to_string())
+        }
+    }
+
+    fn installed_sdk(dir: &Path) -> Option<String> {
+        let output = Command::new("dotnet")
+            .arg("--version")
+            .current_dir(dir)
+            .output_with_timeout(DOTNET_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let version = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        if version.is_empty() {
+            None
+        } else {
+            Some(version)
+        }
+    }
+}
+
+impl Segment for DotnetSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let dir = Path::new(&input.workspace.current_dir);
+        let project_type = Self::project_type(dir)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("project_type".to_string(), project_type.to_string());
+
+        let primary = match Self::pinned_sdk(dir).or_else(|| Self::installed_sdk(dir)) {
+            Some(sdk) => {
+                metadata.insert("sdk_version".to_string(), sdk.clone());
+                format!(".NET {}", sdk)
+            }
+            None => project_type.to_string(),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Dotnet
+    }
+}
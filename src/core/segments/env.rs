@@ -0,0 +1,74 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+
+/// Shows the value of one environment variable, e.g. NODE_ENV
+pub struct EnvSegment {
+    variable: String,
+    prefix: String,
+    levels: HashMap<String, String>,
+}
+
+impl Default for EnvSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvSegment {
+    pub fn new() -> Self {
+        Self {
+            variable: String::new(),
+            prefix: String::new(),
+            levels: HashMap::new(),
+        }
+    }
+
+    pub fn with_variable(mut self, variable: impl Into<String>) -> Self {
+        self.variable = variable.into();
+        self
+    }
+
+    /// Label rendered before the value, e.g. "env:"
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Map values to a level such as "warning", colored by the matching `<level>_color`
+    pub fn with_levels(mut self, levels: HashMap<String, String>) -> Self {
+        self.levels = levels;
+        self
+    }
+}
+
+impl Segment for EnvSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        if self.variable.is_empty() {
+            return None;
+        }
+
+        let value = env::var(&self.variable)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("variable".to_string(), self.variable.clone());
+        metadata.insert("value".to_string(), value.clone());
+        if let Some(level) = self.levels.get(&value) {
+            metadata.insert("level".to_string(), level.clone());
+        }
+
+        Some(SegmentData {
+            primary: format!("{}{}", self.prefix, value),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Env
+    }
+}
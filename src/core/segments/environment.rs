@@ -0,0 +1,114 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Environments that can trigger the segment, most specific first
+pub const ENVIRONMENTS: [&str; 3] = ["devcontainer", "docker", "wsl"];
+
+/// What the process runs inside; probed once per process
+#[derive(Debug, Default)]
+struct Detected {
+    devcontainer: bool,
+    docker: bool,
+    wsl: bool,
+    wsl_distro: Option<String>,
+}
+
+static DETECTED: OnceLock<Detected> = OnceLock::new();
+
+/// Shows when Claude Code runs under WSL, in a Docker container or a devcontainer
+pub struct EnvironmentSegment {
+    environments: Vec<String>,
+}
+
+impl Default for EnvironmentSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvironmentSegment {
+    pub fn new() -> Self {
+        Self {
+            environments: ENVIRONMENTS.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    /// Environments that show the segment; the most specific detected one is displayed
+    pub fn with_environments(mut self, environments: Vec<String>) -> Self {
+        self.environments = environments;
+        self
+    }
+
+    fn detected() -> &'static Detected {
+        DETECTED.get_or_init(|| Detected {
+            devcontainer: env::var_os("REMOTE_CONTAINERS").is_some_and(|v| !v.is_empty()),
+            docker: Self::in_docker(),
+            wsl: fs::read_to_string("/proc/version")
+                .is_ok_and(|version| version.to_lowercase().contains("microsoft")),
+            wsl_distro: env::var("WSL_DISTRO_NAME").ok().filter(|d| !d.is_empty()),
+        })
+    }
+
+    /// `/.dockerenv`, else a docker or containerd cgroup (v1) or container mount (v2)
+    fn in_docker() -> bool {
+        if Path::new("/.dockerenv").exists() {
+            return true;
+        }
+
+        let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+        if cgroup.contains("/docker") || cgroup.contains("containerd") {
+            return true;
+        }
+
+        fs::read_to_string("/proc/self/mountinfo")
+            .is_ok_and(|mounts| mounts.contains("/docker/containers/"))
+    }
+
+    /// Label and Nerd Font icon for an environment
+    fn describe(environment: &str) -> (&'static str, &'static str) {
+        match environment {
+            "devcontainer" => ("devcontainer", "\u{f01a7}"),
+            "docker" => ("Docker", "\u{f0868}"),
+            _ => ("WSL", "\u{f05b3}"),
+        }
+    }
+}
+
+impl Segment for EnvironmentSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let detected = Self::detected();
+        let environment = ENVIRONMENTS.iter().copied().find(|environment| {
+            self.environments.iter().any(|e| e == environment)
+                && match *environment {
+                    "devcontainer" => detected.devcontainer,
+                    "docker" => detected.docker,
+                    _ => detected.wsl,
+                }
+        })?;
+
+        let (label, icon) = Self::describe(environment);
+        let secondary = match environment {
+            "wsl" => detected.wsl_distro.clone().unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("environment".to_string(), environment.to_string());
+        metadata.insert("dynamic_icon".to_string(), icon.to_string());
+
+        Some(SegmentData {
+            primary: label.to_string(),
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Environment
+    }
+}
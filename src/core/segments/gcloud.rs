@@ -0,0 +1,103 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::ini::parse_ini;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct GcloudSegment {
+    show_account: bool,
+}
+
+impl Default for GcloudSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GcloudSegment {
+    pub fn new() -> Self {
+        Self {
+            show_account: false,
+        }
+    }
+
+    /// Render the account email as secondary text
+    pub fn with_account(mut self, show_account: bool) -> Self {
+        self.show_account = show_account;
+        self
+    }
+
+    fn env_value(name: &str) -> Option<String> {
+        env::var(name).ok().filter(|value| !value.is_empty())
+    }
+
+    /// $CLOUDSDK_CONFIG, else ~/.config/gcloud (%APPDATA%\gcloud on Windows)
+    fn config_dir() -> Option<PathBuf> {
+        if let Some(dir) = Self::env_value("CLOUDSDK_CONFIG") {
+            return Some(PathBuf::from(dir));
+        }
+
+        if cfg!(windows) {
+            Self::env_value("APPDATA").map(|appdata| PathBuf::from(appdata).join("gcloud"))
+        } else {
+            dirs::home_dir().map(|home| home.join(".config").join("gcloud"))
+        }
+    }
+
+    fn active_config(dir: &Path) -> String {
+        Self::env_value("CLOUDSDK_ACTIVE_CONFIG_NAME")
+            .or_else(|| {
+                fs::read_to_string(dir.join("active_config"))
+                    .ok()
+                    .map(|content| content.trim().to_string())
+                    .filter(|name| !name.is_empty())
+            })
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+impl Segment for GcloudSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let dir = Self::config_dir()?;
+        let config_name = Self::active_config(&dir);
+
+        let sections = fs::read_to_string(
+            dir.join("configurations")
+                .join(format!("config_{}", config_name)),
+        )
+        .map(|content| parse_ini(&content))
+        .unwrap_or_default();
+        let core = sections.get("core");
+
+        let project = Self::env_value("CLOUDSDK_CORE_PROJECT")
+            .or_else(|| core.and_then(|core| core.get("project").cloned()))
+            .filter(|project| !project.is_empty())?;
+        let account = Self::env_value("CLOUDSDK_CORE_ACCOUNT")
+            .or_else(|| core.and_then(|core| core.get("account").cloned()))
+            .filter(|account| !account.is_empty());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("configuration".to_string(), config_name);
+        metadata.insert("project".to_string(), project.clone());
+        if let Some(ref account) = account {
+            metadata.insert("account".to_string(), account.clone());
+        }
+
+        let secondary = match account {
+            Some(account) if self.show_account => account,
+            _ => String::new(),
+        };
+
+        Some(SegmentData {
+            primary: project,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Gcloud
+    }
+}
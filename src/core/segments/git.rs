@@ -1,26 +1,62 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use crate::utils::ini::parse_ini;
+use crate::utils::OutputWithTimeout;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
 
-#[derive(Debug)]
+/// Upper bound for each git invocation so a slow filesystem can't hang the statusline
+pub(crate) const GIT_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GitInfo {
     pub branch: String,
     pub status: GitStatus,
     pub ahead: u32,
     pub behind: u32,
+    pub upstream: Option<String>,
     pub sha: Option<String>,
+    /// Tag pointing exactly at HEAD
+    #[serde(default)]
+    pub tag: Option<String>,
+    pub stash_count: Option<u32>,
+    #[serde(default)]
+    pub operation: Option<GitOperation>,
+}
+
+/// An in-progress multi-step operation such as a rebase or merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitOperation {
+    /// Label shown in the statusline, e.g. "REBASE" or "MERGING"
+    pub name: String,
+    pub step: Option<u32>,
+    pub total: Option<u32>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum GitStatus {
     Clean,
     Dirty,
     Conflicts,
+    /// Status could not be determined (e.g. `git status` failed)
+    #[default]
+    Unknown,
 }
 
 pub struct GitSegment {
     show_sha: bool,
+    show_tag: bool,
+    show_stash: bool,
+    show_status: bool,
+    untracked_files: bool,
+    show_operation: bool,
+    dirty_symbol: String,
+    clean_symbol: String,
+    cache_ttl: Duration,
 }
 
 impl Default for GitSegment {
@@ -31,7 +67,17 @@ impl Default for GitSegment {
 
 impl GitSegment {
     pub fn new() -> Self {
-        Self { show_sha: false }
+        Self {
+            show_sha: false,
+            show_tag: false,
+            show_stash: false,
+            show_status: true,
+            untracked_files: true,
+            show_operation: true,
+            dirty_symbol: "●".to_string(),
+            clean_symbol: "✓".to_string(),
+            cache_ttl: Duration::from_millis(1000),
+        }
     }
 
     pub fn with_sha(mut self, show_sha: bool) -> Self {
@@ -39,7 +85,81 @@ impl GitSegment {
         self
     }
 
-    fn get_git_info(&self, working_dir: &str) -> Option<GitInfo> {
+    /// Show the tag HEAD sits on, in place of the SHA when both are enabled
+    pub fn with_tag(mut self, show_tag: bool) -> Self {
+        self.show_tag = show_tag;
+        self
+    }
+
+    pub fn with_stash(mut self, show_stash: bool) -> Self {
+        self.show_stash = show_stash;
+        self
+    }
+
+    /// Run `git status` to detect uncommitted changes; disable on very large repositories
+    pub fn with_status(mut self, show_status: bool) -> Self {
+        self.show_status = show_status;
+        self
+    }
+
+    /// Whether untracked files count as changes (`--untracked-files=no` when false)
+    pub fn with_untracked_files(mut self, untracked_files: bool) -> Self {
+        self.untracked_files = untracked_files;
+        self
+    }
+
+    /// Show an in-progress rebase, merge, cherry-pick, revert or bisect
+    pub fn with_operation(mut self, show_operation: bool) -> Self {
+        self.show_operation = show_operation;
+        self
+    }
+
+    pub fn with_dirty_symbol(mut self, dirty_symbol: impl Into<String>) -> Self {
+        self.dirty_symbol = dirty_symbol.into();
+        self
+    }
+
+    /// Symbol shown for a clean working tree, empty to show nothing
+    pub fn with_clean_symbol(mut self, clean_symbol: impl Into<String>) -> Self {
+        self.clean_symbol = clean_symbol.into();
+        self
+    }
+
+    /// How long collected git info is reused across invocations (zero disables the cache)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Locate the repository root and its git dir without spawning git,
+    /// following the `gitdir:` pointer used by worktrees and submodules
+    pub(crate) fn find_repo(working_dir: &str) -> Option<(PathBuf, PathBuf)> {
+        let root = Path::new(working_dir)
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())?;
+        let dot_git = root.join(".git");
+
+        let git_dir = if dot_git.is_file() {
+            let content = fs::read_to_string(&dot_git).ok()?;
+            let target = content.trim().strip_prefix("gitdir:")?.trim();
+            root.join(target)
+        } else {
+            dot_git
+        };
+
+        Some((root.to_path_buf(), git_dir))
+    }
+
+    /// Current HEAD contents and mtime, which change on commit, checkout and branch switch
+    fn head_state(git_dir: &Path) -> Option<(String, u64)> {
+        let head_path = git_dir.join("HEAD");
+        let head = fs::read_to_string(&head_path).ok()?.trim().to_string();
+        let mtime = fs::metadata(&head_path).ok()?.modified().ok()?;
+        let mtime_ns = mtime.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64;
+        Some((head, mtime_ns))
+    }
+
+    fn collect_git_info(&self, working_dir: &str) -> Option<GitInfo> {
         if !self.is_git_repository(working_dir) {
             return None;
         }
@@ -47,20 +167,92 @@ impl GitSegment {
         let branch = self
             .get_branch(working_dir)
             .unwrap_or_else(|| "detached".to_string());
-        let status = self.get_status(working_dir);
-        let (ahead, behind) = self.get_ahead_behind(working_dir);
+        let status = if self.show_status {
+            self.get_status(working_dir)
+        } else {
+            GitStatus::Unknown
+        };
+        let upstream = Self::get_upstream(working_dir, &branch);
+        let (ahead, behind) = self.get_ahead_behind(working_dir).unwrap_or((0, 0));
         let sha = if self.show_sha {
             self.get_sha(working_dir)
         } else {
             None
         };
+        let tag = if self.show_tag {
+            self.get_tag(working_dir)
+        } else {
+            None
+        };
+        let stash_count = if self.show_stash {
+            Some(self.get_stash_count(working_dir))
+        } else {
+            None
+        };
+        let operation = if self.show_operation {
+            Self::find_repo(working_dir).and_then(|(_, git_dir)| Self::get_operation(&git_dir))
+        } else {
+            None
+        };
 
         Some(GitInfo {
             branch,
             status,
             ahead,
             behind,
+            upstream,
             sha,
+            tag,
+            stash_count,
+            operation,
+        })
+    }
+
+    /// Detect a rebase, am, merge, cherry-pick, revert or bisect from the
+    /// marker files git leaves in the git dir, the same way git's prompt does
+    fn get_operation(git_dir: &Path) -> Option<GitOperation> {
+        let read_number =
+            |path: PathBuf| -> Option<u32> { fs::read_to_string(path).ok()?.trim().parse().ok() };
+
+        let rebase_merge = git_dir.join("rebase-merge");
+        if rebase_merge.is_dir() {
+            return Some(GitOperation {
+                name: "REBASE".to_string(),
+                step: read_number(rebase_merge.join("msgnum")),
+                total: read_number(rebase_merge.join("end")),
+            });
+        }
+
+        let rebase_apply = git_dir.join("rebase-apply");
+        if rebase_apply.is_dir() {
+            let name = if rebase_apply.join("rebasing").exists() {
+                "REBASE"
+            } else if rebase_apply.join("applying").exists() {
+                "AM"
+            } else {
+                "AM/REBASE"
+            };
+            return Some(GitOperation {
+                name: name.to_string(),
+                step: read_number(rebase_apply.join("next")),
+                total: read_number(rebase_apply.join("last")),
+            });
+        }
+
+        let name = [
+            ("MERGE_HEAD", "MERGING"),
+            ("CHERRY_PICK_HEAD", "CHERRY-PICKING"),
+            ("REVERT_HEAD", "REVERTING"),
+            ("BISECT_LOG", "BISECTING"),
+        ]
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, name)| name.to_string())?;
+
+        Some(GitOperation {
+            name,
+            step: None,
+            total: None,
         })
     }
 
@@ -68,7 +260,7 @@ impl GitSegment {
         Command::new("git")
             .args(["--no-optional-locks", "rev-parse", "--git-dir"])
             .current_dir(working_dir)
-            .output()
+            .output_with_timeout(GIT_TIMEOUT)
             .map(|output| output.status.success())
             .unwrap_or(false)
     }
@@ -77,7 +269,7 @@ impl GitSegment {
         if let Ok(output) = Command::new("git")
             .args(["--no-optional-locks", "branch", "--show-current"])
             .current_dir(working_dir)
-            .output()
+            .output_with_timeout(GIT_TIMEOUT)
         {
             if output.status.success() {
                 let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
@@ -90,7 +282,7 @@ impl GitSegment {
         if let Ok(output) = Command::new("git")
             .args(["--no-optional-locks", "symbolic-ref", "--short", "HEAD"])
             .current_dir(working_dir)
-            .output()
+            .output_with_timeout(GIT_TIMEOUT)
         {
             if output.status.success() {
                 let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
@@ -104,10 +296,15 @@ impl GitSegment {
     }
 
     fn get_status(&self, working_dir: &str) -> GitStatus {
+        let untracked = if self.untracked_files {
+            "--untracked-files=normal"
+        } else {
+            "--untracked-files=no"
+        };
         let output = Command::new("git")
-            .args(["--no-optional-locks", "status", "--porcelain"])
+            .args(["--no-optional-locks", "status", "--porcelain", untracked])
             .current_dir(working_dir)
-            .output();
+            .output_with_timeout(GIT_TIMEOUT);
 
         match output {
             Ok(output) if output.status.success() => {
@@ -126,21 +323,71 @@ impl GitSegment {
                     GitStatus::Dirty
                 }
             }
-            _ => GitStatus::Clean,
+            _ => GitStatus::Unknown,
         }
     }
 
-    fn get_ahead_behind(&self, working_dir: &str) -> (u32, u32) {
-        let ahead = self.get_commit_count(working_dir, "@{u}..HEAD");
-        let behind = self.get_commit_count(working_dir, "HEAD..@{u}");
-        (ahead, behind)
+    /// Short name of the branch's upstream (e.g. "origin/main"), read from
+    /// `branch.<name>.remote` and `branch.<name>.merge` in the git config without spawning git
+    fn get_upstream(working_dir: &str, branch: &str) -> Option<String> {
+        let (_, git_dir) = Self::find_repo(working_dir)?;
+        // Linked worktrees share the config of the main git dir
+        let common_dir = fs::read_to_string(git_dir.join("commondir"))
+            .map(|dir| git_dir.join(dir.trim()))
+            .unwrap_or(git_dir);
+        let config = fs::read_to_string(common_dir.join("config")).ok()?;
+        let section = parse_ini(&config).remove(&format!("branch \"{}\"", branch))?;
+
+        let remote = section.get("remote")?;
+        let merge = section.get("merge")?;
+        let merge = merge.strip_prefix("refs/heads/").unwrap_or(merge);
+        // A remote of "." tracks another local branch
+        Some(if remote == "." {
+            merge.to_string()
+        } else {
+            format!("{}/{}", remote, merge)
+        })
     }
 
-    fn get_commit_count(&self, working_dir: &str, range: &str) -> u32 {
+    /// Count commits ahead/behind the upstream branch in a single call.
+    /// Returns None when no upstream is configured (or HEAD is detached).
+    fn get_ahead_behind(&self, working_dir: &str) -> Option<(u32, u32)> {
         let output = Command::new("git")
-            .args(["--no-optional-locks", "rev-list", "--count", range])
+            .args([
+                "--no-optional-locks",
+                "rev-list",
+                "--count",
+                "--left-right",
+                "@{upstream}...HEAD",
+            ])
             .current_dir(working_dir)
-            .output();
+            .output_with_timeout(GIT_TIMEOUT)
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        // Output format: "<behind>\t<ahead>" (left side is upstream, right side is HEAD)
+        let counts = String::from_utf8(output.stdout).ok()?;
+        let mut parts = counts.split_whitespace();
+        let behind = parts.next()?.parse().ok()?;
+        let ahead = parts.next()?.parse().ok()?;
+        Some((ahead, behind))
+    }
+
+    /// Count stash entries by walking the refs/stash reflog
+    fn get_stash_count(&self, working_dir: &str) -> u32 {
+        let output = Command::new("git")
+            .args([
+                "--no-optional-locks",
+                "rev-list",
+                "--walk-reflogs",
+                "--count",
+                "refs/stash",
+            ])
+            .current_dir(working_dir)
+            .output_with_timeout(GIT_TIMEOUT);
 
         match output {
             Ok(output) if output.status.success() => String::from_utf8(output.stdout)
@@ -155,7 +402,7 @@ impl GitSegment {
         let output = Command::new("git")
             .args(["--no-optional-locks", "rev-parse", "--short=7", "HEAD"])
             .current_dir(working_dir)
-            .output()
+            .output_with_timeout(GIT_TIMEOUT)
             .ok()?;
 
         if output.status.success() {
@@ -169,29 +416,80 @@ impl GitSegment {
             None
         }
     }
+
+    /// Tag pointing exactly at HEAD, preferring annotated tags as `git describe` does
+    fn get_tag(&self, working_dir: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args([
+                "--no-optional-locks",
+                "describe",
+                "--tags",
+                "--exact-match",
+                "HEAD",
+            ])
+            .current_dir(working_dir)
+            .output_with_timeout(GIT_TIMEOUT)
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+        let tag = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (!tag.is_empty()).then_some(tag)
+    }
 }
 
 impl Segment for GitSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let git_info = self.get_git_info(&input.workspace.current_dir)?;
+        let git_info = self.collect_git_info(&input.workspace.current_dir)?;
 
         let mut metadata = HashMap::new();
         metadata.insert("branch".to_string(), git_info.branch.clone());
         metadata.insert("status".to_string(), format!("{:?}", git_info.status));
         metadata.insert("ahead".to_string(), git_info.ahead.to_string());
         metadata.insert("behind".to_string(), git_info.behind.to_string());
+        if git_info.status != GitStatus::Unknown {
+            let dirty = git_info.status != GitStatus::Clean;
+            metadata.insert("dirty".to_string(), dirty.to_string());
+        }
 
+        if let Some(ref upstream) = git_info.upstream {
+            metadata.insert("upstream".to_string(), upstream.clone());
+        }
         if let Some(ref sha) = git_info.sha {
             metadata.insert("sha".to_string(), sha.clone());
         }
+        if let Some(ref tag) = git_info.tag {
+            metadata.insert("tag".to_string(), tag.clone());
+        }
+        if let Some(stash_count) = git_info.stash_count {
+            metadata.insert("stash_count".to_string(), stash_count.to_string());
+        }
 
         let primary = git_info.branch;
         let mut status_parts = Vec::new();
 
+        if let Some(ref operation) = git_info.operation.filter(|_| self.show_operation) {
+            metadata.insert("operation".to_string(), operation.name.clone());
+            metadata.insert("level".to_string(), "warning".to_string());
+            match (operation.step, operation.total) {
+                (Some(step), Some(total)) => {
+                    metadata.insert("operation_step".to_string(), step.to_string());
+                    metadata.insert("operation_total".to_string(), total.to_string());
+                    status_parts.push(format!("{} {}/{}", operation.name, step, total));
+                }
+                _ => status_parts.push(operation.name.clone()),
+            }
+        }
+
         match git_info.status {
-            GitStatus::Clean => status_parts.push("✓".to_string()),
-            GitStatus::Dirty => status_parts.push("●".to_string()),
+            GitStatus::Clean if !self.clean_symbol.is_empty() => {
+                status_parts.push(self.clean_symbol.clone())
+            }
+            GitStatus::Clean => {}
+            GitStatus::Dirty => status_parts.push(self.dirty_symbol.clone()),
             GitStatus::Conflicts => status_parts.push("⚠".to_string()),
+            GitStatus::Unknown => {}
         }
 
         if git_info.ahead > 0 {
@@ -201,8 +499,17 @@ impl Segment for GitSegment {
             status_parts.push(format!("↓{}", git_info.behind));
         }
 
-        if let Some(ref sha) = git_info.sha {
-            status_parts.push(sha.clone());
+        if let Some(stash_count) = git_info.stash_count {
+            if stash_count > 0 {
+                status_parts.push(format!("⚑{}", stash_count));
+            }
+        }
+
+        // A tag names HEAD more readably than its SHA, which stays in metadata
+        if let Some(ref tag) = git_info.tag {
+            status_parts.push(tag.clone());
+        } else if let Some(ref sha) = git_info.sha {
+            status_parts.push(format!("@{}", sha));
         }
 
         Some(SegmentData {
@@ -215,4 +522,105 @@ impl Segment for GitSegment {
     fn id(&self) -> SegmentId {
         SegmentId::Git
     }
+
+    /// Reused while HEAD is unchanged; commits, checkouts and branch switches rewrite it
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        if self.cache_ttl.is_zero() {
+            return None;
+        }
+        let (root, git_dir) = Self::find_repo(&input.workspace.current_dir)?;
+        let (head, head_mtime_ns) = Self::head_state(&git_dir)?;
+        let toggles: String = [
+            self.show_sha,
+            self.show_tag,
+            self.show_stash,
+            self.show_status,
+            self.untracked_files,
+            self.show_operation,
+        ]
+        .iter()
+        .map(|on| if *on { '1' } else { '0' })
+        .collect();
+        Some(format!(
+            "{}\0{}\0{}\0{}\0{}\0{}",
+            root.to_string_lossy(),
+            head,
+            head_mtime_ns,
+            toggles,
+            self.dirty_symbol,
+            self.clean_symbol
+        ))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Workspace;
+
+    #[test]
+    fn cache_key_follows_head_and_options() {
+        let repo = std::env::temp_dir().join(format!("ccline-git-key-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo);
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(repo.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        let input = InputData {
+            workspace: Workspace {
+                current_dir: repo.join("src").to_string_lossy().to_string(),
+            },
+            ..InputData::default()
+        };
+
+        let segment = GitSegment::new();
+        let key = segment.cache_key(&input).unwrap();
+        assert_eq!(segment.cache_key(&input).unwrap(), key);
+        assert_ne!(
+            GitSegment::new().with_sha(true).cache_key(&input).unwrap(),
+            key
+        );
+        assert_ne!(
+            GitSegment::new()
+                .with_dirty_symbol("*")
+                .cache_key(&input)
+                .unwrap(),
+            key
+        );
+        assert!(GitSegment::new()
+            .with_cache_ttl(Duration::ZERO)
+            .cache_key(&input)
+            .is_none());
+
+        fs::write(repo.join(".git/HEAD"), "ref: refs/heads/feature\n").unwrap();
+        assert_ne!(segment.cache_key(&input).unwrap(), key);
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn upstream_comes_from_git_config() {
+        let repo = std::env::temp_dir().join(format!("ccline-git-upstream-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo);
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(
+            repo.join(".git/config"),
+            "[core]\n\tbare = false\n[branch \"main\"]\n\tremote = origin\n\tmerge = refs/heads/main\n\
+             [branch \"topic\"]\n\tremote = .\n\tmerge = refs/heads/main\n",
+        )
+        .unwrap();
+        let dir = repo.to_string_lossy();
+
+        assert_eq!(
+            GitSegment::get_upstream(&dir, "main").as_deref(),
+            Some("origin/main")
+        );
+        assert_eq!(
+            GitSegment::get_upstream(&dir, "topic").as_deref(),
+            Some("main")
+        );
+        assert_eq!(GitSegment::get_upstream(&dir, "other"), None);
+        let _ = fs::remove_dir_all(&repo);
+    }
 }
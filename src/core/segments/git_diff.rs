@@ -0,0 +1,108 @@
+use super::git::GIT_TIMEOUT;
+use super::{GitSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Lines added and removed in the working tree, like `git diff --shortstat`
+pub struct GitDiffSegment {
+    include_staged: bool,
+    timeout: Duration,
+}
+
+impl Default for GitDiffSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitDiffSegment {
+    pub fn new() -> Self {
+        Self {
+            include_staged: true,
+            timeout: GIT_TIMEOUT,
+        }
+    }
+
+    /// Count staged changes too (diff against HEAD) rather than only unstaged ones
+    pub fn with_staged(mut self, include_staged: bool) -> Self {
+        self.include_staged = include_staged;
+        self
+    }
+
+    /// `git diff` is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Files, insertions and deletions; `None` when git fails or is too slow
+    fn shortstat(&self, root: &Path) -> Option<(u64, u64, u64)> {
+        let mut args = vec!["--no-optional-locks", "diff", "--shortstat"];
+        if self.include_staged {
+            args.push("HEAD");
+        }
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(root)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        // " 3 files changed, 42 insertions(+), 7 deletions(-)"; absent parts are zero
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let (mut files, mut insertions, mut deletions) = (0, 0, 0);
+        for part in stdout.trim().split(',') {
+            let mut words = part.split_whitespace();
+            let (Some(count), Some(kind)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let Ok(count) = count.parse() else {
+                continue;
+            };
+            if kind.starts_with("file") {
+                files = count;
+            } else if kind.starts_with("insertion") {
+                insertions = count;
+            } else if kind.starts_with("deletion") {
+                deletions = count;
+            }
+        }
+        Some((files, insertions, deletions))
+    }
+}
+
+impl Segment for GitDiffSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (root, _) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let (files, insertions, deletions) = self.shortstat(&root)?;
+
+        // A clean tree, or one with only mode changes or binary files
+        if insertions == 0 && deletions == 0 {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("files".to_string(), files.to_string());
+        metadata.insert("insertions".to_string(), insertions.to_string());
+        metadata.insert("deletions".to_string(), deletions.to_string());
+        metadata.insert("staged".to_string(), self.include_staged.to_string());
+
+        // Deletions go in secondary so themes can color them apart via `secondary_color`
+        Some(SegmentData {
+            primary: format!("+{}", insertions),
+            secondary: format!("-{}", deletions),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::GitDiff
+    }
+}
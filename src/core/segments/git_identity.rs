@@ -0,0 +1,147 @@
+use super::git::GIT_TIMEOUT;
+use super::{GitSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// How a matching email is shown
+#[derive(Debug, Clone, Default)]
+pub struct IdentityStyle {
+    /// Replaces the email, e.g. "WORK"
+    pub label: Option<String>,
+    /// Picks the `<level>_color` option, e.g. "warning" or a custom "work"
+    pub level: Option<String>,
+}
+
+/// The git identity commits in the workspace repo would be made with
+pub struct GitIdentitySegment {
+    identities: HashMap<String, IdentityStyle>,
+    default_identity: String,
+    timeout: Duration,
+}
+
+impl Default for GitIdentitySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitIdentitySegment {
+    pub fn new() -> Self {
+        Self {
+            identities: HashMap::new(),
+            default_identity: String::new(),
+            timeout: GIT_TIMEOUT,
+        }
+    }
+
+    /// Labels and levels keyed by email pattern, e.g. "@work.com"
+    pub fn with_identities(mut self, identities: HashMap<String, IdentityStyle>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    /// Email pattern of the usual identity; the segment hides while it is in effect
+    pub fn with_default_identity(mut self, default_identity: impl Into<String>) -> Self {
+        self.default_identity = default_identity.into();
+        self
+    }
+
+    /// `git config` is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Effective value of `key` as git resolves it in `root`, conditional includes included
+    fn config_value(&self, root: &Path, key: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .current_dir(root)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Case-insensitive substring match against an already lowercased email
+    fn matches(pattern: &str, email: &str) -> bool {
+        !pattern.is_empty() && email.contains(&pattern.to_lowercase())
+    }
+
+    /// Exact match first, then the longest pattern contained in the email
+    fn style_for(&self, email: &str) -> Option<&IdentityStyle> {
+        if let Some((_, style)) = self
+            .identities
+            .iter()
+            .find(|(pattern, _)| pattern.to_lowercase() == email)
+        {
+            return Some(style);
+        }
+
+        self.identities
+            .iter()
+            .filter(|(pattern, _)| Self::matches(pattern, email))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, style)| style)
+    }
+}
+
+impl Segment for GitIdentitySegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (root, _) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let email = self.config_value(&root, "user.email")?;
+        let email_lower = email.to_lowercase();
+        if Self::matches(&self.default_identity, &email_lower) {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("email".to_string(), email.clone());
+        if let Some(name) = self.config_value(&root, "user.name") {
+            metadata.insert("name".to_string(), name);
+        }
+
+        let style = self.style_for(&email_lower);
+        if let Some(level) = style.and_then(|style| style.level.clone()) {
+            metadata.insert("level".to_string(), level);
+        }
+        let primary = match style.and_then(|style| style.label.clone()) {
+            Some(label) => {
+                metadata.insert("label".to_string(), label.clone());
+                label
+            }
+            None => email,
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::GitIdentity
+    }
+
+    /// Spawning git twice per render adds up; the identity only changes with git config
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        // Labels and levels end up in the cached result, so they are part of the key
+        let identities: BTreeMap<_, _> = self
+            .identities
+            .iter()
+            .map(|(pattern, style)| (pattern, (&style.label, &style.level)))
+            .collect();
+        Some(format!(
+            "{}\0{}\0{:?}",
+            input.workspace.current_dir, self.default_identity, identities
+        ))
+    }
+}
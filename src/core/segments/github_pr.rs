@@ -0,0 +1,176 @@
+use super::{GitSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+/// Combined result of a pull request's checks
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CiStatus {
+    Passing,
+    Failing,
+    Pending,
+}
+
+#[derive(Debug, Clone)]
+struct PullRequest {
+    number: u64,
+    ci: Option<CiStatus>,
+}
+
+/// Number and CI status of the current branch's open pull request, from the `gh` CLI
+pub struct GithubPrSegment {
+    timeout: Duration,
+    cache_ttl: Duration,
+}
+
+impl Default for GithubPrSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GithubPrSegment {
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_millis(1500),
+            cache_ttl: Duration::from_secs(120),
+        }
+    }
+
+    /// `gh` is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long a lookup, including a failed one, is reused for the same repo and branch
+    /// (zero asks `gh` on every render)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Failing if any check failed, else pending while any runs, else passing
+    fn ci_status(checks: &[serde_json::Value]) -> Option<CiStatus> {
+        if checks.is_empty() {
+            return None;
+        }
+
+        let field = |check: &serde_json::Value, key: &str| {
+            check
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_uppercase()
+        };
+        let mut pending = false;
+        for check in checks {
+            // Check runs report status + conclusion, commit statuses a single state
+            let outcome = match field(check, "__typename").as_str() {
+                "STATUSCONTEXT" => field(check, "state"),
+                _ if field(check, "status") != "COMPLETED" => "PENDING".to_string(),
+                _ => field(check, "conclusion"),
+            };
+            match outcome.as_str() {
+                "FAILURE" | "ERROR" | "CANCELLED" | "TIMED_OUT" | "ACTION_REQUIRED"
+                | "STARTUP_FAILURE" => return Some(CiStatus::Failing),
+                "PENDING" | "EXPECTED" | "" => pending = true,
+                _ => {}
+            }
+        }
+
+        Some(if pending {
+            CiStatus::Pending
+        } else {
+            CiStatus::Passing
+        })
+    }
+
+    /// Repository root and checked out branch; detached HEAD has no branch to look a PR up by
+    fn repo_branch(input: &InputData) -> Option<(PathBuf, String)> {
+        let (root, git_dir) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+        let branch = head.trim().strip_prefix("ref: refs/heads/")?.to_string();
+        Some((root, branch))
+    }
+
+    /// `None` when gh is missing, logged out, times out or the branch has no open PR
+    fn query(&self, repo_root: &str) -> Option<PullRequest> {
+        let output = Command::new("gh")
+            .args(["pr", "view", "--json", "number,state,statusCheckRollup"])
+            .current_dir(repo_root)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let pr: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        if pr.get("state").and_then(|v| v.as_str()) != Some("OPEN") {
+            return None;
+        }
+        let checks = pr
+            .get("statusCheckRollup")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        Some(PullRequest {
+            number: pr.get("number")?.as_u64()?,
+            ci: Self::ci_status(checks),
+        })
+    }
+}
+
+impl Segment for GithubPrSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (root, _) = Self::repo_branch(input)?;
+        let pr = self.query(&root.to_string_lossy())?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("number".to_string(), pr.number.to_string());
+        let symbol = match pr.ci {
+            Some(CiStatus::Passing) => {
+                metadata.insert("ci".to_string(), "passing".to_string());
+                " ✓"
+            }
+            Some(CiStatus::Failing) => {
+                metadata.insert("ci".to_string(), "failing".to_string());
+                metadata.insert("level".to_string(), "critical".to_string());
+                " ✗"
+            }
+            Some(CiStatus::Pending) => {
+                metadata.insert("ci".to_string(), "pending".to_string());
+                metadata.insert("level".to_string(), "warning".to_string());
+                " …"
+            }
+            None => "",
+        };
+
+        Some(SegmentData {
+            primary: format!("#{}{}", pr.number, symbol),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::GithubPr
+    }
+
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        if self.cache_ttl.is_zero() {
+            return None;
+        }
+        let (root, branch) = Self::repo_branch(input)?;
+        Some(format!("{}\0{}", root.to_string_lossy(), branch))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
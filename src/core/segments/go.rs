@@ -0,0 +1,125 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+const GO_TIMEOUT: Duration = Duration::from_millis(1000);
+
+pub struct GoSegment {
+    max_depth: usize,
+    check_installed: bool,
+}
+
+impl Default for GoSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoSegment {
+    pub fn new() -> Self {
+        Self {
+            max_depth: 5,
+            check_installed: false,
+        }
+    }
+
+    /// How many parent directories to search for go.mod
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Compare against `go version` and flag an installed toolchain older than go.mod requires
+    pub fn with_check_installed(mut self, check_installed: bool) -> Self {
+        self.check_installed = check_installed;
+        self
+    }
+
+    fn find_go_mod(&self, working_dir: &str) -> Option<PathBuf> {
+        Path::new(working_dir)
+            .ancestors()
+            .take(self.max_depth + 1)
+            .map(|dir| dir.join("go.mod"))
+            .find(|path| path.is_file())
+    }
+
+    /// Read the `go 1.xx` directive from go.mod
+    fn go_directive(go_mod: &Path) -> Option<String> {
+        fs::read_to_string(go_mod).ok()?.lines().find_map(|line| {
+            let version = line.trim().strip_prefix("go ")?.trim();
+            if version.is_empty() {
+                None
+            } else {
+                Some(version.to_string())
+            }
+        })
+    }
+
+    /// Parse `go version`, e.g. "go version go1.22.3 linux/amd64"
+    fn installed_version(dir: &Path) -> Option<String> {
+        let output = Command::new("go")
+            .arg("version")
+            .current_dir(dir)
+            .output_with_timeout(GO_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8(output.stdout).ok()?;
+        let version = text.split_whitespace().nth(2)?.strip_prefix("go")?;
+        Some(version.to_string())
+    }
+
+    /// Numeric components of a Go version, ignoring pre-release suffixes like "rc1"
+    fn version_parts(version: &str) -> Vec<u32> {
+        version
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+impl Segment for GoSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let go_mod = self.find_go_mod(&input.workspace.current_dir)?;
+        let required = Self::go_directive(&go_mod)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("go_mod".to_string(), go_mod.display().to_string());
+        metadata.insert("required".to_string(), required.clone());
+
+        let mut secondary = String::new();
+        if self.check_installed {
+            let module_dir = go_mod.parent().unwrap_or(Path::new("."));
+            if let Some(installed) = Self::installed_version(module_dir) {
+                if Self::version_parts(&installed) < Self::version_parts(&required) {
+                    metadata.insert("level".to_string(), "warning".to_string());
+                    secondary = format!("≠ {}", installed);
+                }
+                metadata.insert("installed".to_string(), installed);
+            }
+        }
+
+        Some(SegmentData {
+            primary: required,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Go
+    }
+}
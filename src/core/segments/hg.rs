@@ -0,0 +1,226 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Upper bound for `hg status`, which starts a Python interpreter
+const HG_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Marker files Mercurial leaves while an operation is interrupted, with their labels
+const OPERATIONS: [(&str, &str); 5] = [
+    ("merge/state2", "MERGING"),
+    ("merge/state", "MERGING"),
+    ("rebasestate", "REBASE"),
+    ("histedit-state", "HISTEDIT"),
+    ("graftstate", "GRAFTING"),
+];
+
+/// Branch or active bookmark of a Mercurial working copy, read from `.hg/` directly
+pub struct HgSegment {
+    show_status: bool,
+    dirty_symbol: String,
+    clean_symbol: String,
+    cache_ttl: Duration,
+}
+
+impl Default for HgSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HgSegment {
+    pub fn new() -> Self {
+        Self {
+            show_status: true,
+            dirty_symbol: "●".to_string(),
+            clean_symbol: "✓".to_string(),
+            cache_ttl: Duration::from_millis(2000),
+        }
+    }
+
+    /// Run `hg status` to detect uncommitted changes; disable on very large repositories
+    pub fn with_status(mut self, show_status: bool) -> Self {
+        self.show_status = show_status;
+        self
+    }
+
+    pub fn with_dirty_symbol(mut self, dirty_symbol: impl Into<String>) -> Self {
+        self.dirty_symbol = dirty_symbol.into();
+        self
+    }
+
+    /// Symbol shown for a clean working copy, empty to show nothing
+    pub fn with_clean_symbol(mut self, clean_symbol: impl Into<String>) -> Self {
+        self.clean_symbol = clean_symbol.into();
+        self
+    }
+
+    /// How long the result is reused while the dirstate is unchanged (zero disables the cache)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    fn find_repo(working_dir: &str) -> Option<&Path> {
+        Path::new(working_dir)
+            .ancestors()
+            .find(|dir| dir.join(".hg").is_dir())
+    }
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        let content = fs::read_to_string(path).ok()?;
+        let content = content.trim();
+        (!content.is_empty()).then(|| content.to_string())
+    }
+
+    /// An interrupted merge, rebase, histedit or graft, from its state file
+    fn get_operation(hg_dir: &Path) -> Option<&'static str> {
+        OPERATIONS
+            .iter()
+            .find(|(marker, _)| hg_dir.join(marker).exists())
+            .map(|(_, label)| *label)
+    }
+
+    /// Modified, added or removed files, ignoring untracked ones like `git status -uno`;
+    /// `None` when hg is missing or too slow
+    fn has_changes(root: &Path) -> Option<bool> {
+        let output = Command::new("hg")
+            .args(["status", "--quiet"])
+            .env("HGPLAIN", "1")
+            .current_dir(root)
+            .output_with_timeout(HG_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(!output.stdout.iter().all(u8::is_ascii_whitespace))
+    }
+}
+
+impl Segment for HgSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let root = Self::find_repo(&input.workspace.current_dir)?;
+        let hg_dir = root.join(".hg");
+
+        // `.hg/branch` is absent on the default branch
+        let branch =
+            Self::read_trimmed(&hg_dir.join("branch")).unwrap_or_else(|| "default".to_string());
+        let bookmark = Self::read_trimmed(&hg_dir.join("bookmarks.current"));
+        let operation = Self::get_operation(&hg_dir);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("branch".to_string(), branch.clone());
+        if let Some(ref bookmark) = bookmark {
+            metadata.insert("bookmark".to_string(), bookmark.clone());
+        }
+
+        let mut status_parts = Vec::new();
+        if let Some(operation) = operation {
+            metadata.insert("operation".to_string(), operation.to_string());
+            metadata.insert("level".to_string(), "warning".to_string());
+            status_parts.push(operation.to_string());
+        }
+
+        // An interrupted operation always leaves changes behind; only otherwise ask hg
+        let dirty = if operation.is_some() {
+            Some(true)
+        } else if self.show_status {
+            Self::has_changes(root)
+        } else {
+            None
+        };
+        match dirty {
+            Some(true) => status_parts.push(self.dirty_symbol.clone()),
+            Some(false) if !self.clean_symbol.is_empty() => {
+                status_parts.push(self.clean_symbol.clone())
+            }
+            _ => {}
+        }
+        if let Some(dirty) = dirty {
+            metadata.insert("dirty".to_string(), dirty.to_string());
+        }
+
+        Some(SegmentData {
+            primary: bookmark.unwrap_or(branch),
+            secondary: status_parts.join(" "),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Hg
+    }
+
+    /// Only worth caching when `hg status` runs. Reused while the dirstate, which hg
+    /// rewrites on every status-changing command, and the branch and bookmark are unchanged
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        if !self.show_status || self.cache_ttl.is_zero() {
+            return None;
+        }
+        let root = Self::find_repo(&input.workspace.current_dir)?;
+        let hg_dir = root.join(".hg");
+        let dirstate_mtime_ns = fs::metadata(hg_dir.join("dirstate"))
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |since| since.as_nanos());
+        Some(format!(
+            "{}\0{}\0{}\0{}\0{}\0{}\0{}",
+            root.to_string_lossy(),
+            dirstate_mtime_ns,
+            Self::read_trimmed(&hg_dir.join("branch")).unwrap_or_default(),
+            Self::read_trimmed(&hg_dir.join("bookmarks.current")).unwrap_or_default(),
+            Self::get_operation(&hg_dir).unwrap_or_default(),
+            self.dirty_symbol,
+            self.clean_symbol
+        ))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Workspace;
+
+    #[test]
+    fn cache_key_follows_dirstate_and_options() {
+        let repo = std::env::temp_dir().join(format!("ccline-hg-key-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo);
+        fs::create_dir_all(repo.join(".hg")).unwrap();
+        fs::write(repo.join(".hg/dirstate"), "a").unwrap();
+        let input = InputData {
+            workspace: Workspace {
+                current_dir: repo.to_string_lossy().to_string(),
+            },
+            ..InputData::default()
+        };
+
+        let segment = HgSegment::new();
+        let key = segment.cache_key(&input).unwrap();
+        assert_eq!(segment.cache_key(&input).unwrap(), key);
+        assert_ne!(
+            HgSegment::new()
+                .with_clean_symbol("")
+                .cache_key(&input)
+                .unwrap(),
+            key
+        );
+        assert!(HgSegment::new()
+            .with_status(false)
+            .cache_key(&input)
+            .is_none());
+
+        fs::write(repo.join(".hg/bookmarks.current"), "feature").unwrap();
+        assert_ne!(segment.cache_key(&input).unwrap(), key);
+        let _ = fs::remove_dir_all(&repo);
+    }
+}
@@ -0,0 +1,111 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+
+pub struct HostnameSegment {
+    strip_domain: bool,
+    ssh_only: bool,
+}
+
+impl Default for HostnameSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HostnameSegment {
+    pub fn new() -> Self {
+        Self {
+            strip_domain: true,
+            ssh_only: false,
+        }
+    }
+
+    pub fn with_strip_domain(mut self, strip_domain: bool) -> Self {
+        self.strip_domain = strip_domain;
+        self
+    }
+
+    pub fn with_ssh_only(mut self, ssh_only: bool) -> Self {
+        self.ssh_only = ssh_only;
+        self
+    }
+
+    /// Whether this process is running inside an SSH session
+    pub fn is_ssh_session() -> bool {
+        ["SSH_CONNECTION", "SSH_TTY", "SSH_CLIENT"]
+            .iter()
+            .any(|var| env::var_os(var).is_some_and(|v| !v.is_empty()))
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn read_hostname() -> Option<String> {
+        use crate::utils::OutputWithTimeout;
+        use std::process::Command;
+        use std::time::Duration;
+
+        let from_file = ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .map(|s| s.trim().to_string())
+            .find(|s| !s.is_empty());
+        if from_file.is_some() {
+            return from_file;
+        }
+
+        // macOS and the BSDs don't expose the hostname as a file
+        let output = Command::new("hostname")
+            .output_with_timeout(Duration::from_millis(500))
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let hostname = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        if hostname.is_empty() {
+            None
+        } else {
+            Some(hostname)
+        }
+    }
+
+    #[cfg(windows)]
+    pub(crate) fn read_hostname() -> Option<String> {
+        env::var("COMPUTERNAME").ok().filter(|s| !s.is_empty())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub(crate) fn read_hostname() -> Option<String> {
+        None
+    }
+}
+
+impl Segment for HostnameSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let is_ssh = Self::is_ssh_session();
+        if self.ssh_only && !is_ssh {
+            return None;
+        }
+
+        let hostname = Self::read_hostname()?;
+        let primary = if self.strip_domain {
+            hostname.split('.').next().unwrap_or(&hostname).to_string()
+        } else {
+            hostname.clone()
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("hostname".to_string(), hostname);
+        metadata.insert("ssh".to_string(), is_ssh.to_string());
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Hostname
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Build files that mark a JVM project, with the tool they belong to
+const BUILD_FILES: [(&str, &str); 3] = [
+    ("pom.xml", "maven"),
+    ("build.gradle", "gradle"),
+    ("build.gradle.kts", "gradle"),
+];
+
+pub struct JavaSegment {
+    show_build_tool: bool,
+}
+
+impl Default for JavaSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JavaSegment {
+    pub fn new() -> Self {
+        Self {
+            show_build_tool: true,
+        }
+    }
+
+    pub fn with_build_tool(mut self, show_build_tool: bool) -> Self {
+        self.show_build_tool = show_build_tool;
+        self
+    }
+
+    fn build_tool(dir: &Path) -> Option<&'static str> {
+        BUILD_FILES
+            .iter()
+            .find(|(file, _)| dir.join(file).is_file())
+            .map(|(_, tool)| *tool)
+    }
+
+    /// Read JAVA_VERSION from `$JAVA_HOME/release` instead of spawning `java -version`
+    fn java_version() -> Option<String> {
+        let java_home = env::var_os("JAVA_HOME").filter(|home| !home.is_empty())?;
+        let release = fs::read_to_string(Path::new(&java_home).join("release")).ok()?;
+        release.lines().find_map(|line| {
+            let value = line.strip_prefix("JAVA_VERSION=")?.trim().trim_matches('"');
+            if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        })
+    }
+}
+
+impl Segment for JavaSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let tool = Self::build_tool(Path::new(&input.workspace.current_dir))?;
+        let version = Self::java_version();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("build_tool".to_string(), tool.to_string());
+
+        let (primary, secondary) = match version {
+            Some(version) => {
+                metadata.insert("version".to_string(), version.clone());
+                let secondary = if self.show_build_tool {
+                    tool.to_string()
+                } else {
+                    String::new()
+                };
+                (version, secondary)
+            }
+            // Without a JDK to read, the build tool is all we know
+            None => (tool.to_string(), String::new()),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Java
+    }
+}
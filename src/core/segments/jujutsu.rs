@@ -0,0 +1,136 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentConfig, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Upper bound for the `jj log` call
+const JJ_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// One tab-separated line: change id, bookmarks, divergent, conflict
+const TEMPLATE: &str = r#"change_id.shortest(8) ++ "\t" ++ bookmarks.join(" ") ++ "\t" ++ if(divergent, "1") ++ "\t" ++ if(conflict, "1")"#;
+
+/// VCS segments that may win in a colocated jj/git repository
+pub const VCS_PREFERENCES: [&str; 2] = ["jj", "git"];
+
+/// Change id, bookmarks and conflict state of the working-copy change in a Jujutsu repository
+#[derive(Default)]
+pub struct JujutsuSegment;
+
+impl JujutsuSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn find_repo(working_dir: &str) -> Option<&Path> {
+        Path::new(working_dir)
+            .ancestors()
+            .find(|dir| dir.join(".jj").is_dir())
+    }
+
+    /// `--ignore-working-copy` skips the snapshot jj would otherwise take, which is slow
+    /// on large trees and would record a new operation on every render
+    fn describe_working_copy(root: &Path) -> Option<(String, String, bool, bool)> {
+        let output = Command::new("jj")
+            .args([
+                "log",
+                "--ignore-working-copy",
+                "--no-graph",
+                "--color",
+                "never",
+                "-r",
+                "@",
+                "-T",
+                TEMPLATE,
+            ])
+            .current_dir(root)
+            .output_with_timeout(JJ_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let mut fields = stdout.trim_end_matches('\n').split('\t');
+        let change_id = fields.next()?.trim().to_string();
+        if change_id.is_empty() {
+            return None;
+        }
+        let bookmarks = fields.next().unwrap_or("").trim().to_string();
+        let divergent = fields.next() == Some("1");
+        let conflict = fields.next() == Some("1");
+        Some((change_id, bookmarks, divergent, conflict))
+    }
+}
+
+impl Segment for JujutsuSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let root = Self::find_repo(&input.workspace.current_dir)?;
+        let (change_id, bookmarks, divergent, conflict) = Self::describe_working_copy(root)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("change_id".to_string(), change_id.clone());
+        metadata.insert("bookmarks".to_string(), bookmarks.clone());
+        metadata.insert("divergent".to_string(), divergent.to_string());
+        metadata.insert("conflict".to_string(), conflict.to_string());
+        metadata.insert(
+            "colocated".to_string(),
+            root.join(".git").exists().to_string(),
+        );
+
+        let mut secondary = Vec::new();
+        if !bookmarks.is_empty() {
+            secondary.push(bookmarks);
+        }
+        if conflict {
+            secondary.push("⚠".to_string());
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if divergent {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            // jj itself marks divergent change ids with "??"
+            primary: if divergent {
+                format!("{}??", change_id)
+            } else {
+                change_id
+            },
+            secondary: secondary.join(" "),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Jujutsu
+    }
+}
+
+/// In a colocated repository both the git and jj segments render; keep only the one named
+/// by the jujutsu segment's `prefer` option ("jj" by default). Elsewhere at most one of
+/// them finds a repository, so nothing is dropped.
+pub fn resolve_colocated(
+    mut collected: Vec<(SegmentConfig, SegmentData)>,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    let Some((jj_config, _)) = collected.iter().find(|(config, data)| {
+        config.id == SegmentId::Jujutsu
+            && data.metadata.get("colocated").map(String::as_str) == Some("true")
+    }) else {
+        return collected;
+    };
+
+    let loser = match jj_config.options.get("prefer").and_then(|v| v.as_str()) {
+        Some("git") => SegmentId::Jujutsu,
+        _ => SegmentId::Git,
+    };
+    if collected
+        .iter()
+        .any(|(config, _)| config.id == SegmentId::Git)
+    {
+        collected.retain(|(config, _)| config.id != loser);
+    }
+
+    collected
+}
@@ -0,0 +1,134 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize)]
+struct KubeConfig {
+    #[serde(rename = "current-context", default)]
+    current_context: Option<String>,
+    #[serde(default)]
+    contexts: Vec<NamedContext>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedContext {
+    name: String,
+    #[serde(default)]
+    context: Option<ContextDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContextDetails {
+    #[serde(default)]
+    namespace: Option<String>,
+}
+
+pub struct KubernetesSegment {
+    show_namespace: bool,
+    dangerous_contexts: Vec<String>,
+}
+
+impl Default for KubernetesSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KubernetesSegment {
+    pub fn new() -> Self {
+        Self {
+            show_namespace: true,
+            dangerous_contexts: Vec::new(),
+        }
+    }
+
+    pub fn with_namespace(mut self, show_namespace: bool) -> Self {
+        self.show_namespace = show_namespace;
+        self
+    }
+
+    /// Regex patterns for contexts rendered in the critical color, e.g. "prod"
+    pub fn with_dangerous_contexts(mut self, dangerous_contexts: Vec<String>) -> Self {
+        self.dangerous_contexts = dangerous_contexts;
+        self
+    }
+
+    /// Kubeconfig files in precedence order: every entry of $KUBECONFIG, else ~/.kube/config
+    fn kubeconfig_paths() -> Vec<PathBuf> {
+        match env::var_os("KUBECONFIG").filter(|v| !v.is_empty()) {
+            Some(list) => env::split_paths(&list)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect(),
+            None => dirs::home_dir()
+                .map(|home| vec![home.join(".kube").join("config")])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Resolve the current context and its namespace, merging files the way kubectl does:
+    /// the first file to set a value wins
+    fn current_context() -> Option<(String, Option<String>)> {
+        let configs: Vec<KubeConfig> = Self::kubeconfig_paths()
+            .into_iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter_map(|content| serde_yaml::from_str(&content).ok())
+            .collect();
+
+        let context = configs
+            .iter()
+            .find_map(|config| config.current_context.clone())
+            .filter(|context| !context.is_empty())?;
+
+        let namespace = configs
+            .iter()
+            .flat_map(|config| &config.contexts)
+            .find(|named| named.name == context)
+            .and_then(|named| named.context.as_ref())
+            .and_then(|details| details.namespace.clone());
+
+        Some((context, namespace))
+    }
+
+    fn is_dangerous(&self, context: &str) -> bool {
+        self.dangerous_contexts
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .any(|re| re.is_match(context))
+    }
+}
+
+impl Segment for KubernetesSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let (context, namespace) = Self::current_context()?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("context".to_string(), context.clone());
+        if let Some(ref namespace) = namespace {
+            metadata.insert("namespace".to_string(), namespace.clone());
+        }
+        if self.is_dangerous(&context) {
+            metadata.insert("level".to_string(), "critical".to_string());
+        }
+
+        let secondary = if self.show_namespace {
+            namespace.unwrap_or_else(|| "default".to_string())
+        } else {
+            String::new()
+        };
+
+        Some(SegmentData {
+            primary: context,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Kubernetes
+    }
+}
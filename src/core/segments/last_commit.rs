@@ -0,0 +1,101 @@
+use super::git::GIT_TIMEOUT;
+use super::{GitSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Time since the last commit, as a nudge to commit checkpoints during long sessions
+pub struct LastCommitSegment {
+    warning_minutes: u64,
+    critical_minutes: u64,
+    timeout: Duration,
+}
+
+impl Default for LastCommitSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LastCommitSegment {
+    pub fn new() -> Self {
+        Self {
+            warning_minutes: 60,
+            critical_minutes: 180,
+            timeout: GIT_TIMEOUT,
+        }
+    }
+
+    /// Age in minutes at which `warning_color` and `critical_color` apply
+    pub fn with_thresholds(mut self, warning_minutes: u64, critical_minutes: u64) -> Self {
+        self.warning_minutes = warning_minutes;
+        self.critical_minutes = critical_minutes;
+        self
+    }
+
+    /// `git log` is killed and the segment hidden once this elapses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Committer time of HEAD in Unix seconds; `None` without commits or when git fails
+    fn head_commit_time(&self, root: &Path) -> Option<u64> {
+        let output = Command::new("git")
+            .args(["--no-optional-locks", "log", "-1", "--format=%ct"])
+            .current_dir(root)
+            .output_with_timeout(self.timeout)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+    }
+
+    /// Largest whole unit only: "45s", "12m", "2h", "3d"
+    fn format_age(seconds: u64) -> String {
+        if seconds < 60 {
+            format!("{}s", seconds)
+        } else if seconds < 3_600 {
+            format!("{}m", seconds / 60)
+        } else if seconds < 86_400 {
+            format!("{}h", seconds / 3_600)
+        } else {
+            format!("{}d", seconds / 86_400)
+        }
+    }
+}
+
+impl Segment for LastCommitSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (root, _) = GitSegment::find_repo(&input.workspace.current_dir)?;
+        let committed_at = self.head_commit_time(&root)?;
+
+        // A commit dated in the future (clock skew) counts as just made
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let age = now.saturating_sub(committed_at);
+        let minutes = age / 60;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("committed_at".to_string(), committed_at.to_string());
+        metadata.insert("age_seconds".to_string(), age.to_string());
+        if minutes >= self.critical_minutes {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if minutes >= self.warning_minutes {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: Self::format_age(age),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::LastCommit
+    }
+}
@@ -0,0 +1,196 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Bytes read per step while walking the transcript backwards
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Give up after this much of the transcript's tail; long tool outputs can push the
+/// last prompt far back, but rendering must stay fast
+const MAX_SCAN_BYTES: u64 = 8 * 1024 * 1024;
+
+/// The fields of a transcript line needed to recognise a typed prompt
+#[derive(Deserialize)]
+struct PromptEntry {
+    r#type: Option<String>,
+    #[serde(rename = "isMeta", default)]
+    is_meta: bool,
+    #[serde(rename = "isSidechain", default)]
+    is_sidechain: bool,
+    message: Option<PromptMessage>,
+}
+
+#[derive(Deserialize)]
+struct PromptMessage {
+    content: serde_json::Value,
+}
+
+/// Preview of the most recent prompt typed into the session
+pub struct LastPromptSegment {
+    max_length: usize,
+}
+
+impl Default for LastPromptSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LastPromptSegment {
+    pub fn new() -> Self {
+        Self { max_length: 40 }
+    }
+
+    /// Longest preview in characters (grapheme clusters), including the trailing "…"
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Walk the transcript from the end in chunks, so only its tail is ever read
+    fn find_last_prompt(path: &Path) -> Option<String> {
+        let mut file = fs::File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+        let stop = len.saturating_sub(MAX_SCAN_BYTES);
+
+        let mut pos = len;
+        // Start of the earliest line seen so far, which may still be incomplete
+        let mut carry: Vec<u8> = Vec::new();
+
+        while pos > stop {
+            let start = pos.saturating_sub(CHUNK_SIZE).max(stop);
+            let mut chunk = vec![0; (pos - start) as usize];
+            file.seek(SeekFrom::Start(start)).ok()?;
+            file.read_exact(&mut chunk).ok()?;
+            chunk.extend_from_slice(&carry);
+            pos = start;
+
+            // Everything before the first newline may continue in the previous chunk
+            let complete_from = if pos == 0 {
+                0
+            } else {
+                match chunk.iter().position(|&b| b == b'\n') {
+                    Some(newline) => newline + 1,
+                    None => {
+                        carry = chunk;
+                        continue;
+                    }
+                }
+            };
+
+            for line in chunk[complete_from..].split(|&b| b == b'\n').rev() {
+                if let Some(prompt) = Self::parse_prompt(line) {
+                    return Some(prompt);
+                }
+            }
+            carry = chunk[..complete_from].to_vec();
+        }
+
+        None
+    }
+
+    /// The prompt text of a line, if it is a user message typed by the user rather than
+    /// a tool result, a meta message or a sidechain (subagent) message
+    fn parse_prompt(line: &[u8]) -> Option<String> {
+        // Cheap filter before parsing; most lines are assistant messages
+        if !line.windows(6).any(|window| window == b"\"user\"") {
+            return None;
+        }
+
+        let entry: PromptEntry = serde_json::from_slice(line).ok()?;
+        if entry.r#type.as_deref() != Some("user") || entry.is_meta || entry.is_sidechain {
+            return None;
+        }
+
+        let text = match entry.message?.content {
+            serde_json::Value::String(text) => text,
+            serde_json::Value::Array(blocks) => {
+                let mut texts = Vec::new();
+                for block in &blocks {
+                    match block.get("type").and_then(|t| t.as_str()) {
+                        Some("tool_result") => return None,
+                        Some("text") => {
+                            if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                                texts.push(text);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                texts.join(" ")
+            }
+            _ => return None,
+        };
+
+        Self::clean_prompt(&text)
+    }
+
+    /// Slash commands are recorded as tagged text; show them as typed. Command output
+    /// and interruption notices aren't prompts.
+    fn clean_prompt(text: &str) -> Option<String> {
+        let text = text.trim();
+        if text.starts_with("<local-command-") || text.starts_with("[Request interrupted") {
+            return None;
+        }
+
+        let text = match Self::tag_content(text, "command-name") {
+            Some(name) => match Self::tag_content(text, "command-args") {
+                Some(args) if !args.is_empty() => format!("{} {}", name, args),
+                _ => name.to_string(),
+            },
+            None => text.to_string(),
+        };
+
+        // Collapse newlines and runs of spaces so the preview stays on one line
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn tag_content<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+        let open = format!("<{}>", tag);
+        let close = format!("</{}>", tag);
+        let start = text.find(&open)? + open.len();
+        let end = start + text[start..].find(&close)?;
+        Some(text[start..end].trim())
+    }
+
+    /// Cut on grapheme boundaries so emoji and combining marks are never split
+    fn truncate(&self, text: &str) -> String {
+        if self.max_length == 0 || text.graphemes(true).count() <= self.max_length {
+            return text.to_string();
+        }
+
+        let kept: String = text
+            .graphemes(true)
+            .take(self.max_length.saturating_sub(1))
+            .collect();
+        format!("{}…", kept.trim_end())
+    }
+}
+
+impl Segment for LastPromptSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        if input.transcript_path.is_empty() {
+            return None;
+        }
+        let prompt = Self::find_last_prompt(Path::new(&input.transcript_path))?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("prompt".to_string(), prompt.clone());
+
+        Some(SegmentData {
+            primary: self.truncate(&prompt),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::LastPrompt
+    }
+}
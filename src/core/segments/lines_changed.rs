@@ -0,0 +1,63 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::abbreviate_number;
+use std::collections::HashMap;
+
+pub struct LinesChangedSegment {
+    compact_threshold: u32,
+}
+
+impl Default for LinesChangedSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinesChangedSegment {
+    pub fn new() -> Self {
+        Self {
+            compact_threshold: 1000,
+        }
+    }
+
+    /// Counts at or above this are shortened, e.g. 1234 -> "1.2k"
+    pub fn with_compact_threshold(mut self, compact_threshold: u32) -> Self {
+        self.compact_threshold = compact_threshold;
+        self
+    }
+
+    fn format_count(&self, count: u32) -> String {
+        if count < self.compact_threshold {
+            return count.to_string();
+        }
+
+        abbreviate_number(count as f64, 1)
+    }
+}
+
+impl Segment for LinesChangedSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let cost = input.cost.as_ref()?;
+        let added = cost.total_lines_added.unwrap_or(0);
+        let removed = cost.total_lines_removed.unwrap_or(0);
+
+        if added == 0 && removed == 0 {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("added".to_string(), added.to_string());
+        metadata.insert("removed".to_string(), removed.to_string());
+
+        // Removed lines go in secondary so themes can color them apart via `secondary_color`
+        Some(SegmentData {
+            primary: format!("+{}", self.format_count(added)),
+            secondary: format!("-{}", self.format_count(removed)),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::LinesChanged
+    }
+}
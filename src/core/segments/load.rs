@@ -0,0 +1,160 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadDisplay {
+    /// The 1-minute load average as reported by the OS, e.g. "2.41"
+    Raw,
+    /// Load divided by core count as a percentage, e.g. "30%"
+    Normalized,
+}
+
+impl LoadDisplay {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "raw" => Some(LoadDisplay::Raw),
+            "normalized" | "percent" => Some(LoadDisplay::Normalized),
+            _ => None,
+        }
+    }
+}
+
+pub struct LoadSegment {
+    display: LoadDisplay,
+    warning_threshold: f64,
+    critical_threshold: f64,
+}
+
+impl Default for LoadSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadSegment {
+    pub fn new() -> Self {
+        Self {
+            display: LoadDisplay::Normalized,
+            warning_threshold: 70.0,
+            critical_threshold: 90.0,
+        }
+    }
+
+    pub fn with_display(mut self, display: LoadDisplay) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Cutoffs in percent of total capacity, applied regardless of display mode
+    pub fn with_thresholds(mut self, warning_threshold: f64, critical_threshold: f64) -> Self {
+        self.warning_threshold = warning_threshold;
+        self.critical_threshold = critical_threshold;
+        self
+    }
+
+    fn core_count() -> usize {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    }
+
+    #[cfg(unix)]
+    fn load_average() -> Option<f64> {
+        extern "C" {
+            fn getloadavg(loadavg: *mut f64, nelem: i32) -> i32;
+        }
+
+        let mut loads = [0.0f64; 1];
+        // SAFETY: the buffer holds exactly the one sample requested
+        let filled = unsafe { getloadavg(loads.as_mut_ptr(), 1) };
+        (filled == 1).then_some(loads[0])
+    }
+
+    /// Windows has no load average; derive one from CPU busy time since the previous
+    /// invocation, kept in ~/.claude/ccline/.cpu_sample.json so nothing has to sleep
+    #[cfg(windows)]
+    fn load_average() -> Option<f64> {
+        #[repr(C)]
+        #[derive(Default)]
+        struct FileTime {
+            low: u32,
+            high: u32,
+        }
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetSystemTimes(
+                idle: *mut FileTime,
+                kernel: *mut FileTime,
+                user: *mut FileTime,
+            ) -> i32;
+        }
+
+        let (mut idle, mut kernel, mut user) = Default::default();
+        // SAFETY: all three pointers reference valid FILETIME structs
+        if unsafe { GetSystemTimes(&mut idle, &mut kernel, &mut user) } == 0 {
+            return None;
+        }
+
+        let ticks = |ft: &FileTime| (u64::from(ft.high) << 32) | u64::from(ft.low);
+        // Kernel time includes idle time
+        let sample = [ticks(&idle), ticks(&kernel) + ticks(&user)];
+
+        let path = dirs::home_dir()?
+            .join(".claude")
+            .join("ccline")
+            .join(".cpu_sample.json");
+        let previous: Option<[u64; 2]> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        if let Ok(json) = serde_json::to_string(&sample) {
+            let _ = std::fs::write(&path, json);
+        }
+
+        let [prev_idle, prev_total] = previous?;
+        let total = sample[1].checked_sub(prev_total).filter(|t| *t > 0)?;
+        let idle = sample[0].checked_sub(prev_idle)?.min(total);
+        let busy = (total - idle) as f64 / total as f64;
+
+        Some(busy * Self::core_count() as f64)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn load_average() -> Option<f64> {
+        None
+    }
+}
+
+impl Segment for LoadSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let load = Self::load_average().filter(|load| load.is_finite() && *load >= 0.0)?;
+        let cores = Self::core_count();
+        let percent = load / cores as f64 * 100.0;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("load".to_string(), format!("{:.2}", load));
+        metadata.insert("cores".to_string(), cores.to_string());
+        metadata.insert("percent".to_string(), format!("{:.0}", percent));
+        if percent >= self.critical_threshold {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if percent >= self.warning_threshold {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        let primary = match self.display {
+            LoadDisplay::Raw => format!("{:.2}", load),
+            LoadDisplay::Normalized => format!("{:.0}%", percent),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Load
+    }
+}
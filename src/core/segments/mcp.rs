@@ -0,0 +1,116 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Any file with an `mcpServers` table: `.mcp.json` or one project in `~/.claude.json`
+#[derive(Default, Deserialize)]
+struct McpServers {
+    #[serde(rename = "mcpServers", default)]
+    mcp_servers: HashMap<String, serde_json::Value>,
+}
+
+/// The parts of `~/.claude.json` holding MCP servers; everything else is skipped
+#[derive(Default, Deserialize)]
+struct ClaudeJson {
+    #[serde(rename = "mcpServers", default)]
+    mcp_servers: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    projects: HashMap<String, McpServers>,
+}
+
+pub struct McpSegment {
+    project_only: bool,
+}
+
+impl Default for McpSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McpSegment {
+    pub fn new() -> Self {
+        Self {
+            project_only: false,
+        }
+    }
+
+    /// Count only servers from the project's `.mcp.json`
+    pub fn with_project_only(mut self, project_only: bool) -> Self {
+        self.project_only = project_only;
+        self
+    }
+
+    fn read_json<T: for<'de> Deserialize<'de> + Default>(path: &Path) -> T {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Servers from the nearest `.mcp.json` at or above the workspace
+    fn project_servers(dir: &Path) -> Vec<String> {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(".mcp.json"))
+            .find(|path| path.is_file())
+            .map(|path| {
+                Self::read_json::<McpServers>(&path)
+                    .mcp_servers
+                    .into_keys()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// User-scope servers plus local-scope servers registered for the workspace
+    /// (or the nearest parent Claude Code knows as a project)
+    fn global_servers(dir: &Path) -> Vec<String> {
+        let Some(path) = dirs::home_dir().map(|home| home.join(".claude.json")) else {
+            return Vec::new();
+        };
+        let mut claude_json = Self::read_json::<ClaudeJson>(&path);
+
+        let mut servers: Vec<String> = claude_json.mcp_servers.into_keys().collect();
+        if let Some(project) = dir
+            .ancestors()
+            .find_map(|ancestor| claude_json.projects.remove(&*ancestor.to_string_lossy()))
+        {
+            servers.extend(project.mcp_servers.into_keys());
+        }
+        servers
+    }
+}
+
+impl Segment for McpSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let dir = Path::new(&input.workspace.current_dir);
+
+        let mut servers: BTreeSet<String> = Self::project_servers(dir).into_iter().collect();
+        if !self.project_only {
+            servers.extend(Self::global_servers(dir));
+        }
+
+        if servers.is_empty() {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "servers".to_string(),
+            servers.iter().cloned().collect::<Vec<_>>().join(", "),
+        );
+
+        Some(SegmentData {
+            primary: servers.len().to_string(),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Mcp
+    }
+}
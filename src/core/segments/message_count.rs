@@ -0,0 +1,132 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+/// Cached counts, pruned after this many days without an update
+const CACHE_RETENTION_DAYS: i64 = 7;
+
+/// Message counts for one session and how far into the transcript they reach
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MessageCountEntry {
+    transcript_path: String,
+    offset: u64,
+    user: u64,
+    assistant: u64,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Only the entry type is needed, so other fields can't make a line fail to parse
+#[derive(Deserialize)]
+struct EntryType {
+    r#type: Option<String>,
+}
+
+#[derive(Default)]
+pub struct MessageCountSegment;
+
+impl MessageCountSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Count messages appended since `entry.offset`. A trailing line without a newline
+    /// may still be being written, so it is left for the next render.
+    fn count_new_lines(path: &Path, entry: &mut MessageCountEntry) -> Option<()> {
+        let mut file = fs::File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+
+        // The transcript was replaced or truncated; start over
+        if len < entry.offset {
+            *entry = MessageCountEntry {
+                transcript_path: entry.transcript_path.clone(),
+                ..Default::default()
+            };
+        }
+        if len == entry.offset {
+            return Some(());
+        }
+
+        file.seek(SeekFrom::Start(entry.offset)).ok()?;
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line).ok()?;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            entry.offset += read as u64;
+
+            // Malformed lines are skipped rather than aborting the count
+            if let Ok(parsed) = serde_json::from_slice::<EntryType>(&line) {
+                match parsed.r#type.as_deref() {
+                    Some("user") => entry.user += 1,
+                    Some("assistant") => entry.assistant += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Some(())
+    }
+
+    fn resolve_counts(input: &InputData) -> Option<MessageCountEntry> {
+        let cache_key = input
+            .session_id
+            .clone()
+            .unwrap_or_else(|| input.transcript_path.clone());
+
+        let file = StateFile::new(".message_count_cache.json")?;
+        let cache: HashMap<String, MessageCountEntry> = file.load();
+        let cached = cache
+            .get(&cache_key)
+            .filter(|entry| entry.transcript_path == input.transcript_path);
+        let mut entry = cached.cloned().unwrap_or_else(|| MessageCountEntry {
+            transcript_path: input.transcript_path.clone(),
+            ..Default::default()
+        });
+
+        let previous_offset = entry.offset;
+        Self::count_new_lines(Path::new(&input.transcript_path), &mut entry)?;
+
+        if entry.offset != previous_offset || cached.is_none() {
+            let now = Utc::now();
+            entry.updated_at = Some(now);
+            let cutoff = now - Duration::days(CACHE_RETENTION_DAYS);
+            file.update(|cache: &mut HashMap<String, MessageCountEntry>| {
+                cache.retain(|_, cached| cached.updated_at.is_some_and(|at| at > cutoff));
+                cache.insert(cache_key, entry.clone());
+            });
+        }
+
+        Some(entry)
+    }
+}
+
+impl Segment for MessageCountSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let counts = Self::resolve_counts(input)?;
+        let total = counts.user + counts.assistant;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("user".to_string(), counts.user.to_string());
+        metadata.insert("assistant".to_string(), counts.assistant.to_string());
+
+        Some(SegmentData {
+            primary: total.to_string(),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::MessageCount
+    }
+}
@@ -1,23 +1,84 @@
+pub mod api_duration;
+pub mod aws;
+pub mod battery;
+pub mod budget;
+pub mod burn_rate;
+pub mod ci_status;
+pub mod clock;
+pub mod command;
+pub mod conda;
+pub mod connectivity;
 pub mod context_window;
 pub mod cost;
+pub mod cost_ledger;
+pub mod custom;
+pub mod daily_cost;
 pub mod directory;
+pub mod direnv;
+pub mod disk;
+pub mod docker;
+pub mod dotnet;
+pub mod env;
+pub mod environment;
+pub mod gcloud;
 pub mod git;
+pub mod git_diff;
+pub mod git_identity;
+pub mod github_pr;
+pub mod go;
+pub mod hg;
+pub mod hostname;
+pub mod java;
+pub mod jujutsu;
+pub mod kubernetes;
+pub mod last_commit;
+pub mod last_prompt;
+pub mod lines_changed;
+pub mod load;
+pub mod mcp;
+pub mod message_count;
 pub mod model;
+pub mod nix;
+pub mod node;
 pub mod output_style;
+pub mod package;
+pub mod python_env;
+pub mod runtime;
+pub mod rust;
 pub mod session;
+pub mod session_duration;
+pub mod session_id;
+pub mod shell;
+pub mod ssh;
+pub mod terraform;
+pub mod todo;
 pub mod update;
 pub mod usage;
+pub mod user;
+pub mod version;
 
 use crate::config::{InputData, SegmentId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 // New Segment trait for data collection only
 pub trait Segment {
     fn collect(&self, input: &InputData) -> Option<SegmentData>;
     fn id(&self) -> SegmentId;
+
+    /// Identifies the inputs `collect` depends on. Returning `Some` lets the result be
+    /// reused across invocations for `cache_ttl`; `None` collects every time.
+    fn cache_key(&self, _input: &InputData) -> Option<String> {
+        None
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(5)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SegmentData {
     pub primary: String,
     pub secondary: String,
@@ -25,12 +86,60 @@ pub struct SegmentData {
 }
 
 // Re-export all segment types
+pub use api_duration::ApiDurationSegment;
+pub use aws::AwsSegment;
+pub use battery::BatterySegment;
+pub use budget::BudgetSegment;
+pub use burn_rate::BurnRateSegment;
+pub use ci_status::CiStatusSegment;
+pub use clock::ClockSegment;
+pub use command::CustomCommandSegment;
+pub use conda::CondaSegment;
+pub use connectivity::ConnectivitySegment;
 pub use context_window::ContextWindowSegment;
 pub use cost::CostSegment;
-pub use directory::DirectorySegment;
+pub use custom::CustomTextSegment;
+pub use daily_cost::DailyCostSegment;
+pub use directory::{DirectoryDisplay, DirectorySegment};
+pub use direnv::DirenvSegment;
+pub use disk::{DiskSegment, DiskThreshold};
+pub use docker::DockerSegment;
+pub use dotnet::DotnetSegment;
+pub use env::EnvSegment;
+pub use environment::EnvironmentSegment;
+pub use gcloud::GcloudSegment;
 pub use git::GitSegment;
+pub use git_diff::GitDiffSegment;
+pub use git_identity::{GitIdentitySegment, IdentityStyle};
+pub use github_pr::GithubPrSegment;
+pub use go::GoSegment;
+pub use hg::HgSegment;
+pub use hostname::HostnameSegment;
+pub use java::JavaSegment;
+pub use jujutsu::JujutsuSegment;
+pub use kubernetes::KubernetesSegment;
+pub use last_commit::LastCommitSegment;
+pub use last_prompt::LastPromptSegment;
+pub use lines_changed::LinesChangedSegment;
+pub use load::{LoadDisplay, LoadSegment};
+pub use mcp::McpSegment;
+pub use message_count::MessageCountSegment;
 pub use model::ModelSegment;
+pub use nix::NixSegment;
+pub use node::NodeSegment;
 pub use output_style::OutputStyleSegment;
+pub use package::PackageSegment;
+pub use python_env::PythonEnvSegment;
+pub use runtime::RuntimeSegment;
+pub use rust::RustSegment;
 pub use session::SessionSegment;
+pub use session_duration::SessionDurationSegment;
+pub use session_id::SessionIdSegment;
+pub use shell::ShellSegment;
+pub use ssh::SshSegment;
+pub use terraform::TerraformSegment;
+pub use todo::TodoSegment;
 pub use update::UpdateSegment;
 pub use usage::UsageSegment;
+pub use user::UserSegment;
+pub use version::VersionSegment;
@@ -3,16 +3,46 @@ use crate::config::{InputData, ModelConfig, SegmentId};
 use std::collections::HashMap;
 
 #[derive(Default)]
-pub struct ModelSegment;
+pub struct ModelSegment {
+    aliases: HashMap<String, String>,
+}
 
 impl ModelSegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Short names keyed by model id pattern, taking priority over models.toml
+    pub fn with_aliases(mut self, aliases: HashMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Exact id match first, then the longest pattern contained in the id
+    fn alias_for(&self, id: &str) -> Option<String> {
+        let id_lower = id.to_lowercase();
+        if let Some(alias) = self
+            .aliases
+            .iter()
+            .find(|(pattern, _)| pattern.to_lowercase() == id_lower)
+        {
+            return Some(alias.1.clone());
+        }
+
+        self.aliases
+            .iter()
+            .filter(|(pattern, _)| id_lower.contains(&pattern.to_lowercase()))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, name)| name.clone())
     }
 }
 
 impl Segment for ModelSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        if input.model.id.is_empty() && input.model.display_name.is_empty() {
+            return None;
+        }
+
         let mut metadata = HashMap::new();
         metadata.insert("model_id".to_string(), input.model.id.clone());
         metadata.insert("display_name".to_string(), input.model.display_name.clone());
@@ -31,14 +61,20 @@ impl Segment for ModelSegment {
 
 impl ModelSegment {
     fn format_model_name(&self, id: &str, display_name: &str) -> String {
+        if let Some(alias) = self.alias_for(id) {
+            return alias;
+        }
+
         let model_config = ModelConfig::load();
 
         // Try to get display name from external config first
         if let Some(config_name) = model_config.get_display_name(id) {
             config_name
-        } else {
+        } else if !display_name.is_empty() {
             // Fallback to Claude Code's official display_name for unrecognized models
             display_name.to_string()
+        } else {
+            id.to_string()
         }
     }
 }
@@ -0,0 +1,88 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Shows when the statusline runs inside a `nix-shell`, `nix develop` or a
+/// direnv-loaded flake dev shell
+pub struct NixSegment {
+    show_purity: bool,
+}
+
+impl Default for NixSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NixSegment {
+    pub fn new() -> Self {
+        Self { show_purity: true }
+    }
+
+    pub fn with_purity(mut self, show_purity: bool) -> Self {
+        self.show_purity = show_purity;
+        self
+    }
+
+    fn env_value(name: &str) -> Option<String> {
+        env::var(name).ok().filter(|value| !value.is_empty())
+    }
+
+    /// An `.envrc` loaded by direnv that uses `use flake` / `use nix`, or sits next to a flake
+    fn direnv_nix_shell() -> bool {
+        let Some(envrc) = Self::env_value("DIRENV_FILE") else {
+            return false;
+        };
+        let envrc = Path::new(&envrc);
+
+        let uses_nix = fs::read_to_string(envrc).is_ok_and(|content| {
+            content.lines().any(|line| {
+                let line = line.trim();
+                line.starts_with("use flake") || line.starts_with("use nix")
+            })
+        });
+
+        uses_nix
+            || envrc
+                .parent()
+                .is_some_and(|dir| dir.join("flake.nix").is_file())
+    }
+}
+
+impl Segment for NixSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        // nix-shell sets IN_NIX_SHELL to "pure" or "impure"; `nix develop` always "impure"
+        let (purity, source) = match Self::env_value("IN_NIX_SHELL") {
+            Some(purity) => (purity, "nix-shell"),
+            None if Self::direnv_nix_shell() => ("impure".to_string(), "direnv"),
+            None => return None,
+        };
+
+        // stdenv exports the derivation's `name` into the shell
+        let derivation = Self::env_value("name");
+
+        let mut metadata = HashMap::new();
+        metadata.insert("purity".to_string(), purity.clone());
+        metadata.insert("source".to_string(), source.to_string());
+        if let Some(ref derivation) = derivation {
+            metadata.insert("derivation".to_string(), derivation.clone());
+        }
+
+        Some(SegmentData {
+            primary: derivation.unwrap_or_else(|| "nix".to_string()),
+            secondary: if self.show_purity {
+                purity
+            } else {
+                String::new()
+            },
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Nix
+    }
+}
@@ -0,0 +1,104 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// `node --version` results keyed by directory, kept for the life of the process
+static NODE_VERSION_CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+const NODE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+pub struct NodeSegment {
+    run_node: bool,
+}
+
+impl NodeSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fall back to `node --version` when the project doesn't pin a version
+    pub fn with_run_node(mut self, run_node: bool) -> Self {
+        self.run_node = run_node;
+        self
+    }
+
+    /// Version pinned by the project, returned with the file it came from
+    fn project_version(dir: &Path) -> Option<(String, &'static str)> {
+        for file in [".nvmrc", ".node-version"] {
+            if let Ok(content) = fs::read_to_string(dir.join(file)) {
+                let version = content.lines().next().unwrap_or("").trim();
+                if !version.is_empty() {
+                    return Some((version.trim_start_matches('v').to_string(), file));
+                }
+            }
+        }
+
+        let package: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("package.json")).ok()?).ok()?;
+        let engine = package.get("engines")?.get("node")?.as_str()?.trim();
+        if engine.is_empty() {
+            None
+        } else {
+            Some((engine.to_string(), "package.json"))
+        }
+    }
+
+    fn installed_version(dir: &str) -> Option<String> {
+        let cache = NODE_VERSION_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        if let Some(version) = cache.lock().ok()?.get(dir) {
+            return version.clone();
+        }
+
+        let version = Command::new("node")
+            .arg("--version")
+            .current_dir(dir)
+            .output_with_timeout(NODE_TIMEOUT)
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|s| s.trim().trim_start_matches('v').to_string())
+            .filter(|s| !s.is_empty());
+
+        if let Ok(mut cache) = cache.lock() {
+            cache.insert(dir.to_string(), version.clone());
+        }
+        version
+    }
+}
+
+impl Segment for NodeSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let working_dir = &input.workspace.current_dir;
+        let dir = Path::new(working_dir);
+        if !dir.join("package.json").is_file() {
+            return None;
+        }
+
+        let (version, source) = match Self::project_version(dir) {
+            Some(pinned) => pinned,
+            None if self.run_node => (Self::installed_version(working_dir)?, "node"),
+            None => return None,
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("version".to_string(), version.clone());
+        metadata.insert("source".to_string(), source.to_string());
+
+        Some(SegmentData {
+            primary: version,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Node
+    }
+}
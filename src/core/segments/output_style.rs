@@ -2,24 +2,57 @@ use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
 use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct OutputStyleSegment;
+pub struct OutputStyleSegment {
+    always_show: bool,
+}
+
+impl Default for OutputStyleSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl OutputStyleSegment {
     pub fn new() -> Self {
-        Self
+        Self { always_show: false }
+    }
+
+    /// Show the segment even while the "default" style is active
+    pub fn with_always_show(mut self, always_show: bool) -> Self {
+        self.always_show = always_show;
+        self
+    }
+
+    /// Style name as an option key prefix, e.g. "Learning Mode" -> "learning_mode"
+    fn style_key(name: &str) -> String {
+        name.trim()
+            .chars()
+            .map(|ch| {
+                if ch.is_alphanumeric() {
+                    ch.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect()
     }
 }
 
 impl Segment for OutputStyleSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
         let output_style = input.output_style.as_ref()?;
+        let style_key = Self::style_key(&output_style.name);
+        if style_key.is_empty() || (!self.always_show && style_key == "default") {
+            return None;
+        }
 
         // Primary display: style name
         let primary = output_style.name.clone();
 
         let mut metadata = HashMap::new();
         metadata.insert("style_name".to_string(), output_style.name.clone());
+        // Lets themes color each style via a `<style>_color` option, e.g. `learning_color`
+        metadata.insert("level".to_string(), style_key);
 
         Some(SegmentData {
             primary,
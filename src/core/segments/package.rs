@@ -0,0 +1,170 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Manifests searched when the `manifests` option is not set, in priority order
+pub const DEFAULT_MANIFESTS: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// Name and version declared by a manifest
+struct PackageInfo {
+    name: Option<String>,
+    version: String,
+}
+
+/// The version of the package being edited, from the nearest manifest at or above
+/// the working directory. The search stops at the repository root.
+pub struct PackageSegment {
+    manifests: Vec<String>,
+}
+
+impl Default for PackageSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PackageSegment {
+    pub fn new() -> Self {
+        Self {
+            manifests: DEFAULT_MANIFESTS.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    /// Manifest file names to look for, in priority order within a directory
+    pub fn with_manifests(mut self, manifests: Vec<String>) -> Self {
+        self.manifests = manifests;
+        self
+    }
+
+    fn read_toml(path: &Path) -> Option<toml::Value> {
+        toml::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    fn toml_str(value: &toml::Value, path: &[&str]) -> Option<String> {
+        path.iter()
+            .try_fold(value, |value, key| value.get(key))?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// `[package] version`, inheriting `version.workspace = true` from the workspace root.
+    /// A virtual workspace manifest falls back to `[workspace.package] version`.
+    fn cargo_package(dir: &Path) -> Option<PackageInfo> {
+        let manifest = Self::read_toml(&dir.join("Cargo.toml"))?;
+
+        let Some(package) = manifest.get("package") else {
+            return Self::toml_str(&manifest, &["workspace", "package", "version"]).map(
+                |version| PackageInfo {
+                    name: None,
+                    version,
+                },
+            );
+        };
+
+        let name = Self::toml_str(package, &["name"]);
+        let version = match package.get("version")? {
+            toml::Value::String(version) => version.clone(),
+            inherited if inherited.get("workspace").and_then(|v| v.as_bool()) == Some(true) => {
+                Self::workspace_version(dir)?
+            }
+            _ => return None,
+        };
+
+        Some(PackageInfo { name, version })
+    }
+
+    /// Version shared through `[workspace.package]` by the nearest enclosing workspace
+    fn workspace_version(member_dir: &Path) -> Option<String> {
+        member_dir.ancestors().skip(1).find_map(|dir| {
+            let manifest = Self::read_toml(&dir.join("Cargo.toml"))?;
+            manifest.get("workspace")?;
+            Self::toml_str(&manifest, &["workspace", "package", "version"])
+        })
+    }
+
+    fn npm_package(dir: &Path) -> Option<PackageInfo> {
+        let content = fs::read_to_string(dir.join("package.json")).ok()?;
+        let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let version = manifest.get("version")?.as_str()?.to_string();
+        let name = manifest
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        Some(PackageInfo { name, version })
+    }
+
+    /// PEP 621 `[project]`, else Poetry's `[tool.poetry]`; dynamic versions are skipped
+    fn python_package(dir: &Path) -> Option<PackageInfo> {
+        let manifest = Self::read_toml(&dir.join("pyproject.toml"))?;
+
+        let project = manifest.get("project");
+        let poetry = manifest.get("tool").and_then(|tool| tool.get("poetry"));
+
+        for table in [project, poetry].into_iter().flatten() {
+            if let Some(version) = Self::toml_str(table, &["version"]) {
+                let name = Self::toml_str(table, &["name"]);
+                return Some(PackageInfo { name, version });
+            }
+        }
+
+        None
+    }
+
+    fn read_manifest(dir: &Path, manifest: &str) -> Option<PackageInfo> {
+        match manifest {
+            "Cargo.toml" => Self::cargo_package(dir),
+            "package.json" => Self::npm_package(dir),
+            "pyproject.toml" => Self::python_package(dir),
+            _ => None,
+        }
+    }
+}
+
+impl Segment for PackageSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        for dir in Path::new(&input.workspace.current_dir).ancestors() {
+            let found = self.manifests.iter().find_map(|manifest| {
+                Self::read_manifest(dir, manifest).map(|package| (manifest, package))
+            });
+
+            if let Some((manifest, package)) = found {
+                let mut metadata = HashMap::new();
+                metadata.insert("manifest".to_string(), manifest.clone());
+                metadata.insert(
+                    "path".to_string(),
+                    dir.join(manifest).to_string_lossy().to_string(),
+                );
+                metadata.insert("version".to_string(), package.version.clone());
+                if let Some(name) = package.name {
+                    metadata.insert("name".to_string(), name);
+                }
+
+                let version = package.version;
+                let primary = if version.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("v{}", version)
+                } else {
+                    version
+                };
+
+                return Some(SegmentData {
+                    primary,
+                    secondary: String::new(),
+                    metadata,
+                });
+            }
+
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        None
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Package
+    }
+}
@@ -0,0 +1,135 @@
+use super::{CondaSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// In-project virtualenv directory names (poetry, uv, `python -m venv`)
+const PROJECT_VENV_DIRS: [&str; 2] = [".venv", "venv"];
+
+#[derive(Debug)]
+pub struct PythonEnvInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: Option<String>,
+    pub source: &'static str,
+}
+
+pub struct PythonEnvSegment {
+    show_version: bool,
+}
+
+impl Default for PythonEnvSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PythonEnvSegment {
+    pub fn new() -> Self {
+        Self { show_version: true }
+    }
+
+    pub fn with_version(mut self, show_version: bool) -> Self {
+        self.show_version = show_version;
+        self
+    }
+
+    /// Find the active virtualenv, falling back to one inside the workspace
+    pub fn detect(working_dir: &str) -> Option<PythonEnvInfo> {
+        if let Some(venv) = env::var_os("VIRTUAL_ENV").filter(|v| !v.is_empty()) {
+            let venv = PathBuf::from(venv);
+            // A venv-backed conda env is already shown by the conda segment
+            if CondaSegment::active_prefix().as_ref() == Some(&venv) {
+                return None;
+            }
+            return Some(Self::read_env(venv, "virtualenv"));
+        }
+
+        PROJECT_VENV_DIRS
+            .iter()
+            .map(|dir| Path::new(working_dir).join(dir))
+            .find(|path| path.join("pyvenv.cfg").is_file())
+            .map(|path| Self::read_env(path, "project"))
+    }
+
+    fn read_env(path: PathBuf, source: &'static str) -> PythonEnvInfo {
+        let cfg = Self::read_pyvenv_cfg(&path);
+
+        // Prefer the prompt the venv was created with, "(.venv)" tells nobody anything
+        let name = cfg
+            .get("prompt")
+            .map(|prompt| prompt.trim_matches(|c| c == '\'' || c == '"').to_string())
+            .filter(|prompt| !prompt.is_empty())
+            .unwrap_or_else(|| Self::dir_name(&path));
+
+        let version = cfg
+            .get("version")
+            .or_else(|| cfg.get("version_info"))
+            .cloned();
+
+        PythonEnvInfo {
+            name,
+            path,
+            version,
+            source,
+        }
+    }
+
+    /// Parse the `key = value` lines of a venv's pyvenv.cfg
+    fn read_pyvenv_cfg(path: &Path) -> HashMap<String, String> {
+        fs::read_to_string(path.join("pyvenv.cfg"))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                Some((key.trim().to_lowercase(), value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Name a venv after its directory, or its project when it's a generic ".venv"
+    fn dir_name(path: &Path) -> String {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if PROJECT_VENV_DIRS.contains(&name.as_str()) {
+            if let Some(project) = path.parent().and_then(|p| p.file_name()) {
+                return project.to_string_lossy().to_string();
+            }
+        }
+
+        name
+    }
+}
+
+impl Segment for PythonEnvSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let info = Self::detect(&input.workspace.current_dir)?;
+
+        let secondary = match (&info.version, self.show_version) {
+            (Some(version), true) => version.clone(),
+            _ => String::new(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("env_path".to_string(), info.path.display().to_string());
+        metadata.insert("source".to_string(), info.source.to_string());
+        if let Some(version) = &info.version {
+            metadata.insert("python_version".to_string(), version.clone());
+        }
+
+        Some(SegmentData {
+            primary: info.name,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::PythonEnv
+    }
+}
@@ -0,0 +1,155 @@
+use super::{
+    DotnetSegment, GoSegment, JavaSegment, NodeSegment, PythonEnvSegment, RustSegment, Segment,
+    SegmentData,
+};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Probe order when the `runtimes` option is not set
+pub const DEFAULT_RUNTIMES: [&str; 6] = ["node", "python", "rust", "go", "java", "dotnet"];
+
+/// Files that mark a Python project
+const PYTHON_MARKERS: [&str; 5] = [
+    "pyproject.toml",
+    "requirements.txt",
+    "setup.py",
+    "Pipfile",
+    ".python-version",
+];
+
+const PYTHON_TIMEOUT: Duration = Duration::from_millis(500);
+
+const RUNTIME_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// The version of the first runtime whose project files are found in the workspace.
+/// Delegates to the per-language segments, so pinned versions and caches are shared.
+pub struct RuntimeSegment {
+    runtimes: Vec<String>,
+}
+
+impl Default for RuntimeSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuntimeSegment {
+    pub fn new() -> Self {
+        Self {
+            runtimes: DEFAULT_RUNTIMES.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    /// Runtimes to probe, in priority order
+    pub fn with_runtimes(mut self, runtimes: Vec<String>) -> Self {
+        self.runtimes = runtimes;
+        self
+    }
+
+    /// Nerd Font logo shown in place of the configured icon
+    fn runtime_icon(runtime: &str) -> Option<&'static str> {
+        match runtime {
+            "node" => Some("\u{e718}"),
+            "python" => Some("\u{e73c}"),
+            "rust" => Some("\u{e7a8}"),
+            "go" => Some("\u{e627}"),
+            "java" => Some("\u{e738}"),
+            "dotnet" => Some("\u{e77f}"),
+            _ => None,
+        }
+    }
+
+    /// `.python-version`, then the workspace virtualenv, then `python3 --version`
+    fn python_version(working_dir: &str) -> Option<SegmentData> {
+        let dir = Path::new(working_dir);
+        if !PYTHON_MARKERS
+            .iter()
+            .any(|marker| dir.join(marker).is_file())
+        {
+            return None;
+        }
+
+        let pinned = fs::read_to_string(dir.join(".python-version"))
+            .ok()
+            .and_then(|content| content.lines().next().map(|line| line.trim().to_string()))
+            .filter(|version| !version.is_empty())
+            .map(|version| (version, ".python-version"));
+        let (version, source) = pinned
+            .or_else(|| {
+                PythonEnvSegment::detect(working_dir)
+                    .and_then(|env| env.version)
+                    .map(|version| (version, "virtualenv"))
+            })
+            .or_else(|| {
+                Command::new("python3")
+                    .arg("--version")
+                    .current_dir(dir)
+                    .output_with_timeout(PYTHON_TIMEOUT)
+                    .ok()
+                    .filter(|output| output.status.success())
+                    .and_then(|output| String::from_utf8(output.stdout).ok())
+                    .and_then(|out| out.trim().strip_prefix("Python ").map(str::to_string))
+                    .map(|version| (version, "python3"))
+            })?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("version".to_string(), version.clone());
+        metadata.insert("source".to_string(), source.to_string());
+
+        Some(SegmentData {
+            primary: version,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn probe(runtime: &str, input: &InputData) -> Option<SegmentData> {
+        match runtime {
+            "node" => NodeSegment::new().with_run_node(true).collect(input),
+            "python" => Self::python_version(&input.workspace.current_dir),
+            "rust" => RustSegment::new().collect(input),
+            "go" => GoSegment::new().collect(input),
+            "java" => JavaSegment::new().collect(input),
+            "dotnet" => DotnetSegment::new().collect(input),
+            _ => None,
+        }
+    }
+}
+
+impl Segment for RuntimeSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        self.runtimes.iter().find_map(|runtime| {
+            let mut data = Self::probe(runtime, input)?;
+            data.metadata
+                .insert("runtime".to_string(), runtime.to_string());
+            if let Some(icon) = Self::runtime_icon(runtime) {
+                data.metadata
+                    .insert("dynamic_icon".to_string(), icon.to_string());
+            }
+            Some(data)
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Runtime
+    }
+
+    /// Versions only move when a project or toolchain changes, so probing
+    /// (which may spawn `node` or `python3`) is skipped for a while
+    fn cache_key(&self, input: &InputData) -> Option<String> {
+        Some(format!(
+            "{}\0{}",
+            input.workspace.current_dir,
+            self.runtimes.join(",")
+        ))
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        RUNTIME_CACHE_TTL
+    }
+}
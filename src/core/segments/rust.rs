@@ -0,0 +1,217 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::OutputWithTimeout;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// rustup proxies can be slow to resolve the toolchain on first use
+const RUSTC_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RustToolchain {
+    /// Version like "1.79.0", None for unpinned channels such as "nightly"
+    pub version: Option<String>,
+    pub channel: String,
+}
+
+#[derive(Default)]
+pub struct RustSegment {
+    always_run_rustc: bool,
+}
+
+impl RustSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask rustc even when a toolchain file pins the channel
+    pub fn with_always_run_rustc(mut self, always_run_rustc: bool) -> Self {
+        self.always_run_rustc = always_run_rustc;
+        self
+    }
+
+    /// Read `rust-toolchain.toml`, falling back to the legacy plain-text `rust-toolchain`
+    fn toolchain_file(dir: &Path) -> Option<RustToolchain> {
+        if let Ok(content) = fs::read_to_string(dir.join("rust-toolchain.toml")) {
+            let value: toml::Value = toml::from_str(&content).ok()?;
+            let channel = value.get("toolchain")?.get("channel")?.as_str()?;
+            return Some(Self::parse_channel(channel));
+        }
+
+        let content = fs::read_to_string(dir.join("rust-toolchain")).ok()?;
+        // The legacy file may also hold TOML
+        if let Ok(value) = toml::from_str::<toml::Value>(&content) {
+            if let Some(channel) = value
+                .get("toolchain")
+                .and_then(|t| t.get("channel"))
+                .and_then(|c| c.as_str())
+            {
+                return Some(Self::parse_channel(channel));
+            }
+        }
+        let channel = content.lines().next()?.trim();
+        if channel.is_empty() {
+            None
+        } else {
+            Some(Self::parse_channel(channel))
+        }
+    }
+
+    /// Parse a toolchain channel such as "1.79.0", "stable" or "nightly-2024-05-01"
+    fn parse_channel(channel: &str) -> RustToolchain {
+        if channel.starts_with(|c: char| c.is_ascii_digit()) {
+            return RustToolchain {
+                version: Some(channel.to_string()),
+                channel: "stable".to_string(),
+            };
+        }
+
+        let name = channel.split('-').next().unwrap_or(channel);
+        RustToolchain {
+            version: None,
+            channel: name.to_string(),
+        }
+    }
+
+    /// Parse `rustc --version`, e.g. "rustc 1.81.0-nightly (abc 2024-06-10)"
+    fn rustc_toolchain(dir: &str) -> Option<RustToolchain> {
+        let output = Command::new("rustc")
+            .arg("--version")
+            .current_dir(dir)
+            .output_with_timeout(RUSTC_TIMEOUT)
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8(output.stdout).ok()?;
+        let full = text.split_whitespace().nth(1)?;
+        let (version, channel) = match full.split_once('-') {
+            Some((version, pre)) => (version, pre.split('.').next().unwrap_or(pre)),
+            None => (full, "stable"),
+        };
+
+        Some(RustToolchain {
+            version: Some(version.to_string()),
+            channel: channel.to_string(),
+        })
+    }
+}
+
+impl Segment for RustSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let working_dir = &input.workspace.current_dir;
+        let dir = Path::new(working_dir);
+        if !dir.join("Cargo.toml").is_file() {
+            return None;
+        }
+
+        let (toolchain, source) = match Self::toolchain_file(dir) {
+            Some(toolchain) if !self.always_run_rustc => (toolchain, "toolchain_file"),
+            _ => (Self::rustc_toolchain(working_dir)?, "rustc"),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("channel".to_string(), toolchain.channel.clone());
+        metadata.insert("source".to_string(), source.to_string());
+        if toolchain.channel != "stable" {
+            // Themes can color pre-release channels via `nightly_color` / `beta_color`
+            metadata.insert("level".to_string(), toolchain.channel.clone());
+        }
+
+        let (primary, secondary) = match toolchain.version {
+            Some(version) => {
+                metadata.insert("version".to_string(), version.clone());
+                let secondary = if toolchain.channel == "stable" {
+                    String::new()
+                } else {
+                    toolchain.channel
+                };
+                (version, secondary)
+            }
+            None => (toolchain.channel, String::new()),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Rust
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Fresh directory holding a single toolchain file
+    fn dir_with(name: &str, file: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ccline-rust-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(file), content).unwrap();
+        dir
+    }
+
+    #[test]
+    fn toml_toolchain_file() {
+        let dir = dir_with(
+            "toml",
+            "rust-toolchain.toml",
+            "[toolchain]\nchannel = \"1.79.0\"\ncomponents = [\"clippy\"]\n",
+        );
+        assert_eq!(
+            RustSegment::toolchain_file(&dir),
+            Some(RustToolchain {
+                version: Some("1.79.0".to_string()),
+                channel: "stable".to_string(),
+            })
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_plain_toolchain_file() {
+        let dir = dir_with("legacy", "rust-toolchain", "nightly-2024-05-01\n");
+        assert_eq!(
+            RustSegment::toolchain_file(&dir),
+            Some(RustToolchain {
+                version: None,
+                channel: "nightly".to_string(),
+            })
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_file_holding_toml() {
+        let dir = dir_with(
+            "legacy-toml",
+            "rust-toolchain",
+            "[toolchain]\nchannel = \"beta\"\n",
+        );
+        assert_eq!(
+            RustSegment::toolchain_file(&dir),
+            Some(RustToolchain {
+                version: None,
+                channel: "beta".to_string(),
+            })
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn empty_legacy_file_is_ignored() {
+        let dir = dir_with("empty", "rust-toolchain", "\n");
+        assert_eq!(RustSegment::toolchain_file(&dir), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,114 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId, TranscriptEntry};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Cached session start times, pruned after this many days
+const CACHE_RETENTION_DAYS: i64 = 7;
+
+#[derive(Default)]
+pub struct SessionDurationSegment;
+
+impl SessionDurationSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find the first parseable timestamp in the transcript, reading line by line
+    fn first_transcript_timestamp(path: &Path) -> Option<DateTime<Utc>> {
+        let file = fs::File::open(path).ok()?;
+        let reader = BufReader::new(file);
+
+        for line in reader.lines().map_while(Result::ok) {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) {
+                if let Some(timestamp) = entry.timestamp {
+                    if let Ok(dt) = DateTime::parse_from_rfc3339(&timestamp) {
+                        return Some(dt.with_timezone(&Utc));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Fall back to the transcript file's creation (or modification) time
+    fn transcript_file_time(path: &Path) -> Option<DateTime<Utc>> {
+        let metadata = fs::metadata(path).ok()?;
+        let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+        Some(DateTime::<Utc>::from(time))
+    }
+
+    fn resolve_start_time(input: &InputData) -> Option<DateTime<Utc>> {
+        let cache_key = input
+            .session_id
+            .clone()
+            .unwrap_or_else(|| input.transcript_path.clone());
+
+        let file = StateFile::new(".session_start_cache.json")?;
+        let cache: HashMap<String, DateTime<Utc>> = file.load();
+        if let Some(start) = cache.get(&cache_key) {
+            return Some(*start);
+        }
+
+        let path = Path::new(&input.transcript_path);
+        let start =
+            Self::first_transcript_timestamp(path).or_else(|| Self::transcript_file_time(path))?;
+
+        let cutoff = Utc::now() - Duration::days(CACHE_RETENTION_DAYS);
+        // Another pane may have recorded the session meanwhile; its start time wins
+        let start = file.update(|cache: &mut HashMap<String, DateTime<Utc>>| {
+            cache.retain(|_, started_at| *started_at > cutoff);
+            *cache.entry(cache_key).or_insert(start)
+        });
+
+        Some(start)
+    }
+
+    fn format_elapsed(elapsed: Duration) -> String {
+        let total_minutes = elapsed.num_minutes().max(0);
+        let hours = total_minutes / 60;
+        let minutes = total_minutes % 60;
+
+        if hours > 0 {
+            format!("{}h {}m", hours, minutes)
+        } else if minutes > 0 {
+            format!("{}m", minutes)
+        } else {
+            format!("{}s", elapsed.num_seconds().max(0))
+        }
+    }
+}
+
+impl Segment for SessionDurationSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let start = Self::resolve_start_time(input)?;
+        let elapsed = Utc::now().signed_duration_since(start);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("started_at".to_string(), start.to_rfc3339());
+        metadata.insert(
+            "elapsed_seconds".to_string(),
+            elapsed.num_seconds().max(0).to_string(),
+        );
+
+        Some(SegmentData {
+            primary: Self::format_elapsed(elapsed),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::SessionDuration
+    }
+}
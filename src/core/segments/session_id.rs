@@ -0,0 +1,99 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+/// Shortened session id, handy when debugging hook scripts
+pub struct SessionIdSegment {
+    length: usize,
+    link: bool,
+}
+
+impl Default for SessionIdSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionIdSegment {
+    pub fn new() -> Self {
+        Self {
+            length: 8,
+            link: false,
+        }
+    }
+
+    /// Characters of the id to show, 0 for the whole id
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Wrap the id in an OSC 8 hyperlink to the transcript file
+    pub fn with_link(mut self, link: bool) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// `file://` URL for a local path, percent-encoding anything outside the unreserved set
+    fn file_url(path: &str) -> String {
+        let path = path.replace('\\', "/");
+        let mut url = String::from("file://");
+        // Windows paths like C:/... need the extra slash of an empty host
+        if !path.starts_with('/') {
+            url.push('/');
+        }
+        for byte in path.bytes() {
+            match byte {
+                b'A'..=b'Z'
+                | b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'-'
+                | b'_'
+                | b'.'
+                | b'~'
+                | b'/'
+                | b':' => url.push(byte as char),
+                _ => url.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        url
+    }
+}
+
+impl Segment for SessionIdSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let session_id = input.session_id.as_deref().filter(|id| !id.is_empty())?;
+
+        let short_id: String = if self.length == 0 {
+            session_id.to_string()
+        } else {
+            session_id.chars().take(self.length).collect()
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("session_id".to_string(), session_id.to_string());
+        metadata.insert("short_id".to_string(), short_id.clone());
+
+        // Terminals without OSC 8 support ignore the sequence and show the plain id
+        let primary = if self.link && !input.transcript_path.is_empty() {
+            metadata.insert("transcript_path".to_string(), input.transcript_path.clone());
+            format!(
+                "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\",
+                Self::file_url(&input.transcript_path),
+                short_id
+            )
+        } else {
+            short_id
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::SessionId
+    }
+}
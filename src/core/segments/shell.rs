@@ -0,0 +1,201 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+
+/// Process names recognised as interactive shells
+const SHELLS: [&str; 12] = [
+    "bash",
+    "zsh",
+    "fish",
+    "pwsh",
+    "powershell",
+    "nu",
+    "sh",
+    "dash",
+    "ksh",
+    "tcsh",
+    "xonsh",
+    "elvish",
+];
+
+/// Ancestors inspected before giving up
+const MAX_DEPTH: usize = 16;
+
+/// The shell Claude Code was started from, found by walking the parent processes
+pub struct ShellSegment {
+    show_version: bool,
+}
+
+impl Default for ShellSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellSegment {
+    pub fn new() -> Self {
+        Self {
+            show_version: false,
+        }
+    }
+
+    /// Show the version from ZSH_VERSION / BASH_VERSION / FISH_VERSION when exported
+    pub fn with_version(mut self, show_version: bool) -> Self {
+        self.show_version = show_version;
+        self
+    }
+
+    /// "-zsh" (login shell), "/bin/bash" and "pwsh.exe" all name a shell
+    fn shell_name(process: &str) -> Option<&'static str> {
+        let name = process.trim_start_matches('-');
+        let name = Path::new(name).file_stem()?.to_str()?;
+        SHELLS.iter().copied().find(|shell| *shell == name)
+    }
+
+    /// Names of the ancestor processes, nearest first
+    #[cfg(target_os = "linux")]
+    fn ancestor_names() -> Vec<String> {
+        let mut names = Vec::new();
+        let mut pid = std::os::unix::process::parent_id();
+
+        while pid > 1 && names.len() < MAX_DEPTH {
+            // "<pid> (<comm>) <state> <ppid> ..."; comm may itself contain ") "
+            let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+                break;
+            };
+            let (Some(open), Some(close)) = (stat.find('('), stat.rfind(')')) else {
+                break;
+            };
+            names.push(stat[open + 1..close].to_string());
+            pid = match stat[close + 1..].split_whitespace().nth(1) {
+                Some(ppid) => ppid.parse().unwrap_or(0),
+                None => break,
+            };
+        }
+
+        names
+    }
+
+    /// One `ps` call lists every process; the chain is followed from that table
+    #[cfg(all(unix, not(target_os = "linux")))]
+    fn ancestor_names() -> Vec<String> {
+        use crate::utils::OutputWithTimeout;
+        use std::process::Command;
+        use std::time::Duration;
+
+        let Ok(output) = Command::new("ps")
+            .args(["-A", "-o", "pid=,ppid=,comm="])
+            .output_with_timeout(Duration::from_millis(500))
+        else {
+            return Vec::new();
+        };
+        let listing = String::from_utf8_lossy(&output.stdout);
+        let processes: HashMap<u32, (u32, String)> = listing
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let pid = fields.next()?.parse().ok()?;
+                let ppid = fields.next()?.parse().ok()?;
+                let comm = fields.collect::<Vec<_>>().join(" ");
+                Some((pid, (ppid, comm)))
+            })
+            .collect();
+
+        let mut names = Vec::new();
+        let mut pid = std::os::unix::process::parent_id();
+        while pid > 1 && names.len() < MAX_DEPTH {
+            let Some((ppid, comm)) = processes.get(&pid) else {
+                break;
+            };
+            names.push(comm.clone());
+            pid = *ppid;
+        }
+
+        names
+    }
+
+    #[cfg(not(unix))]
+    fn ancestor_names() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Claude Code may run the statusline through `sh -c`, so shells directly above
+    /// this process are skipped in favour of the first shell above a non-shell
+    /// ancestor (Claude Code itself)
+    fn from_process_tree() -> Option<&'static str> {
+        let names = Self::ancestor_names();
+        let shells: Vec<Option<&'static str>> =
+            names.iter().map(|name| Self::shell_name(name)).collect();
+
+        let first_non_shell = shells.iter().position(Option::is_none);
+        first_non_shell
+            .and_then(|start| shells[start..].iter().flatten().next().copied())
+            .or_else(|| shells.iter().flatten().next().copied())
+    }
+
+    fn from_env() -> Option<&'static str> {
+        env::var("SHELL")
+            .ok()
+            .and_then(|shell| Self::shell_name(&shell))
+    }
+
+    fn version(shell: &str) -> Option<String> {
+        let variable = match shell {
+            "zsh" => "ZSH_VERSION",
+            "bash" => "BASH_VERSION",
+            "fish" => "FISH_VERSION",
+            _ => return None,
+        };
+        let version = env::var(variable).ok()?;
+        // BASH_VERSION looks like "5.2.15(1)-release"
+        let version = version.split('(').next().unwrap_or(&version).trim();
+        (!version.is_empty()).then(|| version.to_string())
+    }
+
+    fn shell_icon(shell: &str) -> &'static str {
+        match shell {
+            "bash" => "\u{ebca}",
+            "pwsh" | "powershell" => "\u{ebc7}",
+            "fish" => "\u{f023a}",
+            _ => "\u{f120}",
+        }
+    }
+}
+
+impl Segment for ShellSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let (shell, source) = match Self::from_process_tree() {
+            Some(shell) => (shell, "process"),
+            None => (Self::from_env()?, "SHELL"),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("shell".to_string(), shell.to_string());
+        metadata.insert("source".to_string(), source.to_string());
+        metadata.insert(
+            "dynamic_icon".to_string(),
+            Self::shell_icon(shell).to_string(),
+        );
+
+        let version = if self.show_version {
+            Self::version(shell)
+        } else {
+            None
+        };
+        if let Some(ref version) = version {
+            metadata.insert("version".to_string(), version.clone());
+        }
+
+        Some(SegmentData {
+            primary: shell.to_string(),
+            secondary: version.unwrap_or_default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Shell
+    }
+}
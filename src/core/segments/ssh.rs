@@ -0,0 +1,131 @@
+use super::{HostnameSegment, Segment, SegmentData};
+use crate::config::{InputData, SegmentConfig, SegmentId};
+use std::collections::HashMap;
+use std::env;
+
+/// Marks sessions running over SSH, optionally as `user@host`
+pub struct SshSegment {
+    show_user_host: bool,
+}
+
+impl Default for SshSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SshSegment {
+    pub fn new() -> Self {
+        Self {
+            show_user_host: true,
+        }
+    }
+
+    pub fn with_user_host(mut self, show_user_host: bool) -> Self {
+        self.show_user_host = show_user_host;
+        self
+    }
+
+    fn env_value(name: &str) -> Option<String> {
+        env::var(name).ok().filter(|value| !value.is_empty())
+    }
+
+    /// SSH_CONNECTION is "<client ip> <client port> <server ip> <server port>";
+    /// SSH_CLIENT has only the client half
+    fn connection_info() -> HashMap<String, String> {
+        let mut info = HashMap::new();
+        if let Some(connection) = Self::env_value("SSH_CONNECTION") {
+            let keys = ["client_ip", "client_port", "server_ip", "server_port"];
+            for (key, value) in keys.iter().zip(connection.split_whitespace()) {
+                info.insert(key.to_string(), value.to_string());
+            }
+        } else if let Some(client) = Self::env_value("SSH_CLIENT") {
+            for (key, value) in ["client_ip", "client_port"]
+                .iter()
+                .zip(client.split_whitespace())
+            {
+                info.insert(key.to_string(), value.to_string());
+            }
+        }
+        info
+    }
+}
+
+impl Segment for SshSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        if !HostnameSegment::is_ssh_session() {
+            return None;
+        }
+
+        let mut metadata = Self::connection_info();
+        let user = Self::env_value("USER").or_else(|| Self::env_value("USERNAME"));
+        let host = HostnameSegment::read_hostname()
+            .map(|hostname| hostname.split('.').next().unwrap_or(&hostname).to_string())
+            .or_else(|| metadata.get("server_ip").cloned());
+
+        let user_host = match (&user, &host) {
+            (Some(user), Some(host)) if self.show_user_host => Some(format!("{}@{}", user, host)),
+            _ => None,
+        };
+
+        if let Some(user) = user {
+            metadata.insert("user".to_string(), user);
+        }
+        if let Some(host) = host {
+            metadata.insert("host".to_string(), host);
+        }
+        if let Some(ref user_host) = user_host {
+            metadata.insert("user_host".to_string(), user_host.clone());
+        }
+
+        Some(SegmentData {
+            primary: user_host.unwrap_or_else(|| "ssh".to_string()),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Ssh
+    }
+}
+
+/// With `merge_into_hostname`, fold the SSH marker into the hostname segment: the hostname
+/// takes the SSH icon and `user@host` text (or an "ssh" secondary) and keeps its own colors.
+/// Without a rendered hostname segment the SSH segment keeps its own slot.
+pub fn merge_into_hostname(
+    mut collected: Vec<(SegmentConfig, SegmentData)>,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    let Some(ssh_index) = collected.iter().position(|(config, _)| {
+        config.id == SegmentId::Ssh
+            && config
+                .options
+                .get("merge_into_hostname")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+    }) else {
+        return collected;
+    };
+    if !collected
+        .iter()
+        .any(|(config, _)| config.id == SegmentId::Hostname)
+    {
+        return collected;
+    }
+
+    let (ssh_config, ssh_data) = collected.remove(ssh_index);
+    if let Some((_, hostname)) = collected
+        .iter_mut()
+        .find(|(config, _)| config.id == SegmentId::Hostname)
+    {
+        match ssh_data.metadata.get("user_host") {
+            Some(user_host) => hostname.primary = user_host.clone(),
+            None => hostname.secondary = "ssh".to_string(),
+        }
+        hostname
+            .metadata
+            .insert("dynamic_icon".to_string(), ssh_config.icon.nerd_font);
+    }
+
+    collected
+}
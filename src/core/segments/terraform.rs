@@ -0,0 +1,77 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct TerraformSegment {
+    warning_pattern: String,
+}
+
+impl Default for TerraformSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerraformSegment {
+    pub fn new() -> Self {
+        Self {
+            warning_pattern: "prod.*".to_string(),
+        }
+    }
+
+    /// Workspaces matching this regex render in the warning color
+    pub fn with_warning_pattern(mut self, warning_pattern: impl Into<String>) -> Self {
+        self.warning_pattern = warning_pattern.into();
+        self
+    }
+
+    /// The workspace's `.terraform` directory, refusing symlinks that point outside it
+    fn terraform_dir(workspace: &Path) -> Option<PathBuf> {
+        let dir = workspace.join(".terraform");
+        let metadata = fs::symlink_metadata(&dir).ok()?;
+
+        if metadata.file_type().is_symlink() {
+            let target = fs::canonicalize(&dir).ok()?;
+            let root = fs::canonicalize(workspace).ok()?;
+            if !target.starts_with(&root) || !target.is_dir() {
+                return None;
+            }
+        } else if !metadata.is_dir() {
+            return None;
+        }
+
+        Some(dir)
+    }
+}
+
+impl Segment for TerraformSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let dir = Self::terraform_dir(Path::new(&input.workspace.current_dir))?;
+
+        // Terraform only writes this file once a non-default workspace is selected
+        let workspace = fs::read_to_string(dir.join("environment"))
+            .map(|content| content.trim().to_string())
+            .ok()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "default".to_string());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("workspace".to_string(), workspace.clone());
+        if Regex::new(&self.warning_pattern).is_ok_and(|re| re.is_match(&workspace)) {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: workspace,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Terraform
+    }
+}
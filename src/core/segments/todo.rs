@@ -0,0 +1,130 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One item of the list Claude Code keeps through its TodoWrite tool
+#[derive(Debug, Deserialize)]
+struct TodoItem {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    status: String,
+    #[serde(rename = "activeForm")]
+    active_form: Option<String>,
+}
+
+pub struct TodoSegment {
+    max_length: usize,
+}
+
+impl Default for TodoSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TodoSegment {
+    pub fn new() -> Self {
+        Self { max_length: 30 }
+    }
+
+    /// Truncate the in-progress task to this many characters (zero hides it)
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    fn todos_dir() -> Option<PathBuf> {
+        Some(dirs::home_dir()?.join(".claude").join("todos"))
+    }
+
+    /// Todo files are named `<session>-agent-<agent>.json`. Prefer the main agent's list,
+    /// otherwise take the most recently written file for the session.
+    fn find_todo_file(dir: &Path, session_id: &str) -> Option<PathBuf> {
+        let main_file = dir.join(format!("{}-agent-{}.json", session_id, session_id));
+        if main_file.is_file() {
+            return Some(main_file);
+        }
+
+        let prefix = format!("{}-", session_id);
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with(&prefix) && name.ends_with(".json")
+            })
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .max_by_key(|(modified, _)| *modified)
+            .map(|(_, path)| path)
+    }
+
+    fn truncate(&self, text: &str) -> String {
+        if text.chars().count() <= self.max_length {
+            return text.to_string();
+        }
+
+        let kept: String = text
+            .chars()
+            .take(self.max_length.saturating_sub(1))
+            .collect();
+        format!("{}…", kept.trim_end())
+    }
+}
+
+impl Segment for TodoSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let session_id = input.session_id.as_deref().filter(|id| !id.is_empty())?;
+        let path = Self::find_todo_file(&Self::todos_dir()?, session_id)?;
+        let todos: Vec<TodoItem> = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+
+        if todos.is_empty() {
+            return None;
+        }
+
+        let completed = todos
+            .iter()
+            .filter(|todo| todo.status == "completed")
+            .count();
+        let in_progress = todos
+            .iter()
+            .find(|todo| todo.status == "in_progress")
+            .map(|todo| {
+                todo.active_form
+                    .as_deref()
+                    .filter(|form| !form.is_empty())
+                    .unwrap_or(&todo.content)
+                    .trim()
+            })
+            .filter(|task| !task.is_empty());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("completed".to_string(), completed.to_string());
+        metadata.insert("total".to_string(), todos.len().to_string());
+        if let Some(task) = in_progress {
+            metadata.insert("in_progress".to_string(), task.to_string());
+        }
+
+        let secondary = match in_progress {
+            Some(task) if self.max_length > 0 => self.truncate(task),
+            _ => String::new(),
+        };
+
+        Some(SegmentData {
+            primary: format!("{}/{}", completed, todos.len()),
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Todo
+    }
+}
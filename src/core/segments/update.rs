@@ -1,25 +1,58 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
 use crate::updater::UpdateState;
+use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct UpdateSegment;
+/// Set to any non-empty value to never contact GitHub for release checks
+const OFFLINE_ENV: &str = "CCLINE_OFFLINE";
+
+/// Announces a newer release, or the progress of `ccline --update`, from the cached update state
+pub struct UpdateSegment {
+    auto_check: bool,
+}
+
+impl Default for UpdateSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl UpdateSegment {
     pub fn new() -> Self {
-        Self
+        Self { auto_check: true }
+    }
+
+    /// Refresh the cached latest version in the background once a day
+    pub fn with_auto_check(mut self, auto_check: bool) -> Self {
+        self.auto_check = auto_check;
+        self
+    }
+
+    fn offline() -> bool {
+        std::env::var(OFFLINE_ENV).is_ok_and(|value| !value.is_empty())
     }
 }
 
 impl Segment for UpdateSegment {
     fn collect(&self, _input: &InputData) -> Option<SegmentData> {
-        // Load update state and check for update status
-        let update_state = UpdateState::load();
+        // Rendering only reads the cached state; a stale one is refreshed by a detached process
+        let mut update_state = UpdateState::load();
+        if self.auto_check && !Self::offline() {
+            update_state.spawn_background_check();
+        }
+
+        let status_text = update_state.status_text()?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("current".to_string(), env!("CARGO_PKG_VERSION").to_string());
+        if let Some(latest) = update_state.latest_version {
+            metadata.insert("latest".to_string(), latest);
+        }
 
-        update_state.status_text().map(|status_text| SegmentData {
+        Some(SegmentData {
             primary: status_text,
             secondary: String::new(),
-            metadata: std::collections::HashMap::new(),
+            metadata,
         })
     }
 
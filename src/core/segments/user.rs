@@ -0,0 +1,116 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Default)]
+pub struct UserSegment {
+    default_user: Option<String>,
+}
+
+impl UserSegment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hide the segment when running as this user
+    pub fn with_default_user(mut self, default_user: Option<String>) -> Self {
+        self.default_user = default_user.filter(|user| !user.is_empty());
+        self
+    }
+
+    /// Effective uid of this process, read from procfs
+    #[cfg(target_os = "linux")]
+    fn effective_uid() -> Option<u32> {
+        // "Uid:\t<real>\t<effective>\t<saved>\t<fs>"
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with("Uid:"))?;
+        line.split_whitespace().nth(2)?.parse().ok()
+    }
+
+    /// Look up a uid's login name in /etc/passwd
+    #[cfg(target_os = "linux")]
+    fn user_name_for_uid(uid: u32) -> Option<String> {
+        let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+        passwd.lines().find_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let entry_uid: u32 = fields.nth(1)?.parse().ok()?;
+            (entry_uid == uid).then(|| name.to_string())
+        })
+    }
+
+    /// Resolve (user name, is root) for the effective user
+    #[cfg(target_os = "linux")]
+    fn current_user() -> Option<(String, bool)> {
+        if let Some(uid) = Self::effective_uid() {
+            let name = Self::user_name_for_uid(uid)
+                .or_else(Self::user_from_env)
+                .unwrap_or_else(|| uid.to_string());
+            return Some((name, uid == 0));
+        }
+        let name = Self::user_from_env()?;
+        let is_root = name == "root";
+        Some((name, is_root))
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    fn current_user() -> Option<(String, bool)> {
+        use crate::utils::OutputWithTimeout;
+        use std::process::Command;
+        use std::time::Duration;
+
+        // `id -un` reports the effective user, unlike $USER under sudo
+        let name = Command::new("id")
+            .arg("-un")
+            .output_with_timeout(Duration::from_millis(500))
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .or_else(Self::user_from_env)?;
+        let is_root = name == "root";
+        Some((name, is_root))
+    }
+
+    #[cfg(not(unix))]
+    fn current_user() -> Option<(String, bool)> {
+        let name = Self::user_from_env()?;
+        Some((name, false))
+    }
+
+    fn user_from_env() -> Option<String> {
+        ["USER", "LOGNAME", "USERNAME"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+    }
+}
+
+impl Segment for UserSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let (user, is_root) = Self::current_user()?;
+
+        if self.default_user.as_deref() == Some(user.as_str()) {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("user".to_string(), user.clone());
+        metadata.insert("is_root".to_string(), is_root.to_string());
+        if is_root {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: user,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::User
+    }
+}
@@ -0,0 +1,99 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::core::state::StateFile;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The last Claude Code version seen and when it replaced the one before it
+#[derive(Debug, Serialize, Deserialize)]
+struct VersionState {
+    version: String,
+    changed_at: Option<DateTime<Utc>>,
+}
+
+pub struct VersionSegment {
+    only_on_change: bool,
+    notice_minutes: i64,
+}
+
+impl Default for VersionSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VersionSegment {
+    pub fn new() -> Self {
+        Self {
+            only_on_change: false,
+            notice_minutes: 60,
+        }
+    }
+
+    /// Only show the version for `notice_minutes` after Claude Code was updated
+    pub fn with_only_on_change(mut self, only_on_change: bool, notice_minutes: i64) -> Self {
+        self.only_on_change = only_on_change;
+        self.notice_minutes = notice_minutes;
+        self
+    }
+
+    /// Record `version` and return when it was first seen replacing an older version.
+    /// The very first version ever recorded is not treated as an update.
+    fn track_version(version: &str) -> Option<DateTime<Utc>> {
+        let file = StateFile::new(".last_version.json")?;
+        if let Some(state) = file.load::<Option<VersionState>>() {
+            if state.version == version {
+                return state.changed_at;
+            }
+        }
+
+        file.update(|state: &mut Option<VersionState>| match state {
+            Some(state) if state.version == version => state.changed_at,
+            previous => {
+                let changed_at = previous.as_ref().map(|_| Utc::now());
+                *previous = Some(VersionState {
+                    version: version.to_string(),
+                    changed_at,
+                });
+                changed_at
+            }
+        })
+    }
+}
+
+impl Segment for VersionSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let version = input
+            .version
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())?;
+
+        let changed_at = Self::track_version(version);
+        let recently_updated = changed_at.is_some_and(|changed_at| {
+            Utc::now().signed_duration_since(changed_at) < Duration::minutes(self.notice_minutes)
+        });
+
+        if self.only_on_change && !recently_updated {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("version".to_string(), version.to_string());
+        metadata.insert("updated".to_string(), recently_updated.to_string());
+        if let Some(changed_at) = changed_at {
+            metadata.insert("changed_at".to_string(), changed_at.to_rfc3339());
+        }
+
+        Some(SegmentData {
+            primary: version.to_string(),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Version
+    }
+}
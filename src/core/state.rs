@@ -0,0 +1,135 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a render waits for another pane to release a state file
+const LOCK_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// A lock file this old was left by a render that died while holding it
+const STALE_LOCK: Duration = Duration::from_secs(5);
+
+/// Exclusive hold on a state file, as a lock file that only one process can create
+struct StateLock {
+    path: PathBuf,
+}
+
+impl StateLock {
+    fn acquire(path: PathBuf) -> Option<Self> {
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Some(Self { path }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(&path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > STALE_LOCK);
+                    if stale {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return None;
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+impl Drop for StateLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// A JSON file in `~/.claude/ccline` that segments keep state in between renders.
+/// Several panes render at once, so changes are made under a lock file and written
+/// through a temporary file; readers never see half a file.
+pub struct StateFile {
+    path: PathBuf,
+}
+
+impl StateFile {
+    /// `name` is the file name inside `~/.claude/ccline`, e.g. `.burn_rate.json`
+    pub fn new(name: &str) -> Option<Self> {
+        let home = dirs::home_dir()?;
+        Some(Self::at(home.join(".claude").join("ccline").join(name)))
+    }
+
+    pub(crate) fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Current contents; a missing or unreadable file reads as the default
+    pub fn load<T: DeserializeOwned + Default>(&self) -> T {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Apply `update` to the stored value and write it back, returning what `update`
+    /// returns. The file is read again under the lock, so changes other processes made
+    /// in the meantime are kept. When the lock can't be had in time, `update` still runs
+    /// on the current contents but nothing is written.
+    pub fn update<T, R>(&self, update: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: Serialize + DeserializeOwned + Default,
+    {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let lock = StateLock::acquire(self.path.with_extension("lock"));
+
+        let mut value = self.load();
+        let result = update(&mut value);
+
+        if lock.is_some() {
+            if let Ok(json) = serde_json::to_string(&value) {
+                let tmp_path = self.path.with_extension("json.tmp");
+                if fs::write(&tmp_path, json).is_ok() {
+                    let _ = fs::rename(&tmp_path, &self.path);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn concurrent_updates_are_all_kept() {
+        let dir = std::env::temp_dir().join(format!("ccline-state-{}", std::process::id()));
+        let path = dir.join(".counts.json");
+        let _ = fs::remove_dir_all(&dir);
+
+        thread::scope(|scope| {
+            for i in 0..8 {
+                let path = path.clone();
+                scope.spawn(move || {
+                    StateFile::at(path).update(|counts: &mut HashMap<String, u32>| {
+                        counts.insert(i.to_string(), i);
+                    })
+                });
+            }
+        });
+
+        let counts: HashMap<String, u32> = StateFile::at(path.clone()).load();
+        assert_eq!(counts.len(), 8);
+        assert!(!path.with_extension("lock").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,67 +1,85 @@
-use crate::config::{AnsiColor, Config, SegmentConfig, StyleMode};
+use crate::config::{AnsiColor, Config, SegmentConfig, SegmentId, StyleMode};
 use crate::core::segments::SegmentData;
+use crate::utils::ansi::{truncate_to_width, visible_width};
 
-/// Strip ANSI escape sequences and return visible text length
-fn visible_width(text: &str) -> usize {
-    let mut visible = String::new();
-    let mut in_escape = false;
-    let mut chars = text.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Start of ANSI escape sequence
-            in_escape = true;
-            // Skip the [ character
-            if chars.peek() == Some(&'[') {
-                chars.next();
-            }
-        } else if in_escape {
-            // Skip until we find the end of the escape sequence (letter)
-            if ch.is_alphabetic() {
-                in_escape = false;
-            }
-        } else {
-            // Regular character
-            visible.push(ch);
-        }
-    }
+/// Nerd Font powerline arrow separating segments with background colors
+const POWERLINE_ARROW: &str = "\u{e0b0}";
+
+/// Separator used in place of Nerd Font glyphs when the font lacks them
+const FALLBACK_SEPARATOR: &str = " | ";
+
+/// Segments in display order, paired with their collected data
+type Segments = Vec<(SegmentConfig, SegmentData)>;
 
-    visible.chars().count()
+/// Nerd Font glyphs live in the Unicode private use areas
+fn has_nerd_font_glyph(text: &str) -> bool {
+    text.chars().any(|ch| {
+        ('\u{e000}'..='\u{f8ff}').contains(&ch)
+            || ('\u{f0000}'..='\u{ffffd}').contains(&ch)
+            || ('\u{100000}'..='\u{10fffd}').contains(&ch)
+    })
+}
+
+/// ASCII label for a segment whose plain icon still needs a Nerd Font
+fn ascii_icon(id: SegmentId) -> &'static str {
+    match id {
+        SegmentId::Model => "model",
+        SegmentId::Directory => "dir",
+        SegmentId::Git => "git",
+        SegmentId::ContextWindow => "ctx",
+        SegmentId::Usage => "usage",
+        SegmentId::Cost => "$",
+        _ => ">",
+    }
 }
 
 pub struct StatusLineGenerator {
     config: Config,
+    color: bool,
+    max_width: Option<usize>,
 }
 
 impl StatusLineGenerator {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let color = config.style.color;
+        Self {
+            config,
+            color,
+            max_width: None,
+        }
     }
 
-    pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
-        let mut output = Vec::new();
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
-            .collect();
+    /// Emit ANSI colors and styles; when disabled the output is plain text
+    /// with the same separators and segment order. Defaults to `style.color`
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
 
-        for (config, data) in enabled_segments.iter() {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                output.push(rendered);
-            }
-        }
+    /// Columns the line may fill. Right-aligned segments are pushed against this edge;
+    /// when unknown they simply follow the left ones after a separator
+    pub fn with_max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
 
-        if output.is_empty() {
+    pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
+        let visible_segments = Self::visible_segments(segments);
+        if visible_segments.is_empty() {
             return String::new();
         }
 
-        // Handle Powerline arrow separators with color transition
-        if self.config.style.separator == "\u{e0b0}" {
-            self.join_with_powerline_arrows(&output, &enabled_segments)
+        let (left, right) = Self::split_aligned(visible_segments);
+        let line = if right.is_empty() {
+            self.join_group(&left)
         } else {
-            // For all other separators, use white color and simple join
-            self.join_with_white_separators(&output)
+            self.join_aligned(&left, &right)
+        };
+
+        if self.color {
+            line
+        } else {
+            crate::utils::ansi::strip_ansi(&line)
         }
     }
 
@@ -97,35 +115,26 @@ impl StatusLineGenerator {
         use ansi_to_tui::IntoText;
         use ratatui::text::{Line, Span, Text};
 
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
-            .collect();
-
-        if enabled_segments.is_empty() {
+        let visible_segments = Self::visible_segments(segments);
+        if visible_segments.is_empty() {
             return Text::from(vec![Line::default()]);
         }
+        // The preview wraps instead of aligning, so right-aligned segments just go last
+        let (mut visible_segments, right) = Self::split_aligned(visible_segments);
+        visible_segments.extend(right);
 
         // Render each segment individually
-        let mut rendered_segments = Vec::new();
-        let mut segment_configs = Vec::new();
-
-        for (config, data) in &enabled_segments {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                rendered_segments.push(rendered);
-                segment_configs.push(config.clone());
-            }
-        }
-
-        if rendered_segments.is_empty() {
-            return Text::from(vec![Line::default()]);
-        }
+        let rendered_segments: Vec<String> = visible_segments
+            .iter()
+            .map(|(config, data)| self.render_segment(config, data))
+            .collect();
+        let segment_configs: Vec<&SegmentConfig> =
+            visible_segments.iter().map(|(config, _)| config).collect();
 
         // Pre-calculate separators between segments
         let mut separators = Vec::new();
         for i in 0..rendered_segments.len().saturating_sub(1) {
-            let separator = if self.config.style.separator == "\u{e0b0}" {
+            let separator = if self.use_powerline_arrows() {
                 // Powerline arrows with color transition
                 let prev_bg = segment_configs
                     .get(i)
@@ -136,7 +145,10 @@ impl StatusLineGenerator {
                 self.create_powerline_arrow(prev_bg, curr_bg)
             } else {
                 // Regular separators with white color
-                format!("\x1b[37m{}\x1b[0m", self.config.style.separator)
+                format!(
+                    "\x1b[37m{}\x1b[0m",
+                    self.separator_after(segment_configs[i])
+                )
             };
             separators.push(separator);
         }
@@ -214,19 +226,112 @@ impl StatusLineGenerator {
         Text::from(tui_lines)
     }
 
-    fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
-        let icon = if let Some(dynamic_icon) = data.metadata.get("dynamic_icon") {
-            dynamic_icon.clone()
+    /// Enabled segments with something to show. Dropping the rest before joining keeps
+    /// hidden segments from leaving doubled or dangling separators
+    fn visible_segments(
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> Vec<(SegmentConfig, SegmentData)> {
+        segments
+            .into_iter()
+            .filter(|(config, data)| config.enabled && !data.primary.is_empty())
+            .collect()
+    }
+
+    /// Split into the left group and the segments with `align = "right"`, keeping order
+    fn split_aligned(segments: Segments) -> (Segments, Segments) {
+        segments.into_iter().partition(|(config, _)| {
+            config.options.get("align").and_then(|v| v.as_str()) != Some("right")
+        })
+    }
+
+    /// Render segments and join them with the style's separators
+    fn join_group(&self, segments: &[(SegmentConfig, SegmentData)]) -> String {
+        let output: Vec<String> = segments
+            .iter()
+            .map(|(config, data)| self.render_segment(config, data))
+            .collect();
+
+        // Handle Powerline arrow separators with color transition
+        if self.use_powerline_arrows() {
+            self.join_with_powerline_arrows(&output, segments)
         } else {
-            self.get_icon(config)
+            // For all other separators, use white color and simple join
+            self.join_with_white_separators(&output, segments)
+        }
+    }
+
+    /// Place the right group against the edge of `max_width`, filling the gap with
+    /// spaces. When the two don't fit the left group is cut, since the right one is
+    /// usually short status that should stay readable
+    fn join_aligned(
+        &self,
+        left: &[(SegmentConfig, SegmentData)],
+        right: &[(SegmentConfig, SegmentData)],
+    ) -> String {
+        let right_line = self.join_group(right);
+        let left_line = self.join_group(left);
+
+        let Some(max_width) = self.max_width else {
+            let Some((last, _)) = left.last() else {
+                return right_line;
+            };
+            let separator = if self.use_powerline_arrows() {
+                self.create_powerline_arrow(
+                    last.colors.background.as_ref(),
+                    right[0].0.colors.background.as_ref(),
+                )
+            } else {
+                format!("\x1b[37m{}\x1b[0m", self.separator_after(last))
+            };
+            return format!("{}{}{}", left_line, separator, right_line);
         };
 
+        let right_width = visible_width(&right_line);
+        // At least one space keeps the groups apart
+        let left_budget = max_width.saturating_sub(right_width + 1);
+        let marker = &self.config.style.truncate_marker;
+        let left_line = if visible_width(&left_line) <= left_budget {
+            left_line
+        } else if left_budget > visible_width(marker) {
+            truncate_to_width(&left_line, left_budget, marker)
+        } else {
+            String::new()
+        };
+
+        let gap = max_width.saturating_sub(visible_width(&left_line) + right_width);
+        // Reset first so the gap never carries the left group's background
+        format!("{}\x1b[0m{}{}", left_line, " ".repeat(gap), right_line)
+    }
+
+    fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
+        let (left_pad, right_pad) = self.padding(config);
+        let left_pad = " ".repeat(left_pad);
+        let right_pad = " ".repeat(right_pad);
+
+        let icon = match data.metadata.get("dynamic_icon") {
+            Some(dynamic_icon) if self.use_nerd_font() || !has_nerd_font_glyph(dynamic_icon) => {
+                dynamic_icon.clone()
+            }
+            _ => self.get_icon(config),
+        };
+
+        // Segments may flag a level (e.g. "warning") that swaps in a color from their options
+        let level_color = Self::level_color(config, data);
+        let icon_color = level_color.as_ref().or(config.colors.icon.as_ref());
+        let text_color = level_color.as_ref().or(config.colors.text.as_ref());
+        // An optional `secondary_color` option styles the secondary text separately
+        let secondary_option_color = Self::option_color(config, "secondary_color");
+        let secondary_color = level_color
+            .as_ref()
+            .or(secondary_option_color.as_ref())
+            .or(config.colors.text.as_ref());
+
         // Apply background color to the entire segment if set
         if let Some(bg_color) = &config.colors.background {
             let bg_code = self.apply_background_color(bg_color);
 
             // Build the entire segment content first
-            let icon_colored = if let Some(icon_color) = &config.colors.icon {
+            let icon_colored = if let Some(icon_color) = icon_color {
                 self.apply_color(&icon, Some(icon_color))
                     .replace("\x1b[0m", "")
             } else {
@@ -234,59 +339,73 @@ impl StatusLineGenerator {
             };
 
             let text_styled = self
-                .apply_style(
-                    &data.primary,
-                    config.colors.text.as_ref(),
-                    config.styles.text_bold,
-                )
+                .apply_style(&data.primary, text_color, config.styles.text_bold)
                 .replace("\x1b[0m", "");
 
-            let mut segment_content = format!(" {} {} ", icon_colored, text_styled);
+            let mut segment_content = if icon.is_empty() {
+                format!(" {} ", text_styled)
+            } else {
+                format!(" {} {} ", icon_colored, text_styled)
+            };
 
             if !data.secondary.is_empty() {
                 let secondary_styled = self
-                    .apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold,
-                    )
+                    .apply_style(&data.secondary, secondary_color, config.styles.text_bold)
                     .replace("\x1b[0m", "");
                 segment_content.push_str(&format!("{} ", secondary_styled));
             }
 
-            // Apply background to the entire content and reset at the end
-            format!("{}{}\x1b[49m", bg_code, segment_content)
+            // Apply background to the entire content, padding included, and reset at the end
+            format!(
+                "{}{}{}{}\x1b[49m",
+                bg_code, left_pad, segment_content, right_pad
+            )
         } else {
             // No background color, use original logic
-            let icon_colored = self.apply_color(&icon, config.colors.icon.as_ref());
-            let text_styled = self.apply_style(
-                &data.primary,
-                config.colors.text.as_ref(),
-                config.styles.text_bold,
-            );
+            let icon_colored = self.apply_color(&icon, icon_color);
+            let text_styled = self.apply_style(&data.primary, text_color, config.styles.text_bold);
 
-            let mut segment = format!("{} {}", icon_colored, text_styled);
+            // Themes without icons (e.g. ascii) leave the icon empty
+            let mut segment = if icon.is_empty() {
+                text_styled
+            } else {
+                format!("{} {}", icon_colored, text_styled)
+            };
 
             if !data.secondary.is_empty() {
                 segment.push_str(&format!(
                     " {}",
-                    self.apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold
-                    )
+                    self.apply_style(&data.secondary, secondary_color, config.styles.text_bold)
                 ));
             }
 
-            segment
+            format!("{}{}{}", left_pad, segment, right_pad)
         }
     }
 
+    /// Resolve the `<level>_color` option for a segment reporting a `level` in its metadata
+    fn level_color(config: &SegmentConfig, data: &SegmentData) -> Option<AnsiColor> {
+        let level = data.metadata.get("level")?;
+        Self::option_color(config, &format!("{}_color", level))
+    }
+
+    fn option_color(config: &SegmentConfig, key: &str) -> Option<AnsiColor> {
+        let value = config.options.get(key)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Nerd Font icons are used unless the mode is plain or `nerd_font = false`
+    fn use_nerd_font(&self) -> bool {
+        self.config.style.nerd_font && self.config.style.mode != StyleMode::Plain
+    }
+
     fn get_icon(&self, config: &SegmentConfig) -> String {
-        match self.config.style.mode {
-            StyleMode::Plain => config.icon.plain.clone(),
-            StyleMode::NerdFont => config.icon.nerd_font.clone(),
-            StyleMode::Powerline => config.icon.nerd_font.clone(), // Future: use Powerline icons
+        if self.use_nerd_font() {
+            config.icon.nerd_font.clone()
+        } else if has_nerd_font_glyph(&config.icon.plain) {
+            ascii_icon(config.id).to_string()
+        } else {
+            config.icon.plain.clone()
         }
     }
 
@@ -357,15 +476,66 @@ impl StatusLineGenerator {
         }
     }
 
-    /// Join segments with white separators (non-Powerline)
-    fn join_with_white_separators(&self, rendered_segments: &[String]) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+    /// Powerline arrows are drawn in powerline mode or when the separator is the arrow
+    /// glyph itself, unless the font has no Nerd Font glyphs
+    fn use_powerline_arrows(&self) -> bool {
+        let style = &self.config.style;
+        style.nerd_font
+            && (style.mode == StyleMode::Powerline || style.separator == POWERLINE_ARROW)
+    }
+
+    /// The configured separator, or a plain one if it relies on missing Nerd Font glyphs
+    fn separator(&self) -> &str {
+        let separator = self.config.style.separator.as_str();
+        if has_nerd_font_glyph(separator) && !self.config.style.nerd_font {
+            FALLBACK_SEPARATOR
+        } else {
+            separator
         }
+    }
+
+    /// Separator between a segment and the next: its `separator` option, else the style's
+    fn separator_after<'a>(&'a self, config: &'a SegmentConfig) -> &'a str {
+        match config.options.get("separator").and_then(|v| v.as_str()) {
+            Some(separator) if has_nerd_font_glyph(separator) && !self.config.style.nerd_font => {
+                FALLBACK_SEPARATOR
+            }
+            Some(separator) => separator,
+            None => self.separator(),
+        }
+    }
+
+    /// Spaces around a segment: its `left_pad`/`right_pad` options, else the style's
+    fn padding(&self, config: &SegmentConfig) -> (usize, usize) {
+        let pad = |key: &str, default: usize| {
+            config
+                .options
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map_or(default, |pad| pad as usize)
+        };
+        (
+            pad("left_pad", self.config.style.left_pad),
+            pad("right_pad", self.config.style.right_pad),
+        )
+    }
 
-        // Use white color for separator
-        let white_separator = format!("\x1b[37m{}\x1b[0m", self.config.style.separator);
-        rendered_segments.join(&white_separator)
+    /// Join segments with white separators (non-Powerline)
+    fn join_with_white_separators(
+        &self,
+        rendered_segments: &[String],
+        segment_configs: &[(SegmentConfig, SegmentData)],
+    ) -> String {
+        let mut line = String::new();
+        for (i, segment) in rendered_segments.iter().enumerate() {
+            if i > 0 {
+                // Use white color for separator
+                let separator = self.separator_after(&segment_configs[i - 1].0);
+                line.push_str(&format!("\x1b[37m{}\x1b[0m", separator));
+            }
+            line.push_str(segment);
+        }
+        line
     }
 
     /// Join segments with Powerline arrow separators with proper color transitions
@@ -410,7 +580,7 @@ impl StatusLineGenerator {
         prev_bg: Option<&AnsiColor>,
         curr_bg: Option<&AnsiColor>,
     ) -> String {
-        let arrow_char = "\u{e0b0}";
+        let arrow_char = POWERLINE_ARROW;
 
         match (prev_bg, curr_bg) {
             (Some(prev), Some(curr)) => {
@@ -458,69 +628,967 @@ pub fn collect_all_segments(
     config: &Config,
     input: &crate::config::InputData,
 ) -> Vec<(SegmentConfig, SegmentData)> {
+    let cache = crate::core::cache::SegmentCache::new();
+
+    // Segments block on git, subprocesses and network I/O, so collect them concurrently
+    let collected = std::thread::scope(|scope| {
+        let handles: Vec<_> = config
+            .ordered_segments()
+            .into_iter()
+            // Skip disabled segments to avoid unnecessary API requests
+            .filter(|segment_config| segment_config.enabled)
+            .map(|segment_config| {
+                let cache = &cache;
+                let handle = scope.spawn(move || collect_segment(segment_config, input, cache));
+                (segment_config, handle)
+            })
+            .collect();
+
+        // Join in configured order; a panicking segment is dropped instead of the whole line
+        let collected = handles
+            .into_iter()
+            .filter_map(|(segment_config, handle)| {
+                let data = handle.join().ok().flatten()?;
+                Some((segment_config.clone(), data))
+            })
+            .collect();
+
+        let collected = crate::core::condition::apply_conditions(collected, input);
+        let collected = crate::core::segments::ssh::merge_into_hostname(collected);
+        crate::core::segments::jujutsu::resolve_colocated(collected)
+    });
+
+    cache.save();
+    collected
+}
+
+fn collect_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+    cache: &crate::core::cache::SegmentCache,
+) -> Option<SegmentData> {
     use crate::core::segments::*;
 
-    let mut results = Vec::new();
-
-    for segment_config in &config.segments {
-        // Skip disabled segments to avoid unnecessary API requests
-        if !segment_config.enabled {
-            continue;
-        }
-
-        let segment_data = match segment_config.id {
-            crate::config::SegmentId::Model => {
-                let segment = ModelSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Directory => {
-                let show_full_path = segment_config
-                    .options
-                    .get("show_full_path")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let segment = DirectorySegment::new().with_full_path(show_full_path);
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Git => {
-                let show_sha = segment_config
-                    .options
-                    .get("show_sha")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let segment = GitSegment::new().with_sha(show_sha);
-                segment.collect(input)
-            }
-            crate::config::SegmentId::ContextWindow => {
-                let segment = ContextWindowSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Usage => {
-                let segment = UsageSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Cost => {
-                let segment = CostSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Session => {
-                let segment = SessionSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::OutputStyle => {
-                let segment = OutputStyleSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Update => {
-                let segment = UpdateSegment::new();
-                segment.collect(input)
+    match segment_config.id {
+        crate::config::SegmentId::Model => {
+            let aliases = segment_config
+                .options
+                .get("aliases")
+                .and_then(|v| v.as_object())
+                .map(|map| {
+                    map.iter()
+                        .filter_map(|(pattern, name)| {
+                            name.as_str()
+                                .map(|name| (pattern.clone(), name.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let segment = ModelSegment::new().with_aliases(aliases);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Directory => {
+            let show_full_path = segment_config
+                .options
+                .get("show_full_path")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let mut segment = DirectorySegment::new().with_full_path(show_full_path);
+            // "display" supersedes the older show_full_path flag when present
+            if let Some(display) = segment_config
+                .options
+                .get("display")
+                .and_then(|v| v.as_str())
+                .and_then(DirectoryDisplay::parse)
+            {
+                segment = segment.with_display(display);
             }
-        };
+            let max_path_components = segment_config
+                .options
+                .get("max_path_components")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0) as usize;
+            let segment = segment.with_max_path_components(max_path_components);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Git => {
+            let show_sha = segment_config
+                .options
+                .get("show_sha")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_tag = segment_config
+                .options
+                .get("show_tag")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_stash = segment_config
+                .options
+                .get("show_stash")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let dirty_symbol = segment_config
+                .options
+                .get("dirty_symbol")
+                .and_then(|v| v.as_str())
+                .unwrap_or("●");
+            let clean_symbol = segment_config
+                .options
+                .get("clean_symbol")
+                .and_then(|v| v.as_str())
+                .unwrap_or("✓");
+            let show_status = segment_config
+                .options
+                .get("show_status")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let untracked_files = segment_config
+                .options
+                .get("untracked_files")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let show_operation = segment_config
+                .options
+                .get("show_operation")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1000);
+            let segment = GitSegment::new()
+                .with_sha(show_sha)
+                .with_tag(show_tag)
+                .with_stash(show_stash)
+                .with_status(show_status)
+                .with_untracked_files(untracked_files)
+                .with_operation(show_operation)
+                .with_dirty_symbol(dirty_symbol)
+                .with_clean_symbol(clean_symbol)
+                .with_cache_ttl(std::time::Duration::from_millis(cache_ttl_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::ContextWindow => {
+            let precision = segment_config
+                .options
+                .get("precision")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1);
+            let segment = ContextWindowSegment::new().with_precision(precision as usize);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Usage => {
+            let segment = UsageSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Cost => {
+            let show_cents = segment_config
+                .options
+                .get("show_cents")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let precision = segment_config
+                .options
+                .get("precision")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1);
+            let segment = CostSegment::new()
+                .with_cents(show_cents)
+                .with_precision(precision as usize);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Session => {
+            let segment = SessionSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::OutputStyle => {
+            let always_show = segment_config
+                .options
+                .get("always_show")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = OutputStyleSegment::new().with_always_show(always_show);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Update => {
+            let auto_check = segment_config
+                .options
+                .get("auto_check")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let segment = UpdateSegment::new().with_auto_check(auto_check);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Battery => {
+            let warning_threshold = segment_config
+                .options
+                .get("warning_threshold")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(20)
+                .min(100) as u8;
+            let critical_threshold = segment_config
+                .options
+                .get("critical_threshold")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(10)
+                .min(100) as u8;
+            let segment =
+                BatterySegment::new().with_thresholds(warning_threshold, critical_threshold);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Clock => {
+            let format = segment_config
+                .options
+                .get("format")
+                .and_then(|v| v.as_str())
+                .unwrap_or("%H:%M");
+            let timezone = segment_config
+                .options
+                .get("timezone")
+                .and_then(|v| v.as_str())
+                .unwrap_or("local");
+            let segment = ClockSegment::new()
+                .with_format(format)
+                .with_timezone(timezone);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::SessionDuration => {
+            let segment = SessionDurationSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Hostname => {
+            let strip_domain = segment_config
+                .options
+                .get("strip_domain")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let ssh_only = segment_config
+                .options
+                .get("ssh_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = HostnameSegment::new()
+                .with_strip_domain(strip_domain)
+                .with_ssh_only(ssh_only);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::User => {
+            let default_user = segment_config
+                .options
+                .get("default_user")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            let segment = UserSegment::new().with_default_user(default_user);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::PythonEnv => {
+            let show_version = segment_config
+                .options
+                .get("show_version")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let segment = PythonEnvSegment::new().with_version(show_version);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Conda => {
+            let show_base = segment_config
+                .options
+                .get("show_base")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = CondaSegment::new().with_base(show_base);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Node => {
+            let run_node = segment_config
+                .options
+                .get("run_node")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = NodeSegment::new().with_run_node(run_node);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Rust => {
+            let always_run_rustc = segment_config
+                .options
+                .get("always_run_rustc")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = RustSegment::new().with_always_run_rustc(always_run_rustc);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Go => {
+            let max_depth = segment_config
+                .options
+                .get("max_depth")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(5) as usize;
+            let check_installed = segment_config
+                .options
+                .get("check_installed")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = GoSegment::new()
+                .with_max_depth(max_depth)
+                .with_check_installed(check_installed);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Java => {
+            let show_build_tool = segment_config
+                .options
+                .get("show_build_tool")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let segment = JavaSegment::new().with_build_tool(show_build_tool);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Dotnet => {
+            let segment = DotnetSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Kubernetes => {
+            let show_namespace = segment_config
+                .options
+                .get("show_namespace")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let dangerous_contexts = segment_config
+                .options
+                .get("dangerous_contexts")
+                .and_then(|v| v.as_array())
+                .map(|patterns| {
+                    patterns
+                        .iter()
+                        .filter_map(|p| p.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let segment = KubernetesSegment::new()
+                .with_namespace(show_namespace)
+                .with_dangerous_contexts(dangerous_contexts);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Docker => {
+            let only_when_nondefault = segment_config
+                .options
+                .get("only_when_nondefault")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let mut segment = DockerSegment::new().with_only_when_nondefault(only_when_nondefault);
+            if let Some(marker) = segment_config
+                .options
+                .get("project_marker")
+                .and_then(|v| v.as_str())
+            {
+                segment = segment.with_project_marker(marker);
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Terraform => {
+            let mut segment = TerraformSegment::new();
+            if let Some(pattern) = segment_config
+                .options
+                .get("warning_pattern")
+                .and_then(|v| v.as_str())
+            {
+                segment = segment.with_warning_pattern(pattern);
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Aws => {
+            let always_show = segment_config
+                .options
+                .get("always_show")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_expiry = segment_config
+                .options
+                .get("show_expiry")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let show_region = segment_config
+                .options
+                .get("show_region")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let prefix = segment_config
+                .options
+                .get("prefix")
+                .and_then(|v| v.as_str())
+                .unwrap_or("aws:");
+            let segment = AwsSegment::new()
+                .with_always_show(always_show)
+                .with_expiry(show_expiry)
+                .with_region(show_region)
+                .with_prefix(prefix);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Custom => {
+            let text = segment_config
+                .options
+                .get("text")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let segment = CustomTextSegment::new().with_text(text);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Gcloud => {
+            let show_account = segment_config
+                .options
+                .get("show_account")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = GcloudSegment::new().with_account(show_account);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Load => {
+            let display = segment_config
+                .options
+                .get("display")
+                .and_then(|v| v.as_str())
+                .and_then(LoadDisplay::parse)
+                .unwrap_or(LoadDisplay::Normalized);
+            let warning_threshold = segment_config
+                .options
+                .get("warning_threshold")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(70.0);
+            let critical_threshold = segment_config
+                .options
+                .get("critical_threshold")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(90.0);
+            let segment = LoadSegment::new()
+                .with_display(display)
+                .with_thresholds(warning_threshold, critical_threshold);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Disk => {
+            let show_below = segment_config
+                .options
+                .get("show_below")
+                .and_then(|v| v.as_str())
+                .and_then(DiskThreshold::parse);
+            let critical_below = segment_config
+                .options
+                .get("critical_below")
+                .and_then(|v| v.as_str())
+                .and_then(DiskThreshold::parse);
+            let always_show = segment_config
+                .options
+                .get("always_show")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let mut segment = DiskSegment::new().with_always_show(always_show);
+            if let Some(show_below) = show_below {
+                segment = segment.with_show_below(show_below);
+            }
+            if let Some(critical_below) = critical_below {
+                segment = segment.with_critical_below(critical_below);
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Version => {
+            let only_on_change = segment_config
+                .options
+                .get("only_on_change")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let notice_minutes = segment_config
+                .options
+                .get("notice_minutes")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(60);
+            let segment = VersionSegment::new().with_only_on_change(only_on_change, notice_minutes);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::LinesChanged => {
+            let compact_threshold = segment_config
+                .options
+                .get("compact_threshold")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1000)
+                .min(u32::MAX as u64) as u32;
+            let segment = LinesChangedSegment::new().with_compact_threshold(compact_threshold);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::MessageCount => {
+            let segment = MessageCountSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Todo => {
+            let max_length = segment_config
+                .options
+                .get("max_length")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(30) as usize;
+            let segment = TodoSegment::new().with_max_length(max_length);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Mcp => {
+            let project_only = segment_config
+                .options
+                .get("project_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = McpSegment::new().with_project_only(project_only);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::BurnRate => {
+            let window_minutes = segment_config
+                .options
+                .get("window_minutes")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(10)
+                .max(1);
+            let warning_threshold = segment_config
+                .options
+                .get("warning_threshold")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(5000.0);
+            let critical_threshold = segment_config
+                .options
+                .get("critical_threshold")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(10000.0);
+            let segment = BurnRateSegment::new()
+                .with_window(chrono::Duration::minutes(window_minutes))
+                .with_thresholds(warning_threshold, critical_threshold);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::ApiDuration => {
+            let segment = ApiDurationSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Command => {
+            let command = segment_config
+                .options
+                .get("command")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let args = segment_config
+                .options
+                .get("args")
+                .and_then(|v| v.as_array())
+                .map(|args| {
+                    args.iter()
+                        .filter_map(|arg| arg.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(500);
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let segment = CustomCommandSegment::new()
+                .with_command(command, args)
+                .with_timeout(std::time::Duration::from_millis(timeout_ms))
+                .with_cache_ttl(std::time::Duration::from_millis(cache_ttl_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Runtime => {
+            let mut segment = RuntimeSegment::new();
+            if let Some(runtimes) = segment_config
+                .options
+                .get("runtimes")
+                .and_then(|v| v.as_array())
+            {
+                segment = segment.with_runtimes(
+                    runtimes
+                        .iter()
+                        .filter_map(|r| r.as_str().map(str::to_string))
+                        .collect(),
+                );
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Env => {
+            let variable = segment_config
+                .options
+                .get("variable")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let prefix = segment_config
+                .options
+                .get("prefix")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let levels = segment_config
+                .options
+                .get("levels")
+                .and_then(|v| v.as_object())
+                .map(|map| {
+                    map.iter()
+                        .filter_map(|(value, level)| {
+                            level
+                                .as_str()
+                                .map(|level| (value.clone(), level.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let segment = EnvSegment::new()
+                .with_variable(variable)
+                .with_prefix(prefix)
+                .with_levels(levels);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Package => {
+            let mut segment = PackageSegment::new();
+            if let Some(manifests) = segment_config
+                .options
+                .get("manifests")
+                .and_then(|v| v.as_array())
+            {
+                segment = segment.with_manifests(
+                    manifests
+                        .iter()
+                        .filter_map(|m| m.as_str().map(str::to_string))
+                        .collect(),
+                );
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Nix => {
+            let show_purity = segment_config
+                .options
+                .get("show_purity")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let segment = NixSegment::new().with_purity(show_purity);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Direnv => {
+            let segment = DirenvSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Ssh => {
+            let show_user_host = segment_config
+                .options
+                .get("show_user_host")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let segment = SshSegment::new().with_user_host(show_user_host);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Environment => {
+            let mut segment = EnvironmentSegment::new();
+            if let Some(environments) = segment_config
+                .options
+                .get("environments")
+                .and_then(|v| v.as_array())
+            {
+                segment = segment.with_environments(
+                    environments
+                        .iter()
+                        .filter_map(|e| e.as_str().map(str::to_string))
+                        .collect(),
+                );
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Shell => {
+            let show_version = segment_config
+                .options
+                .get("show_version")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = ShellSegment::new().with_version(show_version);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::GithubPr => {
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1500);
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(120000);
+            let segment = GithubPrSegment::new()
+                .with_timeout(std::time::Duration::from_millis(timeout_ms))
+                .with_cache_ttl(std::time::Duration::from_millis(cache_ttl_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::CiStatus => {
+            let workflows = segment_config
+                .options
+                .get("workflows")
+                .and_then(|v| v.as_array())
+                .map(|workflows| {
+                    workflows
+                        .iter()
+                        .filter_map(|w| w.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1500);
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(120000);
+            let segment = CiStatusSegment::new()
+                .with_workflows(workflows)
+                .with_timeout(std::time::Duration::from_millis(timeout_ms))
+                .with_cache_ttl(std::time::Duration::from_millis(cache_ttl_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Jujutsu => {
+            let segment = JujutsuSegment::new();
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Hg => {
+            let show_status = segment_config
+                .options
+                .get("show_status")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let dirty_symbol = segment_config
+                .options
+                .get("dirty_symbol")
+                .and_then(|v| v.as_str())
+                .unwrap_or("●");
+            let clean_symbol = segment_config
+                .options
+                .get("clean_symbol")
+                .and_then(|v| v.as_str())
+                .unwrap_or("✓");
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(2000);
+            let segment = HgSegment::new()
+                .with_status(show_status)
+                .with_dirty_symbol(dirty_symbol)
+                .with_clean_symbol(clean_symbol)
+                .with_cache_ttl(std::time::Duration::from_millis(cache_ttl_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Connectivity => {
+            let mut segment = ConnectivitySegment::new();
+            if let Some(target) = segment_config
+                .options
+                .get("target")
+                .and_then(|v| v.as_str())
+            {
+                segment = segment.with_target(target);
+            }
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(200);
+            let cache_ttl_ms = segment_config
+                .options
+                .get("cache_ttl_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(30000);
+            let always_show = segment_config
+                .options
+                .get("always_show")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = segment
+                .with_timeout(std::time::Duration::from_millis(timeout_ms))
+                .with_interval(std::time::Duration::from_millis(cache_ttl_ms))
+                .with_always_show(always_show);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::GitDiff => {
+            let include_staged = segment_config
+                .options
+                .get("include_staged")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(500);
+            let segment = GitDiffSegment::new()
+                .with_staged(include_staged)
+                .with_timeout(std::time::Duration::from_millis(timeout_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::SessionId => {
+            let length = segment_config
+                .options
+                .get("length")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(8) as usize;
+            let link = segment_config
+                .options
+                .get("link")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = SessionIdSegment::new().with_length(length).with_link(link);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::LastPrompt => {
+            let max_length = segment_config
+                .options
+                .get("max_length")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(40) as usize;
+            let segment = LastPromptSegment::new().with_max_length(max_length);
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::Budget => {
+            let daily_limit = segment_config
+                .options
+                .get("daily_limit")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(10.0);
+            let warning_percent = segment_config
+                .options
+                .get("warning_percent")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(80.0);
+            let critical_percent = segment_config
+                .options
+                .get("critical_percent")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(100.0);
+            let mut segment = BudgetSegment::new()
+                .with_daily_limit(daily_limit)
+                .with_thresholds(warning_percent, critical_percent);
+            if let Some(timezone) = segment_config
+                .options
+                .get("timezone")
+                .and_then(|v| v.as_str())
+            {
+                segment = segment.with_timezone(timezone);
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::DailyCost => {
+            let mut segment = DailyCostSegment::new();
+            if let Some(timezone) = segment_config
+                .options
+                .get("timezone")
+                .and_then(|v| v.as_str())
+            {
+                segment = segment.with_timezone(timezone);
+            }
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::LastCommit => {
+            let warning_minutes = segment_config
+                .options
+                .get("warning_minutes")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(60);
+            let critical_minutes = segment_config
+                .options
+                .get("critical_minutes")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(180);
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(500);
+            let segment = LastCommitSegment::new()
+                .with_thresholds(warning_minutes, critical_minutes)
+                .with_timeout(std::time::Duration::from_millis(timeout_ms));
+            cache.collect(&segment, input)
+        }
+        crate::config::SegmentId::GitIdentity => {
+            let identities = segment_config
+                .options
+                .get("identities")
+                .and_then(|v| v.as_object())
+                .map(|map| {
+                    map.iter()
+                        .filter_map(|(pattern, style)| {
+                            let style = match style {
+                                serde_json::Value::String(label) => IdentityStyle {
+                                    label: Some(label.clone()),
+                                    level: None,
+                                },
+                                serde_json::Value::Object(style) => IdentityStyle {
+                                    label: style
+                                        .get("label")
+                                        .and_then(|v| v.as_str())
+                                        .map(String::from),
+                                    level: style
+                                        .get("level")
+                                        .and_then(|v| v.as_str())
+                                        .map(String::from),
+                                },
+                                _ => return None,
+                            };
+                            Some((pattern.clone(), style))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let default_identity = segment_config
+                .options
+                .get("default_identity")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let timeout_ms = segment_config
+                .options
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(500);
+            let segment = GitIdentitySegment::new()
+                .with_identities(identities)
+                .with_default_identity(default_identity)
+                .with_timeout(std::time::Duration::from_millis(timeout_ms));
+            cache.collect(&segment, input)
+        }
+    }
+}
 
-        if let Some(data) = segment_data {
-            results.push((segment_config.clone(), data));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::themes::ThemePresets;
+    use std::collections::HashMap;
+
+    fn data(primary: &str) -> SegmentData {
+        SegmentData {
+            primary: primary.to_string(),
+            secondary: String::new(),
+            metadata: HashMap::new(),
         }
     }
 
-    results
+    #[test]
+    fn hidden_middle_segments_leave_no_double_separator() {
+        let config = ThemePresets::get_default();
+        let separator = config.style.separator.clone();
+        let mut disabled = config.segments[2].clone();
+        disabled.enabled = false;
+        let segments = vec![
+            (config.segments[0].clone(), data("first")),
+            (config.segments[1].clone(), data("")),
+            (disabled, data("hidden")),
+            (config.segments[3].clone(), data("last")),
+        ];
+
+        let line = StatusLineGenerator::new(config)
+            .with_color(false)
+            .generate(segments);
+        assert_eq!(line.matches(separator.as_str()).count(), 1);
+        assert!(!line.contains("hidden"));
+        assert!(line.ends_with("last"));
+    }
 }
@@ -1,11 +1,26 @@
-use ccometixline::cli::Cli;
+use ccometixline::cli::{Cli, OutputFormat};
 use ccometixline::config::{Config, InputData};
 use ccometixline::core::{collect_all_segments, StatusLineGenerator};
-use std::io::{self, IsTerminal};
+use ccometixline::utils::terminal::{
+    get_terminal_width, max_line_width, truncate_to_terminal_width,
+};
+use std::io::{self, IsTerminal, Read};
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("ccline: {}", e);
+        std::process::exit(1);
+    }
+}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn run() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse_args();
 
+    if let Some(shell) = cli.completions {
+        Cli::print_completions(shell)?;
+        return Ok(());
+    }
+
     // Handle configuration commands
     if cli.init {
         use ccometixline::config::InitResult;
@@ -30,8 +45,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if let Some(ref path) = cli.export_theme {
+        let config = match cli.theme {
+            Some(ref theme) => ccometixline::ui::themes::ThemePresets::get_theme(theme),
+            None => Config::load().unwrap_or_else(|_| Config::default()),
+        };
+
+        ccometixline::ui::themes::ThemePresets::export_theme(&config, path)?;
+        println!("Exported theme {} to {}", config.theme, path.display());
+        return Ok(());
+    }
+
+    if let Some(ref path) = cli.import_theme {
+        let config = ccometixline::ui::themes::ThemePresets::import_theme(path, cli.force)?;
+        println!(
+            "Imported theme {} (use it with --theme {})",
+            config.theme, config.theme
+        );
+        return Ok(());
+    }
+
     if cli.check {
-        let config = Config::load()?;
+        // With --theme, validate that theme instead of the config file
+        let config = match cli.theme {
+            Some(ref theme) => ccometixline::ui::themes::ThemePresets::load_theme_from_file(theme)
+                .or_else(|e| {
+                    if ccometixline::ui::themes::ThemePresets::get_available_themes()
+                        .iter()
+                        .any(|(name, _)| name == theme)
+                    {
+                        Ok(ccometixline::ui::themes::ThemePresets::get_theme(theme))
+                    } else {
+                        Err(e)
+                    }
+                })?,
+            None => Config::load()?,
+        };
         config.check()?;
         println!("✓ Configuration valid");
         return Ok(());
@@ -50,10 +99,59 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if cli.refresh_update_state {
+        #[cfg(feature = "self-update")]
+        ccometixline::updater::UpdateState::refresh();
+        return Ok(());
+    }
+
     if cli.update {
         #[cfg(feature = "self-update")]
         {
-            println!("Update feature not implemented in new architecture yet");
+            use ccometixline::updater::github::{check_for_updates, install_release};
+            use ccometixline::updater::{UpdateState, UpdateStatus};
+
+            let current = env!("CARGO_PKG_VERSION");
+            let release = match check_for_updates() {
+                Ok(Some(release)) => release,
+                Ok(None) => {
+                    println!("ccline v{} is up to date", current);
+                    return Ok(());
+                }
+                Err(e) => {
+                    eprintln!("Could not check for updates: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let version = release.version();
+            if cli.check_only {
+                println!("Update available: v{} -> v{}", current, version);
+                println!("Run `ccline --update` to install it");
+                return Ok(());
+            }
+
+            println!("Updating ccline v{} -> v{}...", current, version);
+            if let Err(e) = install_release(&release) {
+                eprintln!("Update failed: {}", e);
+                std::process::exit(1);
+            }
+
+            // Lets the statusline briefly announce the update
+            let now = chrono::Utc::now();
+            let _ = UpdateState {
+                status: UpdateStatus::Completed {
+                    version: version.clone(),
+                    completed_at: now,
+                },
+                last_check: Some(now),
+                current_version: version.clone(),
+                latest_version: Some(version.clone()),
+                update_pid: None,
+            }
+            .save();
+
+            println!("Updated to v{}", version);
         }
         #[cfg(not(feature = "self-update"))]
         {
@@ -64,24 +162,39 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Handle Claude Code patcher
     if let Some(claude_path) = cli.patch {
-        use ccometixline::utils::ClaudeCodePatcher;
+        use ccometixline::utils::{ClaudeCodePatcher, PatchKind};
 
         println!("🔧 Claude Code Context Warning Disabler");
         println!("Target file: {}", claude_path);
 
+        // Load and make sure it's a release the patterns know before touching anything
+        let mut patcher = ClaudeCodePatcher::new(&claude_path)?;
+        let (major, minor, patch) = patcher.check_supported()?;
+        println!("Claude Code version: {}.{}.{}", major, minor, patch);
+        if patcher.is_newer_than_tested() {
+            let (major, minor, patch) = ccometixline::utils::LAST_TESTED_VERSION;
+            println!(
+                "⚠️ Newer than the last tested release {}.{}.{}; some patterns may not be found",
+                major, minor, patch
+            );
+        }
+
         // Create backup in same directory
         let backup_path = format!("{}.backup", claude_path);
         std::fs::copy(&claude_path, &backup_path)?;
         println!("📦 Created backup: {}", backup_path);
 
-        // Load and patch
-        let mut patcher = ClaudeCodePatcher::new(&claude_path)?;
+        let selected: &[PatchKind] = if cli.patches.is_empty() {
+            &PatchKind::DEFAULT
+        } else {
+            &cli.patches
+        };
 
         println!("\n🔄 Applying patches...");
-        let results = patcher.apply_all_patches();
+        let results = patcher.apply_patches(selected);
         patcher.save()?;
 
-        ClaudeCodePatcher::print_summary(&results);
+        patcher.print_summary(&results);
         println!("💡 To restore warnings, replace your cli.js with the backup file:");
         println!("   cp {} {}", backup_path, claude_path);
 
@@ -91,13 +204,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
     let mut config = Config::load().unwrap_or_else(|_| Config::default());
 
-    // Apply theme override if provided
+    // An explicit --theme wins over theme_rules
     if let Some(theme) = cli.theme {
         config = ccometixline::ui::themes::ThemePresets::get_theme(&theme);
+    } else if let Some(theme) = ccometixline::ui::themes::ThemePresets::resolve_theme(&config) {
+        config = theme;
+    }
+
+    if cli.validate_input {
+        use ccometixline::config::InputReport;
+        let mut raw = String::new();
+        io::stdin().read_to_string(&mut raw)?;
+        let report = InputReport::from_json(&raw);
+        println!("{}", report);
+        if !report.is_valid() {
+            std::process::exit(1);
+        }
+        return Ok(());
     }
 
     // Check if stdin has data
-    if io::stdin().is_terminal() {
+    if !cli.preview && io::stdin().is_terminal() {
         // No input data available, show main menu
         #[cfg(feature = "tui")]
         {
@@ -127,113 +254,85 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    // Read Claude Code data from stdin
-    let stdin = io::stdin();
-    let input: InputData = serde_json::from_reader(stdin.lock())?;
-
-    // Collect segment data
-    let segments_data = collect_all_segments(&config, &input);
-
-    // Render statusline
-    let generator = StatusLineGenerator::new(config);
-    let statusline = generator.generate(segments_data);
-
-    // Output statusline without truncation (test multi-line support)
-    println!("{}", statusline);
-
-    Ok(())
-}
-
-/// Calculate visible width of text (excluding ANSI escape sequences)
-fn visible_width(text: &str) -> usize {
-    let mut width = 0;
-    let mut in_escape = false;
-
-    for ch in text.chars() {
-        if ch == '\x1b' {
-            in_escape = true;
-        } else if in_escape {
-            if ch.is_alphabetic() {
-                in_escape = false;
+    // Read Claude Code data from stdin, or use the sample for --preview.
+    // Fields that don't parse are dropped so the rest of the line still renders.
+    let input: InputData = if cli.preview {
+        InputData::sample()
+    } else {
+        let value: serde_json::Value = match serde_json::from_reader(io::stdin().lock()) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("ccline: unreadable input, see --validate-input: {}", e);
+                println!();
+                return Ok(());
             }
-        } else {
-            // Count visible characters (CJK characters count as 2)
-            width += if ch > '\u{FF}' { 2 } else { 1 };
+        };
+        let (input, dropped) = InputData::from_value_lenient(value);
+        for field in dropped {
+            eprintln!(
+                "ccline: ignoring input field {}: {}",
+                field.path, field.error
+            );
         }
-    }
+        input
+    };
 
-    width
-}
+    // Project-level .ccline.toml overrides the global config
+    let config = config.with_project_overrides(&input.workspace.current_dir);
 
-/// Get terminal width using multiple fallback methods
-fn get_terminal_width() -> Option<usize> {
-    use std::io::IsTerminal;
+    // --segments / --disable win over both for this run only
+    let config = config.with_segment_selection(&cli.segments, &cli.disable)?;
 
-    // Method 1: Try terminal_size on stderr (stderr is usually still connected to terminal)
-    let stderr = std::io::stderr();
-    if stderr.is_terminal() {
-        if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size_of(&stderr) {
-            return Some(w as usize);
-        }
-    }
+    // Expanded for this render only; the loaded config keeps its ${VAR} references
+    let config = config.with_env_expanded();
 
-    // Method 2: Try COLUMNS environment variable
-    if let Ok(cols) = std::env::var("COLUMNS") {
-        if let Ok(w) = cols.parse::<usize>() {
-            return Some(w);
-        }
-    }
+    // Collect segment data
+    let segments_data = collect_all_segments(&config, &input);
 
-    // Method 3: Try terminal_size on stdout (fallback)
-    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
-        return Some(w as usize);
+    // Emit raw segment data for other tools instead of rendering
+    if cli.format == OutputFormat::Json {
+        let segments: Vec<_> = segments_data
+            .iter()
+            .map(|(segment_config, data)| {
+                serde_json::json!({
+                    "id": segment_config.id,
+                    "primary": data.primary,
+                    "secondary": data.secondary,
+                    "metadata": data.metadata,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string(&segments)?);
+        return Ok(());
     }
 
-    None
-}
-
-/// Truncate statusline to fit within a percentage of terminal width
-fn truncate_to_terminal_width(text: &str, percent: usize) -> String {
-    let max_width = if let Some(term_width) = get_terminal_width() {
-        // Reserve space for Claude Code's context indicator (~40 chars)
-        let reserved_for_context = 40;
-        let available = term_width.saturating_sub(reserved_for_context);
-        // Use the smaller of: percentage-based limit or available space
-        std::cmp::min((term_width * percent) / 100, available)
-    } else {
-        // Fallback: assume 120 char terminal, use 60%
-        72
-    };
+    // Render statusline
+    // https://no-color.org: any non-empty NO_COLOR disables color
+    let no_color = cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let truncate = config.truncate;
+    let truncate_percent = config.truncate_percent;
+    let reserved_width = config.reserved_width;
+    let max_width =
+        get_terminal_width().map(|w| max_line_width(w, truncate_percent, reserved_width));
+    let marker = config.style.truncate_marker.clone();
+    let color = config.style.color && !no_color;
+    let generator = StatusLineGenerator::new(config)
+        .with_color(color)
+        .with_max_width(max_width);
+    let statusline = generator.generate(segments_data);
 
-    let current_width = visible_width(text);
-    if current_width <= max_width {
-        return text.to_string();
+    if !truncate {
+        println!("{}", statusline);
+        return Ok(());
     }
 
-    // Need to truncate
-    let mut result = String::new();
-    let mut width = 0;
-    let mut in_escape = false;
-
-    for ch in text.chars() {
-        if ch == '\x1b' {
-            in_escape = true;
-            result.push(ch);
-        } else if in_escape {
-            result.push(ch);
-            if ch.is_alphabetic() {
-                in_escape = false;
-            }
-        } else {
-            let char_width = if ch > '\u{FF}' { 2 } else { 1 };
-            if width + char_width > max_width.saturating_sub(3) {
-                result.push_str("...\x1b[0m");
-                break;
-            }
-            result.push(ch);
-            width += char_width;
-        }
+    // Each line of a multi-line statusline is fitted on its own
+    for line in statusline.split('\n') {
+        println!(
+            "{}",
+            truncate_to_terminal_width(line, truncate_percent, reserved_width, &marker)
+        );
     }
 
-    result
+    Ok(())
 }
@@ -24,6 +24,14 @@ use ratatui::{
 };
 use std::io;
 
+/// What the text input popup is collecting
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NameInputAction {
+    SaveAs,
+    Export,
+    Import,
+}
+
 pub struct App {
     config: Config,
     selected_segment: usize,
@@ -33,6 +41,7 @@ pub struct App {
     color_picker: ColorPickerComponent,
     icon_selector: IconSelectorComponent,
     name_input: NameInputComponent,
+    name_input_action: NameInputAction,
     preview: PreviewComponent,
     segment_list: SegmentListComponent,
     separator_editor: SeparatorEditorComponent,
@@ -53,6 +62,7 @@ impl App {
             color_picker: ColorPickerComponent::new(),
             icon_selector: IconSelectorComponent::new(),
             name_input: NameInputComponent::new(),
+            name_input_action: NameInputAction::SaveAs,
             preview: PreviewComponent::new(),
             segment_list: SegmentListComponent::new(),
             separator_editor: SeparatorEditorComponent::new(),
@@ -107,8 +117,12 @@ impl App {
                     match key.code {
                         KeyCode::Esc => app.name_input.close(),
                         KeyCode::Enter => {
-                            if let Some(name) = app.name_input.get_input() {
-                                app.save_as_new_theme(&name);
+                            if let Some(input) = app.name_input.get_input() {
+                                match app.name_input_action {
+                                    NameInputAction::SaveAs => app.save_as_new_theme(&input),
+                                    NameInputAction::Export => app.export_theme(&input),
+                                    NameInputAction::Import => app.import_theme(&input),
+                                }
                             }
                             app.name_input.close();
                         }
@@ -190,6 +204,7 @@ impl App {
                         KeyCode::Char('s') => {
                             if key.modifiers.contains(KeyModifiers::CONTROL) {
                                 // Ctrl+S: Save as new theme with name input
+                                app.name_input_action = NameInputAction::SaveAs;
                                 app.name_input.open("Save as New Theme", "Enter theme name");
                             } else {
                                 // s: Save config to config.toml
@@ -229,6 +244,16 @@ impl App {
                         KeyCode::Char('p') => app.cycle_theme(),
                         KeyCode::Char('r') => app.reset_to_theme_defaults(),
                         KeyCode::Char('e') | KeyCode::Char('E') => app.open_separator_editor(),
+                        KeyCode::Char('x') | KeyCode::Char('X') => {
+                            app.name_input_action = NameInputAction::Export;
+                            app.name_input
+                                .open_path("Export Theme", "Path to .toml or .json file");
+                        }
+                        KeyCode::Char('i') | KeyCode::Char('I') => {
+                            app.name_input_action = NameInputAction::Import;
+                            app.name_input
+                                .open_path("Import Theme", "Path to .toml or .json file");
+                        }
                         _ => {}
                     }
                 }
@@ -506,6 +531,54 @@ impl App {
                         SegmentId::Session => "Session",
                         SegmentId::OutputStyle => "Output Style",
                         SegmentId::Update => "Update",
+                        SegmentId::Battery => "Battery",
+                        SegmentId::Clock => "Clock",
+                        SegmentId::SessionDuration => "Session Duration",
+                        SegmentId::Hostname => "Hostname",
+                        SegmentId::User => "User",
+                        SegmentId::PythonEnv => "Python Env",
+                        SegmentId::Conda => "Conda",
+                        SegmentId::Node => "Node",
+                        SegmentId::Rust => "Rust",
+                        SegmentId::Go => "Go",
+                        SegmentId::Java => "Java",
+                        SegmentId::Dotnet => ".NET",
+                        SegmentId::Kubernetes => "Kubernetes",
+                        SegmentId::Docker => "Docker",
+                        SegmentId::Terraform => "Terraform",
+                        SegmentId::Aws => "AWS",
+                        SegmentId::Custom => "Custom Text",
+                        SegmentId::Gcloud => "GCloud",
+                        SegmentId::Load => "Load",
+                        SegmentId::Disk => "Disk",
+                        SegmentId::Version => "Version",
+                        SegmentId::LinesChanged => "Lines Changed",
+                        SegmentId::MessageCount => "Message Count",
+                        SegmentId::Todo => "Todo",
+                        SegmentId::Mcp => "MCP Servers",
+                        SegmentId::BurnRate => "Burn Rate",
+                        SegmentId::ApiDuration => "API Duration",
+                        SegmentId::Command => "Custom Command",
+                        SegmentId::Runtime => "Runtime",
+                        SegmentId::Env => "Environment Variable",
+                        SegmentId::Package => "Package",
+                        SegmentId::Nix => "Nix Shell",
+                        SegmentId::Direnv => "Direnv",
+                        SegmentId::Ssh => "SSH",
+                        SegmentId::Environment => "Environment",
+                        SegmentId::Shell => "Shell",
+                        SegmentId::GithubPr => "GitHub PR",
+                        SegmentId::CiStatus => "CI Status",
+                        SegmentId::Jujutsu => "Jujutsu",
+                        SegmentId::Hg => "Mercurial",
+                        SegmentId::Connectivity => "Connectivity",
+                        SegmentId::GitDiff => "Git Diff",
+                        SegmentId::SessionId => "Session ID",
+                        SegmentId::LastPrompt => "Last Prompt",
+                        SegmentId::Budget => "Budget",
+                        SegmentId::DailyCost => "Daily Cost",
+                        SegmentId::LastCommit => "Last Commit",
+                        SegmentId::GitIdentity => "Git Identity",
                     };
                     let is_enabled = segment.enabled;
                     self.status_message = Some(format!(
@@ -533,6 +606,54 @@ impl App {
                                 SegmentId::Session => "Session",
                                 SegmentId::OutputStyle => "Output Style",
                                 SegmentId::Update => "Update",
+                                SegmentId::Battery => "Battery",
+                                SegmentId::Clock => "Clock",
+                                SegmentId::SessionDuration => "Session Duration",
+                                SegmentId::Hostname => "Hostname",
+                                SegmentId::User => "User",
+                                SegmentId::PythonEnv => "Python Env",
+                                SegmentId::Conda => "Conda",
+                                SegmentId::Node => "Node",
+                                SegmentId::Rust => "Rust",
+                                SegmentId::Go => "Go",
+                                SegmentId::Java => "Java",
+                                SegmentId::Dotnet => ".NET",
+                                SegmentId::Kubernetes => "Kubernetes",
+                                SegmentId::Docker => "Docker",
+                                SegmentId::Terraform => "Terraform",
+                                SegmentId::Aws => "AWS",
+                                SegmentId::Custom => "Custom Text",
+                                SegmentId::Gcloud => "GCloud",
+                                SegmentId::Load => "Load",
+                                SegmentId::Disk => "Disk",
+                                SegmentId::Version => "Version",
+                                SegmentId::LinesChanged => "Lines Changed",
+                                SegmentId::MessageCount => "Message Count",
+                                SegmentId::Todo => "Todo",
+                                SegmentId::Mcp => "MCP Servers",
+                                SegmentId::BurnRate => "Burn Rate",
+                                SegmentId::ApiDuration => "API Duration",
+                                SegmentId::Command => "Custom Command",
+                                SegmentId::Runtime => "Runtime",
+                                SegmentId::Env => "Environment Variable",
+                                SegmentId::Package => "Package",
+                                SegmentId::Nix => "Nix Shell",
+                                SegmentId::Direnv => "Direnv",
+                                SegmentId::Ssh => "SSH",
+                                SegmentId::Environment => "Environment",
+                                SegmentId::Shell => "Shell",
+                                SegmentId::GithubPr => "GitHub PR",
+                                SegmentId::CiStatus => "CI Status",
+                                SegmentId::Jujutsu => "Jujutsu",
+                                SegmentId::Hg => "Mercurial",
+                                SegmentId::Connectivity => "Connectivity",
+                                SegmentId::GitDiff => "Git Diff",
+                                SegmentId::SessionId => "Session ID",
+                                SegmentId::LastPrompt => "Last Prompt",
+                                SegmentId::Budget => "Budget",
+                                SegmentId::DailyCost => "Daily Cost",
+                                SegmentId::LastCommit => "Last Commit",
+                                SegmentId::GitIdentity => "Git Identity",
                             };
                             let is_enabled = segment.enabled;
                             self.status_message = Some(format!(
@@ -701,6 +822,42 @@ impl App {
         }
     }
 
+    /// Export the theme being edited so it can be shared
+    fn export_theme(&mut self, path: &str) {
+        let path = Self::expand_home(path);
+        match crate::ui::themes::ThemePresets::export_theme(&self.config, &path) {
+            Ok(_) => {
+                self.status_message = Some(format!("Exported theme to {}", path.display()));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to export theme: {}", e));
+            }
+        }
+    }
+
+    /// Install a theme file and switch the editor to it
+    fn import_theme(&mut self, path: &str) {
+        let path = Self::expand_home(path);
+        match crate::ui::themes::ThemePresets::import_theme(&path, false) {
+            Ok(config) => {
+                self.status_message = Some(format!("Imported theme: {}", config.theme));
+                self.config = config;
+                self.selected_segment = 0;
+                self.preview.update_preview(&self.config);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to import theme: {}", e));
+            }
+        }
+    }
+
+    fn expand_home(path: &str) -> std::path::PathBuf {
+        match (path.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => std::path::PathBuf::from(path),
+        }
+    }
+
     /// Open separator editor with current separator
     fn open_separator_editor(&mut self) {
         self.status_message = Some("Opening separator editor...".to_string());
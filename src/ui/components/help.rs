@@ -49,6 +49,8 @@ impl HelpComponent {
                 ("[S]", "Save Config"),
                 ("[W]", "Write Theme"),
                 ("[Ctrl+S]", "Save Theme"),
+                ("[X]", "Export Theme"),
+                ("[I]", "Import Theme"),
                 ("[Esc]", "Quit"),
             ]
         };
@@ -11,6 +11,8 @@ pub struct NameInputComponent {
     pub input: String,
     pub title: String,
     pub placeholder: String,
+    /// Accept file paths rather than bare names
+    pub path_mode: bool,
 }
 
 impl Default for NameInputComponent {
@@ -26,6 +28,7 @@ impl NameInputComponent {
             input: String::new(),
             title: "Input Name".to_string(),
             placeholder: "Enter name...".to_string(),
+            path_mode: false,
         }
     }
 
@@ -34,6 +37,12 @@ impl NameInputComponent {
         self.input.clear();
         self.title = title.to_string();
         self.placeholder = placeholder.to_string();
+        self.path_mode = false;
+    }
+
+    pub fn open_path(&mut self, title: &str, placeholder: &str) {
+        self.open(title, placeholder);
+        self.path_mode = true;
     }
 
     pub fn close(&mut self) {
@@ -42,7 +51,8 @@ impl NameInputComponent {
     }
 
     pub fn input_char(&mut self, c: char) {
-        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+        if (self.path_mode && !c.is_control()) || c.is_ascii_alphanumeric() || c == '_' || c == '-'
+        {
             self.input.push(c);
         }
     }
@@ -114,7 +124,11 @@ impl NameInputComponent {
                 } else {
                     Style::default().fg(Color::Yellow)
                 })
-                .block(Block::default().borders(Borders::ALL).title("Name")),
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(if self.path_mode { "Path" } else { "Name" }),
+                ),
             chunks[0],
         );
 
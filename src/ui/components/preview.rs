@@ -33,6 +33,9 @@ impl PreviewComponent {
     }
 
     pub fn update_preview_with_width(&mut self, config: &Config, width: u16) {
+        // Render with ${VAR} expanded while the edited config keeps the references
+        let config = &config.clone().with_env_expanded();
+
         // Generate mock segments data directly for preview
         let segments_data = self.generate_mock_segments_data(config);
 
@@ -89,7 +92,7 @@ impl PreviewComponent {
     ) -> Vec<(crate::config::SegmentConfig, SegmentData)> {
         let mut segments_data = Vec::new();
 
-        for segment_config in &config.segments {
+        for segment_config in config.ordered_segments() {
             if !segment_config.enabled {
                 continue;
             }
@@ -126,13 +129,13 @@ impl PreviewComponent {
                     },
                 },
                 SegmentId::ContextWindow => SegmentData {
-                    primary: "78.2%".to_string(),
-                    secondary: "· 156.4k".to_string(),
+                    primary: "78.2% (156.4k/200k)".to_string(),
+                    secondary: String::new(),
                     metadata: {
                         let mut map = HashMap::new();
-                        map.insert("total_tokens".to_string(), "156400".to_string());
-                        map.insert("percentage".to_string(), "78.2".to_string());
-                        map.insert("session_tokens".to_string(), "48200".to_string());
+                        map.insert("used_tokens".to_string(), "156400".to_string());
+                        map.insert("max_tokens".to_string(), "200000".to_string());
+                        map.insert("percent".to_string(), "78.2".to_string());
                         map
                     },
                 },
@@ -183,6 +186,337 @@ impl PreviewComponent {
                         map
                     },
                 },
+                SegmentId::Battery => SegmentData {
+                    primary: "82%".to_string(),
+                    secondary: "⚡".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("percentage".to_string(), "82".to_string());
+                        map.insert("state".to_string(), "Charging".to_string());
+                        map
+                    },
+                },
+                SegmentId::Clock => SegmentData {
+                    primary: "14:32".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("format".to_string(), "%H:%M".to_string());
+                        map
+                    },
+                },
+                SegmentId::SessionDuration => SegmentData {
+                    primary: "1h 23m".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("elapsed_seconds".to_string(), "4980".to_string());
+                        map
+                    },
+                },
+                SegmentId::Hostname => SegmentData {
+                    primary: "devbox".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("hostname".to_string(), "devbox.example.com".to_string());
+                        map
+                    },
+                },
+                SegmentId::User => SegmentData {
+                    primary: "root".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("user".to_string(), "root".to_string());
+                        map.insert("level".to_string(), "warning".to_string());
+                        map
+                    },
+                },
+                SegmentId::PythonEnv => SegmentData {
+                    primary: "myproject".to_string(),
+                    secondary: "3.12.4".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("python_version".to_string(), "3.12.4".to_string());
+                        map
+                    },
+                },
+                SegmentId::Conda => SegmentData {
+                    primary: "datasci".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert(
+                            "env_path".to_string(),
+                            "/opt/conda/envs/datasci".to_string(),
+                        );
+                        map
+                    },
+                },
+                SegmentId::Node => SegmentData {
+                    primary: "20.11.1".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("source".to_string(), ".nvmrc".to_string());
+                        map
+                    },
+                },
+                SegmentId::Rust => SegmentData {
+                    primary: "1.79.0".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("channel".to_string(), "stable".to_string());
+                        map
+                    },
+                },
+                SegmentId::Go => SegmentData {
+                    primary: "1.22".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("required".to_string(), "1.22".to_string());
+                        map
+                    },
+                },
+                SegmentId::Java => SegmentData {
+                    primary: "21.0.2".to_string(),
+                    secondary: "gradle".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("build_tool".to_string(), "gradle".to_string());
+                        map
+                    },
+                },
+                SegmentId::Dotnet => SegmentData {
+                    primary: ".NET 8.0.204".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("project_type".to_string(), "solution".to_string());
+                        map
+                    },
+                },
+                SegmentId::Kubernetes => SegmentData {
+                    primary: "staging".to_string(),
+                    secondary: "web".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("namespace".to_string(), "web".to_string());
+                        map
+                    },
+                },
+                SegmentId::Docker => SegmentData {
+                    primary: "colima".to_string(),
+                    secondary: "📦".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("context".to_string(), "colima".to_string());
+                        map
+                    },
+                },
+                SegmentId::Terraform => SegmentData {
+                    primary: "staging".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("workspace".to_string(), "staging".to_string());
+                        map
+                    },
+                },
+                SegmentId::Aws => SegmentData {
+                    primary: "dev".to_string(),
+                    secondary: "us-east-1".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("region".to_string(), "us-east-1".to_string());
+                        map
+                    },
+                },
+                SegmentId::Custom => SegmentData {
+                    primary: "workstation".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Gcloud => SegmentData {
+                    primary: "my-project".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("account".to_string(), "dev@example.com".to_string());
+                        map
+                    },
+                },
+                SegmentId::Load => SegmentData {
+                    primary: "32%".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Disk => SegmentData {
+                    primary: "4.2G".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Version => SegmentData {
+                    primary: "1.0.83".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::LinesChanged => SegmentData {
+                    primary: "+123".to_string(),
+                    secondary: "-45".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::MessageCount => SegmentData {
+                    primary: "42".to_string(),
+                    secondary: String::new(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Todo => SegmentData {
+                    primary: "3/7".to_string(),
+                    secondary: "Running tests".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Mcp => SegmentData {
+                    primary: "3".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert(
+                            "servers".to_string(),
+                            "context7, github, playwright".to_string(),
+                        );
+                        map
+                    },
+                },
+                SegmentId::BurnRate => SegmentData {
+                    primary: "2.4k/min".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::ApiDuration => SegmentData {
+                    primary: "4m 12s".to_string(),
+                    secondary: "+8.3s".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Command => SegmentData {
+                    primary: "PROJ-123".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Runtime => SegmentData {
+                    primary: "20.11".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("runtime".to_string(), "node".to_string());
+                        map.insert("dynamic_icon".to_string(), "\u{e718}".to_string());
+                        map
+                    },
+                },
+                SegmentId::Env => SegmentData {
+                    primary: "production".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Package => SegmentData {
+                    primary: "v1.4.2".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Nix => SegmentData {
+                    primary: "nix-shell".to_string(),
+                    secondary: "impure".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Direnv => SegmentData {
+                    primary: "loaded".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Ssh => SegmentData {
+                    primary: "me@devbox".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Environment => SegmentData {
+                    primary: "WSL".to_string(),
+                    secondary: "Ubuntu".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Shell => SegmentData {
+                    primary: "zsh".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::GithubPr => SegmentData {
+                    primary: "#123 ✓".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::CiStatus => SegmentData {
+                    primary: "passed".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Jujutsu => SegmentData {
+                    primary: "kxqpmzrw".to_string(),
+                    secondary: "main".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Hg => SegmentData {
+                    primary: "default".to_string(),
+                    secondary: "✓".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Connectivity => SegmentData {
+                    primary: "offline".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::GitDiff => SegmentData {
+                    primary: "+42".to_string(),
+                    secondary: "-7".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::SessionId => SegmentData {
+                    primary: "3f2a9c1e".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::LastPrompt => SegmentData {
+                    primary: "Fix the flaky login test…".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::Budget => SegmentData {
+                    primary: "$3.42/$10".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::DailyCost => SegmentData {
+                    primary: "$7.81".to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut metadata = HashMap::new();
+                        metadata.insert("sessions".to_string(), "2".to_string());
+                        metadata.insert("session.3f2a9c1e".to_string(), "4.6000".to_string());
+                        metadata.insert("session.b71d04aa".to_string(), "3.2100".to_string());
+                        metadata
+                    },
+                },
+                SegmentId::LastCommit => SegmentData {
+                    primary: "2h".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
+                SegmentId::GitIdentity => SegmentData {
+                    primary: "WORK".to_string(),
+                    secondary: "".to_string(),
+                    metadata: HashMap::new(),
+                },
             };
 
             segments_data.push((segment_config.clone(), mock_data));
@@ -36,6 +36,54 @@ impl SettingsComponent {
                 SegmentId::Session => "Session",
                 SegmentId::OutputStyle => "Output Style",
                 SegmentId::Update => "Update",
+                SegmentId::Battery => "Battery",
+                SegmentId::Clock => "Clock",
+                SegmentId::SessionDuration => "Session Duration",
+                SegmentId::Hostname => "Hostname",
+                SegmentId::User => "User",
+                SegmentId::PythonEnv => "Python Env",
+                SegmentId::Conda => "Conda",
+                SegmentId::Node => "Node",
+                SegmentId::Rust => "Rust",
+                SegmentId::Go => "Go",
+                SegmentId::Java => "Java",
+                SegmentId::Dotnet => ".NET",
+                SegmentId::Kubernetes => "Kubernetes",
+                SegmentId::Docker => "Docker",
+                SegmentId::Terraform => "Terraform",
+                SegmentId::Aws => "AWS",
+                SegmentId::Custom => "Custom Text",
+                SegmentId::Gcloud => "GCloud",
+                SegmentId::Load => "Load",
+                SegmentId::Disk => "Disk",
+                SegmentId::Version => "Version",
+                SegmentId::LinesChanged => "Lines Changed",
+                SegmentId::MessageCount => "Message Count",
+                SegmentId::Todo => "Todo",
+                SegmentId::Mcp => "MCP Servers",
+                SegmentId::BurnRate => "Burn Rate",
+                SegmentId::ApiDuration => "API Duration",
+                SegmentId::Command => "Custom Command",
+                SegmentId::Runtime => "Runtime",
+                SegmentId::Env => "Environment Variable",
+                SegmentId::Package => "Package",
+                SegmentId::Nix => "Nix Shell",
+                SegmentId::Direnv => "Direnv",
+                SegmentId::Ssh => "SSH",
+                SegmentId::Environment => "Environment",
+                SegmentId::Shell => "Shell",
+                SegmentId::GithubPr => "GitHub PR",
+                SegmentId::CiStatus => "CI Status",
+                SegmentId::Jujutsu => "Jujutsu",
+                SegmentId::Hg => "Mercurial",
+                SegmentId::Connectivity => "Connectivity",
+                SegmentId::GitDiff => "Git Diff",
+                SegmentId::SessionId => "Session ID",
+                SegmentId::LastPrompt => "Last Prompt",
+                SegmentId::Budget => "Budget",
+                SegmentId::DailyCost => "Daily Cost",
+                SegmentId::LastCommit => "Last Commit",
+                SegmentId::GitIdentity => "Git Identity",
             };
             let current_icon = match config.style.mode {
                 StyleMode::Plain => &segment.icon.plain,
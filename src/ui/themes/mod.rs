@@ -1,4 +1,5 @@
 pub mod presets;
+pub mod theme_ascii;
 pub mod theme_cometix;
 pub mod theme_default;
 pub mod theme_gruvbox;
@@ -1,11 +1,15 @@
 // Theme presets for TUI configuration
 
-use crate::config::{Config, StyleConfig, StyleMode};
+use crate::config::{
+    default_reserved_width, default_truncate_marker, default_truncate_percent, Config, StyleConfig,
+    StyleMode,
+};
 
 // Import all theme modules
 use super::{
-    theme_cometix, theme_default, theme_gruvbox, theme_minimal, theme_nord, theme_powerline_dark,
-    theme_powerline_light, theme_powerline_rose_pine, theme_powerline_tokyo_night,
+    theme_ascii, theme_cometix, theme_default, theme_gruvbox, theme_minimal, theme_nord,
+    theme_powerline_dark, theme_powerline_light, theme_powerline_rose_pine,
+    theme_powerline_tokyo_night,
 };
 
 pub struct ThemePresets;
@@ -19,6 +23,7 @@ impl ThemePresets {
 
         // Fallback to built-in themes
         match theme_name {
+            "ascii" => Self::get_ascii(),
             "cometix" => Self::get_cometix(),
             "default" => Self::get_default(),
             "gruvbox" => Self::get_gruvbox(),
@@ -32,24 +37,105 @@ impl ThemePresets {
         }
     }
 
+    /// Theme chosen by the first of `config.theme_rules` matching the local time and
+    /// terminal, or `None` to keep `config` as loaded
+    pub fn resolve_theme(config: &Config) -> Option<Config> {
+        let now = chrono::Local::now().time();
+        let rule = config.theme_rules.iter().find(|rule| rule.matches(now))?;
+        // The loaded config already is that theme, possibly with local edits worth keeping
+        if rule.theme == config.theme {
+            return None;
+        }
+        Some(Self::get_theme(&rule.theme))
+    }
+
     /// Load theme from file system
     pub fn load_theme_from_file(theme_name: &str) -> Result<Config, Box<dyn std::error::Error>> {
-        let themes_dir = Self::get_themes_path();
-        let theme_path = themes_dir.join(format!("{}.toml", theme_name));
+        // A name with a path separator or .toml/.json extension refers to a file directly
+        let direct_path = std::path::Path::new(theme_name);
+        let is_path = theme_name.contains(std::path::MAIN_SEPARATOR)
+            || theme_name.contains('/')
+            || direct_path
+                .extension()
+                .is_some_and(|ext| ext == "toml" || ext == "json");
+
+        let theme_path = if is_path {
+            direct_path.to_path_buf()
+        } else {
+            Self::get_themes_path().join(format!("{}.toml", theme_name))
+        };
 
         if !theme_path.exists() {
             return Err(format!("Theme file not found: {}", theme_path.display()).into());
         }
 
         let content = std::fs::read_to_string(&theme_path)?;
-        let mut config: Config = toml::from_str(&content)?;
+        let mut config: Config = if Self::is_json_path(&theme_path) {
+            serde_json::from_str(&content)
+                .map_err(|e| format!("{}: {}", theme_path.display(), e))?
+        } else {
+            toml::from_str(&content)
+                .map_err(|e| format!("{}: {}", theme_path.display(), e.message()))?
+        };
 
         // Ensure the theme field matches the requested theme
-        config.theme = theme_name.to_string();
+        config.theme = if is_path {
+            direct_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| theme_name.to_string())
+        } else {
+            theme_name.to_string()
+        };
 
         Ok(config)
     }
 
+    fn is_json_path(path: &std::path::Path) -> bool {
+        path.extension().is_some_and(|ext| ext == "json")
+    }
+
+    /// Write a theme to `path` for sharing, as JSON for a .json extension and TOML otherwise
+    pub fn export_theme(
+        config: &Config,
+        path: &std::path::Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let content = if Self::is_json_path(path) {
+            serde_json::to_string_pretty(config)?
+        } else {
+            toml::to_string_pretty(config)?
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    /// Read an exported theme file and install it into the themes directory under its file name.
+    /// A file named after a built-in theme would shadow it, so that needs `force`
+    pub fn import_theme(
+        path: &std::path::Path,
+        force: bool,
+    ) -> Result<Config, Box<dyn std::error::Error>> {
+        let config = Self::load_theme_from_file(&path.to_string_lossy())?;
+        let built_in = Self::get_available_themes()
+            .iter()
+            .any(|(name, _)| *name == config.theme);
+        if built_in && !force {
+            return Err(format!(
+                "'{}' is a built-in theme name; rename the file or use --force to replace it",
+                config.theme
+            )
+            .into());
+        }
+        config.check()?;
+        Self::save_theme(&config.theme, &config)?;
+        Ok(config)
+    }
+
     /// Get the themes directory path (~/.claude/ccline/themes/)
     fn get_themes_path() -> std::path::PathBuf {
         if let Some(home) = dirs::home_dir() {
@@ -89,6 +175,7 @@ impl ThemePresets {
             "powerline-light".to_string(),
             "powerline-rose-pine".to_string(),
             "powerline-tokyo-night".to_string(),
+            "ascii".to_string(),
         ];
 
         // Add custom themes from file system
@@ -119,14 +206,43 @@ impl ThemePresets {
             ("powerline-light", "Light powerline theme"),
             ("powerline-rose-pine", "Rose Pine powerline theme"),
             ("powerline-tokyo-night", "Tokyo Night powerline theme"),
+            ("ascii", "No colors, icons or Nerd Font glyphs"),
         ]
     }
 
+    /// The default theme's segments without colors, icons or glyphs, joined by a plain " | "
+    pub fn get_ascii() -> Config {
+        let default = Self::get_default();
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::Plain,
+                separator: " | ".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: false,
+                color: false,
+                left_pad: 0,
+                right_pad: 0,
+            },
+            segments: default
+                .segments
+                .into_iter()
+                .map(theme_ascii::ascii_segment)
+                .collect(),
+            theme: "ascii".to_string(),
+            ..default
+        }
+    }
+
     pub fn get_cometix() -> Config {
         Config {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_cometix::model_segment(),
@@ -137,8 +253,62 @@ impl ThemePresets {
                 theme_cometix::cost_segment(),
                 theme_cometix::session_segment(),
                 theme_cometix::output_style_segment(),
+                theme_cometix::battery_segment(),
+                theme_cometix::clock_segment(),
+                theme_cometix::session_duration_segment(),
+                theme_cometix::hostname_segment(),
+                theme_cometix::user_segment(),
+                theme_cometix::python_env_segment(),
+                theme_cometix::conda_segment(),
+                theme_cometix::node_segment(),
+                theme_cometix::rust_segment(),
+                theme_cometix::go_segment(),
+                theme_cometix::java_segment(),
+                theme_cometix::dotnet_segment(),
+                theme_cometix::kubernetes_segment(),
+                theme_cometix::docker_segment(),
+                theme_cometix::terraform_segment(),
+                theme_cometix::aws_segment(),
+                theme_cometix::custom_segment(),
+                theme_cometix::gcloud_segment(),
+                theme_cometix::load_segment(),
+                theme_cometix::disk_segment(),
+                theme_cometix::version_segment(),
+                theme_cometix::lines_changed_segment(),
+                theme_cometix::message_count_segment(),
+                theme_cometix::todo_segment(),
+                theme_cometix::mcp_segment(),
+                theme_cometix::burn_rate_segment(),
+                theme_cometix::api_duration_segment(),
+                theme_cometix::command_segment(),
+                theme_cometix::runtime_segment(),
+                theme_cometix::env_segment(),
+                theme_cometix::package_segment(),
+                theme_cometix::nix_segment(),
+                theme_cometix::direnv_segment(),
+                theme_cometix::ssh_segment(),
+                theme_cometix::environment_segment(),
+                theme_cometix::shell_segment(),
+                theme_cometix::github_pr_segment(),
+                theme_cometix::ci_status_segment(),
+                theme_cometix::jujutsu_segment(),
+                theme_cometix::hg_segment(),
+                theme_cometix::connectivity_segment(),
+                theme_cometix::git_diff_segment(),
+                theme_cometix::session_id_segment(),
+                theme_cometix::last_prompt_segment(),
+                theme_cometix::budget_segment(),
+                theme_cometix::daily_cost_segment(),
+                theme_cometix::last_commit_segment(),
+                theme_cometix::git_identity_segment(),
             ],
             theme: "cometix".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -147,6 +317,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " | ".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_default::model_segment(),
@@ -157,8 +332,62 @@ impl ThemePresets {
                 theme_default::cost_segment(),
                 theme_default::session_segment(),
                 theme_default::output_style_segment(),
+                theme_default::battery_segment(),
+                theme_default::clock_segment(),
+                theme_default::session_duration_segment(),
+                theme_default::hostname_segment(),
+                theme_default::user_segment(),
+                theme_default::python_env_segment(),
+                theme_default::conda_segment(),
+                theme_default::node_segment(),
+                theme_default::rust_segment(),
+                theme_default::go_segment(),
+                theme_default::java_segment(),
+                theme_default::dotnet_segment(),
+                theme_default::kubernetes_segment(),
+                theme_default::docker_segment(),
+                theme_default::terraform_segment(),
+                theme_default::aws_segment(),
+                theme_default::custom_segment(),
+                theme_default::gcloud_segment(),
+                theme_default::load_segment(),
+                theme_default::disk_segment(),
+                theme_default::version_segment(),
+                theme_default::lines_changed_segment(),
+                theme_default::message_count_segment(),
+                theme_default::todo_segment(),
+                theme_default::mcp_segment(),
+                theme_default::burn_rate_segment(),
+                theme_default::api_duration_segment(),
+                theme_default::command_segment(),
+                theme_default::runtime_segment(),
+                theme_default::env_segment(),
+                theme_default::package_segment(),
+                theme_default::nix_segment(),
+                theme_default::direnv_segment(),
+                theme_default::ssh_segment(),
+                theme_default::environment_segment(),
+                theme_default::shell_segment(),
+                theme_default::github_pr_segment(),
+                theme_default::ci_status_segment(),
+                theme_default::jujutsu_segment(),
+                theme_default::hg_segment(),
+                theme_default::connectivity_segment(),
+                theme_default::git_diff_segment(),
+                theme_default::session_id_segment(),
+                theme_default::last_prompt_segment(),
+                theme_default::budget_segment(),
+                theme_default::daily_cost_segment(),
+                theme_default::last_commit_segment(),
+                theme_default::git_identity_segment(),
             ],
             theme: "default".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -167,6 +396,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " │ ".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_minimal::model_segment(),
@@ -177,8 +411,62 @@ impl ThemePresets {
                 theme_minimal::cost_segment(),
                 theme_minimal::session_segment(),
                 theme_minimal::output_style_segment(),
+                theme_minimal::battery_segment(),
+                theme_minimal::clock_segment(),
+                theme_minimal::session_duration_segment(),
+                theme_minimal::hostname_segment(),
+                theme_minimal::user_segment(),
+                theme_minimal::python_env_segment(),
+                theme_minimal::conda_segment(),
+                theme_minimal::node_segment(),
+                theme_minimal::rust_segment(),
+                theme_minimal::go_segment(),
+                theme_minimal::java_segment(),
+                theme_minimal::dotnet_segment(),
+                theme_minimal::kubernetes_segment(),
+                theme_minimal::docker_segment(),
+                theme_minimal::terraform_segment(),
+                theme_minimal::aws_segment(),
+                theme_minimal::custom_segment(),
+                theme_minimal::gcloud_segment(),
+                theme_minimal::load_segment(),
+                theme_minimal::disk_segment(),
+                theme_minimal::version_segment(),
+                theme_minimal::lines_changed_segment(),
+                theme_minimal::message_count_segment(),
+                theme_minimal::todo_segment(),
+                theme_minimal::mcp_segment(),
+                theme_minimal::burn_rate_segment(),
+                theme_minimal::api_duration_segment(),
+                theme_minimal::command_segment(),
+                theme_minimal::runtime_segment(),
+                theme_minimal::env_segment(),
+                theme_minimal::package_segment(),
+                theme_minimal::nix_segment(),
+                theme_minimal::direnv_segment(),
+                theme_minimal::ssh_segment(),
+                theme_minimal::environment_segment(),
+                theme_minimal::shell_segment(),
+                theme_minimal::github_pr_segment(),
+                theme_minimal::ci_status_segment(),
+                theme_minimal::jujutsu_segment(),
+                theme_minimal::hg_segment(),
+                theme_minimal::connectivity_segment(),
+                theme_minimal::git_diff_segment(),
+                theme_minimal::session_id_segment(),
+                theme_minimal::last_prompt_segment(),
+                theme_minimal::budget_segment(),
+                theme_minimal::daily_cost_segment(),
+                theme_minimal::last_commit_segment(),
+                theme_minimal::git_identity_segment(),
             ],
             theme: "minimal".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -187,6 +475,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_gruvbox::model_segment(),
@@ -197,8 +490,62 @@ impl ThemePresets {
                 theme_gruvbox::cost_segment(),
                 theme_gruvbox::session_segment(),
                 theme_gruvbox::output_style_segment(),
+                theme_gruvbox::battery_segment(),
+                theme_gruvbox::clock_segment(),
+                theme_gruvbox::session_duration_segment(),
+                theme_gruvbox::hostname_segment(),
+                theme_gruvbox::user_segment(),
+                theme_gruvbox::python_env_segment(),
+                theme_gruvbox::conda_segment(),
+                theme_gruvbox::node_segment(),
+                theme_gruvbox::rust_segment(),
+                theme_gruvbox::go_segment(),
+                theme_gruvbox::java_segment(),
+                theme_gruvbox::dotnet_segment(),
+                theme_gruvbox::kubernetes_segment(),
+                theme_gruvbox::docker_segment(),
+                theme_gruvbox::terraform_segment(),
+                theme_gruvbox::aws_segment(),
+                theme_gruvbox::custom_segment(),
+                theme_gruvbox::gcloud_segment(),
+                theme_gruvbox::load_segment(),
+                theme_gruvbox::disk_segment(),
+                theme_gruvbox::version_segment(),
+                theme_gruvbox::lines_changed_segment(),
+                theme_gruvbox::message_count_segment(),
+                theme_gruvbox::todo_segment(),
+                theme_gruvbox::mcp_segment(),
+                theme_gruvbox::burn_rate_segment(),
+                theme_gruvbox::api_duration_segment(),
+                theme_gruvbox::command_segment(),
+                theme_gruvbox::runtime_segment(),
+                theme_gruvbox::env_segment(),
+                theme_gruvbox::package_segment(),
+                theme_gruvbox::nix_segment(),
+                theme_gruvbox::direnv_segment(),
+                theme_gruvbox::ssh_segment(),
+                theme_gruvbox::environment_segment(),
+                theme_gruvbox::shell_segment(),
+                theme_gruvbox::github_pr_segment(),
+                theme_gruvbox::ci_status_segment(),
+                theme_gruvbox::jujutsu_segment(),
+                theme_gruvbox::hg_segment(),
+                theme_gruvbox::connectivity_segment(),
+                theme_gruvbox::git_diff_segment(),
+                theme_gruvbox::session_id_segment(),
+                theme_gruvbox::last_prompt_segment(),
+                theme_gruvbox::budget_segment(),
+                theme_gruvbox::daily_cost_segment(),
+                theme_gruvbox::last_commit_segment(),
+                theme_gruvbox::git_identity_segment(),
             ],
             theme: "gruvbox".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -207,6 +554,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_nord::model_segment(),
@@ -217,8 +569,62 @@ impl ThemePresets {
                 theme_nord::cost_segment(),
                 theme_nord::session_segment(),
                 theme_nord::output_style_segment(),
+                theme_nord::battery_segment(),
+                theme_nord::clock_segment(),
+                theme_nord::session_duration_segment(),
+                theme_nord::hostname_segment(),
+                theme_nord::user_segment(),
+                theme_nord::python_env_segment(),
+                theme_nord::conda_segment(),
+                theme_nord::node_segment(),
+                theme_nord::rust_segment(),
+                theme_nord::go_segment(),
+                theme_nord::java_segment(),
+                theme_nord::dotnet_segment(),
+                theme_nord::kubernetes_segment(),
+                theme_nord::docker_segment(),
+                theme_nord::terraform_segment(),
+                theme_nord::aws_segment(),
+                theme_nord::custom_segment(),
+                theme_nord::gcloud_segment(),
+                theme_nord::load_segment(),
+                theme_nord::disk_segment(),
+                theme_nord::version_segment(),
+                theme_nord::lines_changed_segment(),
+                theme_nord::message_count_segment(),
+                theme_nord::todo_segment(),
+                theme_nord::mcp_segment(),
+                theme_nord::burn_rate_segment(),
+                theme_nord::api_duration_segment(),
+                theme_nord::command_segment(),
+                theme_nord::runtime_segment(),
+                theme_nord::env_segment(),
+                theme_nord::package_segment(),
+                theme_nord::nix_segment(),
+                theme_nord::direnv_segment(),
+                theme_nord::ssh_segment(),
+                theme_nord::environment_segment(),
+                theme_nord::shell_segment(),
+                theme_nord::github_pr_segment(),
+                theme_nord::ci_status_segment(),
+                theme_nord::jujutsu_segment(),
+                theme_nord::hg_segment(),
+                theme_nord::connectivity_segment(),
+                theme_nord::git_diff_segment(),
+                theme_nord::session_id_segment(),
+                theme_nord::last_prompt_segment(),
+                theme_nord::budget_segment(),
+                theme_nord::daily_cost_segment(),
+                theme_nord::last_commit_segment(),
+                theme_nord::git_identity_segment(),
             ],
             theme: "nord".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -227,6 +633,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_powerline_dark::model_segment(),
@@ -237,8 +648,62 @@ impl ThemePresets {
                 theme_powerline_dark::cost_segment(),
                 theme_powerline_dark::session_segment(),
                 theme_powerline_dark::output_style_segment(),
+                theme_powerline_dark::battery_segment(),
+                theme_powerline_dark::clock_segment(),
+                theme_powerline_dark::session_duration_segment(),
+                theme_powerline_dark::hostname_segment(),
+                theme_powerline_dark::user_segment(),
+                theme_powerline_dark::python_env_segment(),
+                theme_powerline_dark::conda_segment(),
+                theme_powerline_dark::node_segment(),
+                theme_powerline_dark::rust_segment(),
+                theme_powerline_dark::go_segment(),
+                theme_powerline_dark::java_segment(),
+                theme_powerline_dark::dotnet_segment(),
+                theme_powerline_dark::kubernetes_segment(),
+                theme_powerline_dark::docker_segment(),
+                theme_powerline_dark::terraform_segment(),
+                theme_powerline_dark::aws_segment(),
+                theme_powerline_dark::custom_segment(),
+                theme_powerline_dark::gcloud_segment(),
+                theme_powerline_dark::load_segment(),
+                theme_powerline_dark::disk_segment(),
+                theme_powerline_dark::version_segment(),
+                theme_powerline_dark::lines_changed_segment(),
+                theme_powerline_dark::message_count_segment(),
+                theme_powerline_dark::todo_segment(),
+                theme_powerline_dark::mcp_segment(),
+                theme_powerline_dark::burn_rate_segment(),
+                theme_powerline_dark::api_duration_segment(),
+                theme_powerline_dark::command_segment(),
+                theme_powerline_dark::runtime_segment(),
+                theme_powerline_dark::env_segment(),
+                theme_powerline_dark::package_segment(),
+                theme_powerline_dark::nix_segment(),
+                theme_powerline_dark::direnv_segment(),
+                theme_powerline_dark::ssh_segment(),
+                theme_powerline_dark::environment_segment(),
+                theme_powerline_dark::shell_segment(),
+                theme_powerline_dark::github_pr_segment(),
+                theme_powerline_dark::ci_status_segment(),
+                theme_powerline_dark::jujutsu_segment(),
+                theme_powerline_dark::hg_segment(),
+                theme_powerline_dark::connectivity_segment(),
+                theme_powerline_dark::git_diff_segment(),
+                theme_powerline_dark::session_id_segment(),
+                theme_powerline_dark::last_prompt_segment(),
+                theme_powerline_dark::budget_segment(),
+                theme_powerline_dark::daily_cost_segment(),
+                theme_powerline_dark::last_commit_segment(),
+                theme_powerline_dark::git_identity_segment(),
             ],
             theme: "powerline-dark".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -247,6 +712,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_powerline_light::model_segment(),
@@ -257,8 +727,62 @@ impl ThemePresets {
                 theme_powerline_light::cost_segment(),
                 theme_powerline_light::session_segment(),
                 theme_powerline_light::output_style_segment(),
+                theme_powerline_light::battery_segment(),
+                theme_powerline_light::clock_segment(),
+                theme_powerline_light::session_duration_segment(),
+                theme_powerline_light::hostname_segment(),
+                theme_powerline_light::user_segment(),
+                theme_powerline_light::python_env_segment(),
+                theme_powerline_light::conda_segment(),
+                theme_powerline_light::node_segment(),
+                theme_powerline_light::rust_segment(),
+                theme_powerline_light::go_segment(),
+                theme_powerline_light::java_segment(),
+                theme_powerline_light::dotnet_segment(),
+                theme_powerline_light::kubernetes_segment(),
+                theme_powerline_light::docker_segment(),
+                theme_powerline_light::terraform_segment(),
+                theme_powerline_light::aws_segment(),
+                theme_powerline_light::custom_segment(),
+                theme_powerline_light::gcloud_segment(),
+                theme_powerline_light::load_segment(),
+                theme_powerline_light::disk_segment(),
+                theme_powerline_light::version_segment(),
+                theme_powerline_light::lines_changed_segment(),
+                theme_powerline_light::message_count_segment(),
+                theme_powerline_light::todo_segment(),
+                theme_powerline_light::mcp_segment(),
+                theme_powerline_light::burn_rate_segment(),
+                theme_powerline_light::api_duration_segment(),
+                theme_powerline_light::command_segment(),
+                theme_powerline_light::runtime_segment(),
+                theme_powerline_light::env_segment(),
+                theme_powerline_light::package_segment(),
+                theme_powerline_light::nix_segment(),
+                theme_powerline_light::direnv_segment(),
+                theme_powerline_light::ssh_segment(),
+                theme_powerline_light::environment_segment(),
+                theme_powerline_light::shell_segment(),
+                theme_powerline_light::github_pr_segment(),
+                theme_powerline_light::ci_status_segment(),
+                theme_powerline_light::jujutsu_segment(),
+                theme_powerline_light::hg_segment(),
+                theme_powerline_light::connectivity_segment(),
+                theme_powerline_light::git_diff_segment(),
+                theme_powerline_light::session_id_segment(),
+                theme_powerline_light::last_prompt_segment(),
+                theme_powerline_light::budget_segment(),
+                theme_powerline_light::daily_cost_segment(),
+                theme_powerline_light::last_commit_segment(),
+                theme_powerline_light::git_identity_segment(),
             ],
             theme: "powerline-light".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -267,6 +791,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_powerline_rose_pine::model_segment(),
@@ -277,8 +806,62 @@ impl ThemePresets {
                 theme_powerline_rose_pine::cost_segment(),
                 theme_powerline_rose_pine::session_segment(),
                 theme_powerline_rose_pine::output_style_segment(),
+                theme_powerline_rose_pine::battery_segment(),
+                theme_powerline_rose_pine::clock_segment(),
+                theme_powerline_rose_pine::session_duration_segment(),
+                theme_powerline_rose_pine::hostname_segment(),
+                theme_powerline_rose_pine::user_segment(),
+                theme_powerline_rose_pine::python_env_segment(),
+                theme_powerline_rose_pine::conda_segment(),
+                theme_powerline_rose_pine::node_segment(),
+                theme_powerline_rose_pine::rust_segment(),
+                theme_powerline_rose_pine::go_segment(),
+                theme_powerline_rose_pine::java_segment(),
+                theme_powerline_rose_pine::dotnet_segment(),
+                theme_powerline_rose_pine::kubernetes_segment(),
+                theme_powerline_rose_pine::docker_segment(),
+                theme_powerline_rose_pine::terraform_segment(),
+                theme_powerline_rose_pine::aws_segment(),
+                theme_powerline_rose_pine::custom_segment(),
+                theme_powerline_rose_pine::gcloud_segment(),
+                theme_powerline_rose_pine::load_segment(),
+                theme_powerline_rose_pine::disk_segment(),
+                theme_powerline_rose_pine::version_segment(),
+                theme_powerline_rose_pine::lines_changed_segment(),
+                theme_powerline_rose_pine::message_count_segment(),
+                theme_powerline_rose_pine::todo_segment(),
+                theme_powerline_rose_pine::mcp_segment(),
+                theme_powerline_rose_pine::burn_rate_segment(),
+                theme_powerline_rose_pine::api_duration_segment(),
+                theme_powerline_rose_pine::command_segment(),
+                theme_powerline_rose_pine::runtime_segment(),
+                theme_powerline_rose_pine::env_segment(),
+                theme_powerline_rose_pine::package_segment(),
+                theme_powerline_rose_pine::nix_segment(),
+                theme_powerline_rose_pine::direnv_segment(),
+                theme_powerline_rose_pine::ssh_segment(),
+                theme_powerline_rose_pine::environment_segment(),
+                theme_powerline_rose_pine::shell_segment(),
+                theme_powerline_rose_pine::github_pr_segment(),
+                theme_powerline_rose_pine::ci_status_segment(),
+                theme_powerline_rose_pine::jujutsu_segment(),
+                theme_powerline_rose_pine::hg_segment(),
+                theme_powerline_rose_pine::connectivity_segment(),
+                theme_powerline_rose_pine::git_diff_segment(),
+                theme_powerline_rose_pine::session_id_segment(),
+                theme_powerline_rose_pine::last_prompt_segment(),
+                theme_powerline_rose_pine::budget_segment(),
+                theme_powerline_rose_pine::daily_cost_segment(),
+                theme_powerline_rose_pine::last_commit_segment(),
+                theme_powerline_rose_pine::git_identity_segment(),
             ],
             theme: "powerline-rose-pine".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
         }
     }
 
@@ -287,6 +870,11 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                truncate_marker: default_truncate_marker(),
+                nerd_font: true,
+                color: true,
+                left_pad: 0,
+                right_pad: 0,
             },
             segments: vec![
                 theme_powerline_tokyo_night::model_segment(),
@@ -297,8 +885,118 @@ impl ThemePresets {
                 theme_powerline_tokyo_night::cost_segment(),
                 theme_powerline_tokyo_night::session_segment(),
                 theme_powerline_tokyo_night::output_style_segment(),
+                theme_powerline_tokyo_night::battery_segment(),
+                theme_powerline_tokyo_night::clock_segment(),
+                theme_powerline_tokyo_night::session_duration_segment(),
+                theme_powerline_tokyo_night::hostname_segment(),
+                theme_powerline_tokyo_night::user_segment(),
+                theme_powerline_tokyo_night::python_env_segment(),
+                theme_powerline_tokyo_night::conda_segment(),
+                theme_powerline_tokyo_night::node_segment(),
+                theme_powerline_tokyo_night::rust_segment(),
+                theme_powerline_tokyo_night::go_segment(),
+                theme_powerline_tokyo_night::java_segment(),
+                theme_powerline_tokyo_night::dotnet_segment(),
+                theme_powerline_tokyo_night::kubernetes_segment(),
+                theme_powerline_tokyo_night::docker_segment(),
+                theme_powerline_tokyo_night::terraform_segment(),
+                theme_powerline_tokyo_night::aws_segment(),
+                theme_powerline_tokyo_night::custom_segment(),
+                theme_powerline_tokyo_night::gcloud_segment(),
+                theme_powerline_tokyo_night::load_segment(),
+                theme_powerline_tokyo_night::disk_segment(),
+                theme_powerline_tokyo_night::version_segment(),
+                theme_powerline_tokyo_night::lines_changed_segment(),
+                theme_powerline_tokyo_night::message_count_segment(),
+                theme_powerline_tokyo_night::todo_segment(),
+                theme_powerline_tokyo_night::mcp_segment(),
+                theme_powerline_tokyo_night::burn_rate_segment(),
+                theme_powerline_tokyo_night::api_duration_segment(),
+                theme_powerline_tokyo_night::command_segment(),
+                theme_powerline_tokyo_night::runtime_segment(),
+                theme_powerline_tokyo_night::env_segment(),
+                theme_powerline_tokyo_night::package_segment(),
+                theme_powerline_tokyo_night::nix_segment(),
+                theme_powerline_tokyo_night::direnv_segment(),
+                theme_powerline_tokyo_night::ssh_segment(),
+                theme_powerline_tokyo_night::environment_segment(),
+                theme_powerline_tokyo_night::shell_segment(),
+                theme_powerline_tokyo_night::github_pr_segment(),
+                theme_powerline_tokyo_night::ci_status_segment(),
+                theme_powerline_tokyo_night::jujutsu_segment(),
+                theme_powerline_tokyo_night::hg_segment(),
+                theme_powerline_tokyo_night::connectivity_segment(),
+                theme_powerline_tokyo_night::git_diff_segment(),
+                theme_powerline_tokyo_night::session_id_segment(),
+                theme_powerline_tokyo_night::last_prompt_segment(),
+                theme_powerline_tokyo_night::budget_segment(),
+                theme_powerline_tokyo_night::daily_cost_segment(),
+                theme_powerline_tokyo_night::last_commit_segment(),
+                theme_powerline_tokyo_night::git_identity_segment(),
             ],
             theme: "powerline-tokyo-night".to_string(),
+            blank_missing_env: false,
+            order: None,
+            truncate: false,
+            truncate_percent: default_truncate_percent(),
+            reserved_width: default_reserved_width(),
+            theme_rules: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SegmentId;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("ccline-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn export_then_load_round_trips_every_builtin_theme() {
+        let dir = temp_dir("theme-round-trip");
+        for (name, _) in ThemePresets::get_available_themes() {
+            let config = ThemePresets::get_theme(name);
+            for ext in ["toml", "json"] {
+                let path = dir.join(format!("{}.{}", name, ext));
+                ThemePresets::export_theme(&config, &path).unwrap();
+                let loaded = ThemePresets::load_theme_from_file(&path.to_string_lossy()).unwrap();
+                assert_eq!(
+                    serde_json::to_value(&loaded).unwrap(),
+                    serde_json::to_value(&config).unwrap(),
+                    "{} via {}",
+                    name,
+                    ext
+                );
+            }
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_refuses_builtin_names_without_force() {
+        let dir = temp_dir("theme-import");
+        let path = dir.join("nord.toml");
+        ThemePresets::export_theme(&ThemePresets::get_default(), &path).unwrap();
+
+        let err = ThemePresets::import_theme(&path, false).unwrap_err();
+        assert!(err.to_string().contains("built-in theme"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ascii_theme_status_symbols_are_ascii() {
+        let config = ThemePresets::get_ascii();
+        for id in [SegmentId::Git, SegmentId::Hg] {
+            let segment = config.segments.iter().find(|s| s.id == id).unwrap();
+            for key in ["dirty_symbol", "clean_symbol"] {
+                let symbol = segment.options[key].as_str().unwrap();
+                assert!(symbol.is_ascii(), "{:?} {} is {:?}", id, key, symbol);
+            }
         }
     }
 }
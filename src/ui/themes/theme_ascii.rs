@@ -0,0 +1,28 @@
+use crate::config::{ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig};
+
+/// The ascii theme's version of a segment: same options and enabled state, but no icon,
+/// colors or bold text, so output is portable to logs, CI and terminals without Nerd Fonts
+pub fn ascii_segment(mut segment: SegmentConfig) -> SegmentConfig {
+    segment.icon = IconConfig {
+        plain: String::new(),
+        nerd_font: String::new(),
+    };
+    segment.colors = ColorConfig {
+        icon: None,
+        text: None,
+        background: None,
+    };
+    segment.styles = TextStyleConfig::default();
+    // `warning_color`, `secondary_color` and the like
+    segment.options.retain(|key, _| !key.ends_with("_color"));
+    // Replace the default ● and ✓ status symbols
+    if matches!(segment.id, SegmentId::Git | SegmentId::Hg) {
+        segment
+            .options
+            .insert("dirty_symbol".to_string(), serde_json::json!("*"));
+        segment
+            .options
+            .insert("clean_symbol".to_string(), serde_json::json!(""));
+    }
+    segment
+}
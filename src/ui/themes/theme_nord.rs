@@ -92,6 +92,11 @@ pub fn git_segment() -> SegmentConfig {
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
+            opts.insert("show_tag".to_string(), serde_json::Value::Bool(false));
+            opts.insert(
+                "warning_color".to_string(),
+                serde_json::json!({ "c16": 11 }),
+            );
             opts
         },
     }
@@ -213,7 +218,11 @@ pub fn output_style_segment() -> SegmentConfig {
             }), // Nord cyan background
         },
         styles: TextStyleConfig::default(),
-        options: HashMap::new(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("always_show".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
     }
 }
 
@@ -258,3 +267,1794 @@ pub fn usage_segment() -> SegmentConfig {
         },
     }
 }
+
+pub fn battery_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Battery,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔋".to_string(),
+            nerd_font: "\u{f0079}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("warning_threshold".to_string(), serde_json::json!(20));
+            opts.insert("critical_threshold".to_string(), serde_json::json!(10));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn clock_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Clock,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🕐".to_string(),
+            nerd_font: "\u{f0954}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }), // Nord cyan background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "format".to_string(),
+                serde_json::Value::String("%H:%M".to_string()),
+            );
+            opts.insert(
+                "timezone".to_string(),
+                serde_json::Value::String("local".to_string()),
+            );
+            opts
+        },
+    }
+}
+
+pub fn session_duration_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::SessionDuration,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⌛".to_string(),
+            nerd_font: "\u{f051f}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+    }
+}
+
+pub fn hostname_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Hostname,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🖥️".to_string(),
+            nerd_font: "\u{f048b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("strip_domain".to_string(), serde_json::Value::Bool(true));
+            opts.insert("ssh_only".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn user_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::User,
+        enabled: false,
+        icon: IconConfig {
+            plain: "👤".to_string(),
+            nerd_font: "\u{f007}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "default_user".to_string(),
+                serde_json::Value::String(String::new()),
+            );
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 9 }));
+            opts
+        },
+    }
+}
+
+pub fn python_env_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::PythonEnv,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🐍".to_string(),
+            nerd_font: "\u{e73c}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn conda_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Conda,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🅒".to_string(),
+            nerd_font: "\u{e715}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_base".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn node_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Node,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⬢".to_string(),
+            nerd_font: "\u{e718}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("run_node".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn rust_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Rust,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🦀".to_string(),
+            nerd_font: "\u{e7a8}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "always_run_rustc".to_string(),
+                serde_json::Value::Bool(false),
+            );
+            opts.insert(
+                "nightly_color".to_string(),
+                serde_json::json!({ "c16": 13 }),
+            );
+            opts.insert("beta_color".to_string(), serde_json::json!({ "c16": 14 }));
+            opts
+        },
+    }
+}
+
+pub fn go_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Go,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🐹".to_string(),
+            nerd_font: "\u{e627}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }), // Nord cyan background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("max_depth".to_string(), serde_json::Value::Number(5.into()));
+            opts.insert(
+                "check_installed".to_string(),
+                serde_json::Value::Bool(false),
+            );
+            opts.insert(
+                "warning_color".to_string(),
+                serde_json::json!({ "c16": 11 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn java_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Java,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☕".to_string(),
+            nerd_font: "\u{e738}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_build_tool".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn dotnet_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Dotnet,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🟣".to_string(),
+            nerd_font: "\u{e77f}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 180,
+                g: 142,
+                b: 173,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+    }
+}
+
+pub fn kubernetes_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Kubernetes,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☸".to_string(),
+            nerd_font: "\u{f10fe}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_namespace".to_string(), serde_json::Value::Bool(true));
+            opts.insert(
+                "dangerous_contexts".to_string(),
+                serde_json::json!(["prod"]),
+            );
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn docker_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Docker,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🐳".to_string(),
+            nerd_font: "\u{f0868}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "only_when_nondefault".to_string(),
+                serde_json::Value::Bool(false),
+            );
+            opts
+        },
+    }
+}
+
+pub fn terraform_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Terraform,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🏗️".to_string(),
+            nerd_font: "\u{f1062}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 180,
+                g: 142,
+                b: 173,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "warning_pattern".to_string(),
+                serde_json::Value::String("prod.*".to_string()),
+            );
+            opts.insert(
+                "warning_color".to_string(),
+                serde_json::json!({ "c16": 11 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn aws_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Aws,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☁️".to_string(),
+            nerd_font: "\u{f0e0f}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("always_show".to_string(), serde_json::Value::Bool(false));
+            opts.insert("show_expiry".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_region".to_string(), serde_json::Value::Bool(true));
+            opts.insert("prefix".to_string(), serde_json::json!("aws:"));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn custom_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Custom,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🏷️".to_string(),
+            nerd_font: "\u{f04fc}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("text".to_string(), serde_json::Value::String(String::new()));
+            opts
+        },
+    }
+}
+
+pub fn gcloud_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Gcloud,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☁".to_string(),
+            nerd_font: "\u{f0163}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_account".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn load_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Load,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📈".to_string(),
+            nerd_font: "\u{f4bc}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("display".to_string(), serde_json::json!("normalized"));
+            opts.insert("warning_threshold".to_string(), serde_json::json!(70));
+            opts.insert("critical_threshold".to_string(), serde_json::json!(90));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn disk_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Disk,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⛁".to_string(),
+            nerd_font: "\u{f02ca}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_below".to_string(), serde_json::json!("10G"));
+            opts.insert("critical_below".to_string(), serde_json::json!("2G"));
+            opts.insert("always_show".to_string(), serde_json::Value::Bool(false));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn version_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Version,
+        enabled: false,
+        icon: IconConfig {
+            plain: "CC".to_string(),
+            nerd_font: "\u{f02b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }), // Nord cyan background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("only_on_change".to_string(), serde_json::Value::Bool(false));
+            opts.insert("notice_minutes".to_string(), serde_json::json!(60));
+            opts
+        },
+    }
+}
+
+pub fn lines_changed_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::LinesChanged,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📝".to_string(),
+            nerd_font: "\u{f440}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("compact_threshold".to_string(), serde_json::json!(1000));
+            opts.insert(
+                "secondary_color".to_string(),
+                serde_json::json!({ "c16": 1 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn message_count_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::MessageCount,
+        enabled: false,
+        icon: IconConfig {
+            plain: "✉".to_string(),
+            nerd_font: "\u{f01f0}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+    }
+}
+
+pub fn todo_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Todo,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☑".to_string(),
+            nerd_font: "\u{f0139}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("max_length".to_string(), serde_json::json!(30));
+            opts
+        },
+    }
+}
+
+pub fn mcp_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Mcp,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔌".to_string(),
+            nerd_font: "\u{f06a5}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("project_only".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn burn_rate_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::BurnRate,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔥".to_string(),
+            nerd_font: "\u{f0238}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("window_minutes".to_string(), serde_json::json!(10));
+            opts.insert("warning_threshold".to_string(), serde_json::json!(5000));
+            opts.insert("critical_threshold".to_string(), serde_json::json!(10000));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn api_duration_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::ApiDuration,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏱".to_string(),
+            nerd_font: "\u{f051b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+    }
+}
+
+pub fn command_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Command,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⚙️".to_string(),
+            nerd_font: "\u{f489}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("command".to_string(), serde_json::json!(""));
+            opts.insert("args".to_string(), serde_json::json!([]));
+            opts.insert("timeout_ms".to_string(), serde_json::json!(500));
+            opts.insert("cache_ttl_ms".to_string(), serde_json::json!(0));
+            opts
+        },
+    }
+}
+
+pub fn runtime_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Runtime,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⚙️".to_string(),
+            nerd_font: "\u{f0ad}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "runtimes".to_string(),
+                serde_json::json!(["node", "python", "rust", "go", "java", "dotnet"]),
+            );
+            opts
+        },
+    }
+}
+
+pub fn env_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Env,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🌐".to_string(),
+            nerd_font: "\u{f0462}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("variable".to_string(), serde_json::json!("NODE_ENV"));
+            opts.insert("prefix".to_string(), serde_json::json!(""));
+            opts.insert(
+                "levels".to_string(),
+                serde_json::json!({ "production": "critical", "staging": "warning" }),
+            );
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn package_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Package,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📦".to_string(),
+            nerd_font: "\u{f03d7}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "manifests".to_string(),
+                serde_json::json!(["Cargo.toml", "package.json", "pyproject.toml"]),
+            );
+            opts
+        },
+    }
+}
+
+pub fn nix_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Nix,
+        enabled: false,
+        icon: IconConfig {
+            plain: "❄".to_string(),
+            nerd_font: "\u{f1105}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_purity".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn direnv_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Direnv,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📂".to_string(),
+            nerd_font: "\u{f0256}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn ssh_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Ssh,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔐".to_string(),
+            nerd_font: "\u{f08c0}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_user_host".to_string(), serde_json::Value::Bool(true));
+            opts.insert(
+                "merge_into_hostname".to_string(),
+                serde_json::Value::Bool(false),
+            );
+            opts
+        },
+    }
+}
+
+pub fn environment_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Environment,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📦".to_string(),
+            nerd_font: "\u{f01a7}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "environments".to_string(),
+                serde_json::json!(["devcontainer", "docker", "wsl"]),
+            );
+            opts
+        },
+    }
+}
+
+pub fn shell_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Shell,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🐚".to_string(),
+            nerd_font: "\u{f120}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn github_pr_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::GithubPr,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔀".to_string(),
+            nerd_font: "\u{f407}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("timeout_ms".to_string(), serde_json::json!(1500));
+            opts.insert("cache_ttl_ms".to_string(), serde_json::json!(120000));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn ci_status_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::CiStatus,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⚙️".to_string(),
+            nerd_font: "\u{f0493}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("workflows".to_string(), serde_json::json!([]));
+            opts.insert("timeout_ms".to_string(), serde_json::json!(1500));
+            opts.insert("cache_ttl_ms".to_string(), serde_json::json!(120000));
+            opts.insert("success_color".to_string(), serde_json::json!({ "c16": 2 }));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn jujutsu_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Jujutsu,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🥋".to_string(),
+            nerd_font: "\u{f1d3}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("prefer".to_string(), serde_json::json!("jj"));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn hg_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Hg,
+        enabled: false,
+        icon: IconConfig {
+            plain: "☿".to_string(),
+            nerd_font: "\u{f0c62}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_status".to_string(), serde_json::Value::Bool(true));
+            opts.insert(
+                "warning_color".to_string(),
+                serde_json::json!({ "c16": 11 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn connectivity_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Connectivity,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🌐".to_string(),
+            nerd_font: "\u{f0318}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "target".to_string(),
+                serde_json::json!("api.anthropic.com:443"),
+            );
+            opts.insert("timeout_ms".to_string(), serde_json::json!(200));
+            opts.insert("cache_ttl_ms".to_string(), serde_json::json!(30000));
+            opts.insert("always_show".to_string(), serde_json::Value::Bool(false));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn git_diff_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::GitDiff,
+        enabled: false,
+        icon: IconConfig {
+            plain: "±".to_string(),
+            nerd_font: "\u{f0440}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("include_staged".to_string(), serde_json::Value::Bool(true));
+            opts.insert("timeout_ms".to_string(), serde_json::json!(500));
+            opts.insert(
+                "secondary_color".to_string(),
+                serde_json::json!({ "c16": 1 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn session_id_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::SessionId,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🆔".to_string(),
+            nerd_font: "\u{f2c2}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("length".to_string(), serde_json::json!(8));
+            opts.insert("link".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn last_prompt_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::LastPrompt,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💬".to_string(),
+            nerd_font: "\u{f075}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("max_length".to_string(), serde_json::json!(40));
+            opts
+        },
+    }
+}
+
+pub fn budget_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Budget,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💵".to_string(),
+            nerd_font: "\u{f0d6}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("daily_limit".to_string(), serde_json::json!(10.0));
+            opts.insert("warning_percent".to_string(), serde_json::json!(80));
+            opts.insert("critical_percent".to_string(), serde_json::json!(100));
+            opts.insert("timezone".to_string(), serde_json::json!("local"));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn daily_cost_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::DailyCost,
+        enabled: false,
+        icon: IconConfig {
+            plain: "Σ".to_string(),
+            nerd_font: "\u{f04a0}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("timezone".to_string(), serde_json::json!("local"));
+            opts
+        },
+    }
+}
+
+pub fn last_commit_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::LastCommit,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏲".to_string(),
+            nerd_font: "\u{f0150}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("warning_minutes".to_string(), serde_json::json!(60));
+            opts.insert("critical_minutes".to_string(), serde_json::json!(180));
+            opts.insert("timeout_ms".to_string(), serde_json::json!(500));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
+
+pub fn git_identity_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::GitIdentity,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🪪".to_string(),
+            nerd_font: "\u{f2bb}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("identities".to_string(), serde_json::json!({}));
+            opts.insert("default_identity".to_string(), serde_json::json!(""));
+            opts.insert("timeout_ms".to_string(), serde_json::json!(500));
+            opts.insert("warning_color".to_string(), serde_json::json!({ "c16": 3 }));
+            opts.insert(
+                "critical_color".to_string(),
+                serde_json::json!({ "c16": 9 }),
+            );
+            opts
+        },
+    }
+}
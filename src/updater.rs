@@ -30,6 +30,10 @@ pub enum UpdateStatus {
     Failed { error: String },
 }
 
+/// Hours between background release checks
+#[cfg(feature = "self-update")]
+const CHECK_INTERVAL_HOURS: i64 = 24;
+
 /// Update state persistence structure
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct UpdateState {
@@ -45,10 +49,10 @@ impl UpdateState {
     /// Get status bar display text
     pub fn status_text(&self) -> Option<String> {
         match &self.status {
-            #[cfg(feature = "self-update")]
-            UpdateStatus::Ready { version, .. } => Some(format!("\u{f06b0} Update v{}!", version)),
-            #[cfg(not(feature = "self-update"))]
-            UpdateStatus::Ready { version, .. } => Some(format!("\u{f06b0} Update v{}!", version)),
+            // A release found earlier may since have been installed by other means
+            UpdateStatus::Ready { .. } => self
+                .newer_version()
+                .map(|version| format!("⬆ v{}", version)),
             UpdateStatus::Downloading { progress } => Some(format!("\u{f01da} {}%", progress)),
             UpdateStatus::Installing => Some("\u{f01da} Installing...".to_string()),
             #[cfg(feature = "self-update")]
@@ -73,7 +77,7 @@ impl UpdateState {
         }
     }
 
-    /// Load update state from config directory and trigger auto-check if needed
+    /// Load update state from config directory; never touches the network
     pub fn load() -> Self {
         #[cfg(feature = "self-update")]
         {
@@ -84,7 +88,7 @@ impl UpdateState {
 
             let state_file = config_dir.join(".update_state.json");
 
-            let mut state = if let Ok(content) = std::fs::read_to_string(&state_file) {
+            if let Ok(content) = std::fs::read_to_string(&state_file) {
                 if let Ok(state) = serde_json::from_str::<UpdateState>(&content) {
                     state
                 } else {
@@ -98,56 +102,7 @@ impl UpdateState {
                     current_version: env!("CARGO_PKG_VERSION").to_string(),
                     ..Default::default()
                 }
-            };
-
-            // Trigger background update check if needed
-            if state.should_check_update() {
-                // Check if another update process is running
-                let should_start_check = if let Some(pid) = state.update_pid {
-                    !Self::is_process_running(pid)
-                } else {
-                    true
-                };
-
-                if should_start_check {
-                    // Perform synchronous update check for simplicity and reliability
-                    use crate::updater::github::check_for_updates;
-
-                    state.update_pid = Some(std::process::id());
-                    state.last_check = Some(chrono::Utc::now());
-                    let _ = state.save();
-
-                    // Perform update check
-                    match check_for_updates() {
-                        Ok(Some(release)) => {
-                            if release.find_asset_for_platform().is_some() {
-                                // Set Ready status with timestamp, user must run --update manually
-                                state.status = UpdateStatus::Ready {
-                                    version: release.version(),
-                                    found_at: chrono::Utc::now(),
-                                };
-                            } else {
-                                state.status = UpdateStatus::Failed {
-                                    error: "No compatible asset found".to_string(),
-                                };
-                            }
-                            state.latest_version = Some(release.version());
-                        }
-                        Ok(None) => {
-                            state.status = UpdateStatus::Idle;
-                        }
-                        Err(_) => {
-                            state.status = UpdateStatus::Idle;
-                        }
-                    }
-
-                    // Clear PID and save final state
-                    state.update_pid = None;
-                    let _ = state.save();
-                }
             }
-
-            state
         }
 
         #[cfg(not(feature = "self-update"))]
@@ -157,6 +112,84 @@ impl UpdateState {
         }
     }
 
+    /// Latest known release when it is newer than this binary
+    #[cfg(feature = "self-update")]
+    pub fn newer_version(&self) -> Option<String> {
+        let latest = self.latest_version.as_deref()?;
+        let current = semver::Version::parse(env!("CARGO_PKG_VERSION")).ok()?;
+        let parsed = semver::Version::parse(latest.trim_start_matches('v')).ok()?;
+        (parsed > current).then(|| latest.trim_start_matches('v').to_string())
+    }
+
+    #[cfg(not(feature = "self-update"))]
+    pub fn newer_version(&self) -> Option<String> {
+        None
+    }
+
+    /// Start `ccline --refresh-update-state` as a detached process when the last check
+    /// is stale, so rendering never waits on the network
+    #[cfg(feature = "self-update")]
+    pub fn spawn_background_check(&mut self) {
+        if !self.should_check_update() {
+            return;
+        }
+        let Ok(exe) = std::env::current_exe() else {
+            return;
+        };
+
+        // Claim this check first so concurrent renders don't start their own
+        self.last_check = Some(Utc::now());
+        if self.save().is_err() {
+            return;
+        }
+
+        use std::process::{Command, Stdio};
+        let _ = Command::new(exe)
+            .arg("--refresh-update-state")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+
+    #[cfg(not(feature = "self-update"))]
+    pub fn spawn_background_check(&mut self) {}
+
+    /// Query GitHub for the latest release and record it; run by the background process
+    #[cfg(feature = "self-update")]
+    pub fn refresh() {
+        use crate::updater::github::check_for_updates;
+
+        let mut state = Self::load();
+        state.update_pid = Some(std::process::id());
+        state.last_check = Some(chrono::Utc::now());
+        let _ = state.save();
+
+        match check_for_updates() {
+            Ok(Some(release)) => {
+                if release.find_asset_for_platform().is_some() {
+                    // Set Ready status with timestamp, user must run --update manually
+                    state.status = UpdateStatus::Ready {
+                        version: release.version(),
+                        found_at: chrono::Utc::now(),
+                    };
+                } else {
+                    state.status = UpdateStatus::Failed {
+                        error: "No compatible asset found".to_string(),
+                    };
+                }
+                state.latest_version = Some(release.version());
+            }
+            Ok(None) | Err(_) => {
+                state.status = UpdateStatus::Idle;
+            }
+        }
+
+        // Clear PID and save final state
+        state.update_pid = None;
+        let _ = state.save();
+    }
+
     /// Check if a process with given PID is still running
     #[cfg(feature = "self-update")]
     fn is_process_running(pid: u32) -> bool {
@@ -218,11 +251,18 @@ impl UpdateState {
             _ => {}
         }
 
-        // Check time interval (1 hour)
+        // A background check that is still running will write its own result
+        if let Some(pid) = self.update_pid {
+            if Self::is_process_running(pid) {
+                return false;
+            }
+        }
+
+        // At most one check per day
         if let Some(last_check) = self.last_check {
             let now = Utc::now();
             let hours_passed = now.signed_duration_since(last_check).num_hours();
-            hours_passed >= 1
+            hours_passed >= CHECK_INTERVAL_HOURS
         } else {
             true
         }
@@ -273,9 +313,16 @@ pub mod github {
         /// Find asset for current platform
         pub fn find_asset_for_platform(&self) -> Option<&ReleaseAsset> {
             let platform_suffix = get_platform_asset_name();
+            // ends_with, so the archive's `.sha256` companion doesn't match
             self.assets
                 .iter()
-                .find(|asset| asset.name.contains(&platform_suffix))
+                .find(|asset| asset.name.ends_with(&platform_suffix))
+        }
+
+        /// Find the published SHA-256 for `asset`, from `<asset>.sha256`
+        fn find_checksum_asset(&self, asset: &ReleaseAsset) -> Option<&ReleaseAsset> {
+            let checksum_name = format!("{}.sha256", asset.name);
+            self.assets.iter().find(|a| a.name == checksum_name)
         }
     }
 
@@ -300,17 +347,30 @@ pub mod github {
             }
         }
 
+        #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+        {
+            if should_use_static_binary() {
+                "linux-arm64-static.tar.gz".to_string()
+            } else {
+                "linux-arm64.tar.gz".to_string()
+            }
+        }
+
         #[cfg(not(any(
             all(target_os = "windows", target_arch = "x86_64"),
             all(target_os = "macos", target_arch = "x86_64"),
             all(target_os = "macos", target_arch = "aarch64"),
-            all(target_os = "linux", target_arch = "x86_64")
+            all(target_os = "linux", target_arch = "x86_64"),
+            all(target_os = "linux", target_arch = "aarch64")
         )))]
         return "unknown".to_string();
     }
 
     /// Determine if we should use static binary based on glibc version
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
     fn should_use_static_binary() -> bool {
         use std::process::Command;
 
@@ -337,7 +397,10 @@ pub mod github {
     }
 
     /// Parse version string like "2.35" into (major, minor)
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
     fn parse_version(version: &str) -> Option<(u32, u32)> {
         let parts: Vec<&str> = version.split('.').collect();
         if parts.len() >= 2 {
@@ -378,4 +441,142 @@ pub mod github {
             Err(format!("HTTP {}: {}", response.status(), response.status_text()).into())
         }
     }
+
+    /// Release archives are a few MB; refuse anything absurdly larger
+    const MAX_DOWNLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+    fn download(url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let response = ureq::get(url)
+            .set(
+                "User-Agent",
+                &format!("CCometixLine/{}", env!("CARGO_PKG_VERSION")),
+            )
+            .call()?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_DOWNLOAD_BYTES + 1)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+            return Err(format!("Download from {} is too large", url).into());
+        }
+        Ok(bytes)
+    }
+
+    /// Pull the `ccline` binary out of a release archive
+    fn extract_binary(
+        archive_name: &str,
+        archive: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let binary_name = if cfg!(windows) {
+            "ccline.exe"
+        } else {
+            "ccline"
+        };
+        let mut binary = Vec::new();
+
+        if archive_name.ends_with(".zip") {
+            let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive))?;
+            zip.by_name(binary_name)?.read_to_end(&mut binary)?;
+        } else {
+            let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+            let mut entry = tar
+                .entries()?
+                .filter_map(Result::ok)
+                .find(|entry| {
+                    entry
+                        .path()
+                        .is_ok_and(|path| path.file_name() == Some(binary_name.as_ref()))
+                })
+                .ok_or_else(|| format!("{} not found in {}", binary_name, archive_name))?;
+            entry.read_to_end(&mut binary)?;
+        }
+
+        if binary.is_empty() {
+            return Err(format!("{} in {} is empty", binary_name, archive_name).into());
+        }
+        Ok(binary)
+    }
+
+    /// Write `binary` next to the running executable, then rename it into place so the
+    /// executable is never left half-written. Windows can't replace a running image,
+    /// so the old one is first moved aside to `<name>.old`.
+    fn replace_current_exe(binary: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        let exe = std::fs::canonicalize(std::env::current_exe()?)?;
+        let file_name = exe
+            .file_name()
+            .ok_or("Cannot determine executable name")?
+            .to_string_lossy()
+            .into_owned();
+        let staged = exe.with_file_name(format!(".{}.new", file_name));
+
+        std::fs::write(&staged, binary)?;
+        // Keep the installed file's mode (executable bits)
+        std::fs::set_permissions(&staged, std::fs::metadata(&exe)?.permissions())?;
+
+        #[cfg(windows)]
+        {
+            let old = exe.with_file_name(format!("{}.old", file_name));
+            let _ = std::fs::remove_file(&old);
+            std::fs::rename(&exe, &old)?;
+            if let Err(e) = std::fs::rename(&staged, &exe) {
+                let _ = std::fs::rename(&old, &exe);
+                return Err(e.into());
+            }
+        }
+
+        #[cfg(not(windows))]
+        if let Err(e) = std::fs::rename(&staged, &exe) {
+            let _ = std::fs::remove_file(&staged);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    /// Download this platform's asset, verify it against the release's published
+    /// SHA-256 and install it over the running binary
+    pub fn install_release(release: &GitHubRelease) -> Result<(), Box<dyn std::error::Error>> {
+        use sha2::{Digest, Sha256};
+
+        let asset = release
+            .find_asset_for_platform()
+            .ok_or("No release asset for this platform")?;
+        let checksum_asset = release.find_checksum_asset(asset).ok_or_else(|| {
+            format!(
+                "Release {} publishes no checksum for {}; refusing to install unverified",
+                release.tag_name, asset.name
+            )
+        })?;
+
+        // `sha256sum` format: "<hex digest>  <file name>"
+        let checksum = String::from_utf8(download(&checksum_asset.browser_download_url)?)?;
+        let expected = checksum
+            .split_whitespace()
+            .next()
+            .filter(|digest| digest.len() == 64)
+            .ok_or_else(|| format!("Malformed checksum file {}", checksum_asset.name))?
+            .to_ascii_lowercase();
+
+        let archive = download(&asset.browser_download_url)?;
+        let actual: String = Sha256::digest(&archive)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        if actual != expected {
+            return Err(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                asset.name, expected, actual
+            )
+            .into());
+        }
+
+        let binary = extract_binary(&asset.name, &archive)?;
+        replace_current_exe(&binary)
+    }
 }
@@ -0,0 +1,168 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A run of printable text or a complete ANSI escape sequence
+pub enum AnsiToken<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+}
+
+/// Split text into escape sequences and the printable runs between them
+pub fn ansi_tokens(text: &str) -> Vec<AnsiToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        match rest.find('\x1b') {
+            Some(0) => {
+                let end = escape_len(rest);
+                tokens.push(AnsiToken::Escape(&rest[..end]));
+                rest = &rest[end..];
+            }
+            Some(start) => {
+                tokens.push(AnsiToken::Text(&rest[..start]));
+                rest = &rest[start..];
+            }
+            None => {
+                tokens.push(AnsiToken::Text(rest));
+                rest = "";
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Byte length of the escape sequence at the start of `text` (which begins with ESC)
+fn escape_len(text: &str) -> usize {
+    let body = &text[1..];
+    match body.chars().next() {
+        // CSI: ESC [ params... final byte in '@'..='~'
+        Some('[') => body[1..]
+            .char_indices()
+            .find(|(_, ch)| ('@'..='~').contains(ch))
+            .map(|(i, ch)| 2 + i + ch.len_utf8())
+            .unwrap_or(text.len()),
+        // OSC (e.g. OSC 8 hyperlinks): ESC ] ... terminated by BEL or ST (ESC \)
+        Some(']') => {
+            let payload = &body[1..];
+            let bel = payload.find('\x07').map(|i| (i, 1));
+            let st = payload.find("\x1b\\").map(|i| (i, 2));
+            match (bel, st) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+            .map(|(i, terminator)| 2 + i + terminator)
+            .unwrap_or(text.len())
+        }
+        // Two-character escapes such as ESC 7 / ESC 8
+        Some(ch) => 1 + ch.len_utf8(),
+        None => text.len(),
+    }
+}
+
+/// Remove every escape sequence, leaving only the printable text
+pub fn strip_ansi(text: &str) -> String {
+    ansi_tokens(text)
+        .into_iter()
+        .filter_map(|token| match token {
+            AnsiToken::Text(run) => Some(run),
+            AnsiToken::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// Terminal cells taken by one grapheme cluster, so combining marks,
+/// ZWJ emoji sequences and flags count as a single glyph
+pub fn grapheme_width(grapheme: &str) -> usize {
+    UnicodeWidthStr::width(grapheme)
+}
+
+/// Calculate visible width of text (excluding ANSI escape sequences)
+pub fn visible_width(text: &str) -> usize {
+    ansi_tokens(text)
+        .into_iter()
+        .map(|token| match token {
+            AnsiToken::Text(run) => run.graphemes(true).map(grapheme_width).sum(),
+            AnsiToken::Escape(_) => 0,
+        })
+        .sum()
+}
+
+/// Cut text to `max_width` columns, ending it with `marker`, while keeping escape
+/// sequences intact. Text that already fits is returned unchanged
+pub fn truncate_to_width(text: &str, max_width: usize, marker: &str) -> String {
+    if visible_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let limit = max_width.saturating_sub(visible_width(marker));
+    let mut result = String::new();
+    let mut width = 0;
+    let mut styled = false;
+
+    'tokens: for token in ansi_tokens(text) {
+        match token {
+            AnsiToken::Escape(seq) => {
+                result.push_str(seq);
+                styled = true;
+            }
+            AnsiToken::Text(run) => {
+                for grapheme in run.graphemes(true) {
+                    let char_width = grapheme_width(grapheme);
+                    if width + char_width > limit {
+                        result.push_str(marker);
+                        // Plain (--no-color) output stays free of escapes
+                        if styled {
+                            result.push_str("\x1b[0m");
+                        }
+                        break 'tokens;
+                    }
+                    result.push_str(grapheme);
+                    width += char_width;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cjk_is_two_columns_per_character() {
+        assert_eq!(visible_width("中文"), 4);
+        assert_eq!(visible_width("\x1b[32m日本語\x1b[0m ok"), 9);
+    }
+
+    #[test]
+    fn combining_accents_add_no_width() {
+        // "e" + COMBINING ACUTE ACCENT
+        assert_eq!(visible_width("e\u{301}"), 1);
+        assert_eq!(visible_width("cafe\u{301}"), 4);
+    }
+
+    #[test]
+    fn flag_emoji_is_one_wide_glyph() {
+        assert_eq!(visible_width("🇯🇵"), 2);
+        assert_eq!(visible_width("🇯🇵🇫🇷"), 4);
+    }
+
+    #[test]
+    fn zwj_sequence_is_one_wide_glyph() {
+        assert_eq!(visible_width("👩\u{200d}💻"), 2);
+    }
+
+    #[test]
+    fn osc8_hyperlinks_are_not_counted() {
+        // BEL-terminated and ST-terminated forms
+        let bel = "\x1b]8;;https://example.com\x07link\x1b]8;;\x07";
+        let st = "\x1b]8;;file:///tmp/a%20b\x1b\\link\x1b]8;;\x1b\\";
+        assert_eq!(visible_width(bel), 4);
+        assert_eq!(visible_width(st), 4);
+        assert_eq!(strip_ansi(st), "link");
+    }
+}
@@ -1,3 +1,4 @@
+use clap::ValueEnum;
 use std::fs;
 use std::path::Path;
 use tree_sitter::{Node, Parser, Tree};
@@ -16,10 +17,90 @@ struct PatchInfo {
     replacement: String,
 }
 
+/// Oldest Claude Code release the patch patterns were written against
+pub const MIN_SUPPORTED_VERSION: (u32, u32, u32) = (1, 0, 0);
+
+/// Newest Claude Code release the patch patterns were checked against; later releases
+/// may have moved the code the patterns look for
+pub const LAST_TESTED_VERSION: (u32, u32, u32) = (2, 1, 19);
+
+/// A single patch, selectable with `--patches`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchKind {
+    /// Show token counts in the spinner
+    SpinnerTokens,
+    /// Hide the "Context low" warning
+    ContextLow,
+    /// Hide the "esc to interrupt" hint
+    EscInterrupt,
+    /// Skip the subscription check for Chrome integration
+    ChromeSubscription,
+    /// Drop the subscription message from /chrome
+    ChromeCommand,
+    /// Drop the Chrome startup notification
+    ChromeStartup,
+    /// Hide the "Context left until auto-compact" notice
+    AutoCompactNotice,
+}
+
+impl PatchKind {
+    /// Applied when `--patches` is not given; newer patches are opt-in
+    pub const DEFAULT: [PatchKind; 6] = [
+        PatchKind::SpinnerTokens,
+        PatchKind::ContextLow,
+        PatchKind::EscInterrupt,
+        PatchKind::ChromeSubscription,
+        PatchKind::ChromeCommand,
+        PatchKind::ChromeStartup,
+    ];
+
+    /// Name shown in the results summary
+    pub fn label(self) -> &'static str {
+        match self {
+            PatchKind::SpinnerTokens => "Spinner token counter",
+            PatchKind::ContextLow => "Context low warnings",
+            PatchKind::EscInterrupt => "ESC interrupt display",
+            PatchKind::ChromeSubscription => "Chrome subscription check",
+            PatchKind::ChromeCommand => "/chrome command message",
+            PatchKind::ChromeStartup => "Chrome startup notification",
+            PatchKind::AutoCompactNotice => "Auto-compact notice",
+        }
+    }
+}
+
+/// What happened to one selected patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchOutcome {
+    Applied,
+    /// The code the patch targets wasn't found in this release
+    PatternNotFound,
+    /// The target overlaps a patch applied earlier in the same run
+    Overlapping,
+    /// `cli.js` could not be parsed, so nothing was looked up
+    ParseFailed,
+}
+
+impl PatchOutcome {
+    pub fn is_applied(self) -> bool {
+        self == PatchOutcome::Applied
+    }
+
+    /// Reason shown next to a patch that wasn't applied
+    pub fn reason(self) -> &'static str {
+        match self {
+            PatchOutcome::Applied => "applied",
+            PatchOutcome::PatternNotFound => "pattern not found",
+            PatchOutcome::Overlapping => "overlaps another patch",
+            PatchOutcome::ParseFailed => "cli.js could not be parsed",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ClaudeCodePatcher {
     file_content: String,
     file_path: String,
+    version: Option<(u32, u32, u32)>,
 }
 
 impl ClaudeCodePatcher {
@@ -27,31 +108,50 @@ impl ClaudeCodePatcher {
         let path = file_path.as_ref();
         let content = fs::read_to_string(path)?;
 
+        // cli.js normally carries its version in a header comment; the package.json next
+        // to it covers builds that strip comments
+        let version =
+            Self::parse_header_version(&content).or_else(|| Self::read_package_version(path));
+
         Ok(Self {
             file_content: content,
             file_path: path.to_string_lossy().to_string(),
+            version,
         })
     }
 
-    /// Get the version of Claude Code from the file header
-    /// Format: // Version: X.Y.Z
+    /// Parse `X.Y.Z`, ignoring any pre-release or build suffix
+    fn parse_version(version_str: &str) -> Option<(u32, u32, u32)> {
+        let mut parts = version_str.trim().split(['.', '-', '+']);
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        Some((major, minor, patch))
+    }
+
+    /// Format: // Version: X.Y.Z, within the first 500 bytes
+    fn parse_header_version(content: &str) -> Option<(u32, u32, u32)> {
+        let end = (0..=content.len().min(500))
+            .rev()
+            .find(|&i| content.is_char_boundary(i))?;
+
+        content[..end]
+            .lines()
+            .find_map(|line| line.strip_prefix("// Version:"))
+            .and_then(Self::parse_version)
+    }
+
+    /// `version` from the package.json in the same directory as `cli.js`
+    fn read_package_version(cli_path: &Path) -> Option<(u32, u32, u32)> {
+        let package_path = cli_path.parent()?.join("package.json");
+        let content = fs::read_to_string(package_path).ok()?;
+        let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+        Self::parse_version(package.get("version")?.as_str()?)
+    }
+
+    /// Get the version of Claude Code detected when the file was loaded
     pub fn get_version(&self) -> Option<(u32, u32, u32)> {
-        // Look for "// Version: X.Y.Z" in the first 500 bytes
-        let header = &self.file_content[..std::cmp::min(500, self.file_content.len())];
-
-        for line in header.lines() {
-            if line.starts_with("// Version:") {
-                let version_str = line.trim_start_matches("// Version:").trim();
-                let parts: Vec<&str> = version_str.split('.').collect();
-                if parts.len() >= 3 {
-                    let major = parts[0].parse().ok()?;
-                    let minor = parts[1].parse().ok()?;
-                    let patch = parts[2].parse().ok()?;
-                    return Some((major, minor, patch));
-                }
-            }
-        }
-        None
+        self.version
     }
 
     /// Check if version is >= the specified version
@@ -70,6 +170,33 @@ impl ClaudeCodePatcher {
         false
     }
 
+    /// Refuse files that don't look like a supported Claude Code `cli.js`, before
+    /// anything is backed up or written
+    pub fn check_supported(&self) -> Result<(u32, u32, u32), String> {
+        let version = self.get_version().ok_or_else(|| {
+            format!(
+                "{} has no \"// Version:\" header and no package.json beside it; is it Claude Code's cli.js?",
+                self.file_path
+            )
+        })?;
+
+        let (major, minor, patch) = MIN_SUPPORTED_VERSION;
+        if !self.version_gte(major, minor, patch) {
+            return Err(format!(
+                "Claude Code {}.{}.{} is older than the oldest supported release {}.{}.{}",
+                version.0, version.1, version.2, major, minor, patch
+            ));
+        }
+
+        Ok(version)
+    }
+
+    /// Whether the detected release is newer than [`LAST_TESTED_VERSION`]
+    pub fn is_newer_than_tested(&self) -> bool {
+        self.version
+            .is_some_and(|version| version > LAST_TESTED_VERSION)
+    }
+
     // =========================================================================
     // Core parsing - parse once and reuse
     // =========================================================================
@@ -582,10 +709,48 @@ impl ClaudeCodePatcher {
         None
     }
 
+    // =========================================================================
+    // Patch 7: Auto-compact Notice
+    // =========================================================================
+
+    /// Find the early return guarding the "until auto-compact" notice. Releases that
+    /// render it from the context low function are already covered by patch 2.
+    fn find_auto_compact_function(&self, node: Node) -> Option<LocationResult> {
+        if node.kind() == "function_declaration" || node.kind() == "function" {
+            let node_text = self.get_node_text(node);
+
+            if node_text.contains("until auto-compact") && !node_text.contains("Context low (") {
+                println!(
+                    "Found auto-compact notice function at {}-{}",
+                    node.start_byte(),
+                    node.end_byte()
+                );
+                return self.find_if_return_null_in_function(node);
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if let Some(result) = self.find_auto_compact_function(child) {
+                return Some(result);
+            }
+        }
+        None
+    }
+
     // =========================================================================
     // Utility functions
     // =========================================================================
 
+    /// Whether `location` intersects a patch already queued; applying both would
+    /// splice one replacement into the other
+    fn overlaps(patches: &[PatchInfo], location: &LocationResult) -> bool {
+        patches.iter().any(|patch| {
+            location.start_index < patch.location.end_index
+                && patch.location.start_index < location.end_index
+        })
+    }
+
     /// Show a diff of the changes (for debugging)
     fn show_diff(&self, title: &str, injected_text: &str, start_index: usize, end_index: usize) {
         let context_start = start_index.saturating_sub(50);
@@ -622,9 +787,14 @@ impl ClaudeCodePatcher {
     // Optimized batch patching - parse once, apply all
     // =========================================================================
 
-    /// Apply all patches using optimized single-parse strategy
-    /// Returns results in the same order as the original implementation
-    pub fn apply_all_patches(&mut self) -> Vec<(&'static str, bool)> {
+    /// Apply the default patch set
+    pub fn apply_all_patches(&mut self) -> Vec<(&'static str, PatchOutcome)> {
+        self.apply_patches(&PatchKind::DEFAULT)
+    }
+
+    /// Apply the selected patches using an optimized single-parse strategy;
+    /// results follow the fixed patch order, one entry per selected patch
+    pub fn apply_patches(&mut self, selected: &[PatchKind]) -> Vec<(&'static str, PatchOutcome)> {
         let mut results = Vec::new();
 
         // Parse AST only once
@@ -632,14 +802,10 @@ impl ClaudeCodePatcher {
             Some(t) => t,
             None => {
                 println!("⚠️ Failed to parse JavaScript AST");
-                return vec![
-                    ("Spinner token counter", false),
-                    ("Context low warnings", false),
-                    ("ESC interrupt display", false),
-                    ("Chrome subscription check", false),
-                    ("/chrome command message", false),
-                    ("Chrome startup notification", false),
-                ];
+                return selected
+                    .iter()
+                    .map(|kind| (kind.label(), PatchOutcome::ParseFailed))
+                    .collect();
             }
         };
 
@@ -647,162 +813,223 @@ impl ClaudeCodePatcher {
         let mut patches: Vec<PatchInfo> = Vec::new();
 
         // 1. Spinner token counter (verbose property)
-        match self.find_spinner_verbose_property(root) {
-            Some(loc) => {
-                let replacement = "verbose:true".to_string();
-                self.show_diff(
-                    "Spinner Token Counter",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("Spinner token counter", true));
-            }
-            None => {
-                println!("⚠️ Could not enable Spinner token counter");
-                results.push(("Spinner token counter", false));
+        if selected.contains(&PatchKind::SpinnerTokens) {
+            match self.find_spinner_verbose_property(root) {
+                Some(loc) => {
+                    let replacement = "verbose:true".to_string();
+                    self.show_diff(
+                        "Spinner Token Counter",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::SpinnerTokens.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not enable Spinner token counter");
+                    results.push((
+                        PatchKind::SpinnerTokens.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
         }
 
         // 2. Context low warnings
-        match self.find_context_low_condition(root) {
-            Some(loc) => {
-                let replacement = "if(true)return null;".to_string();
-                self.show_diff(
-                    "Context Low Condition",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("Context low warnings", true));
-            }
-            None => {
-                println!("⚠️ Could not disable context low warnings");
-                results.push(("Context low warnings", false));
+        if selected.contains(&PatchKind::ContextLow) {
+            match self.find_context_low_condition(root) {
+                Some(loc) => {
+                    let replacement = "if(true)return null;".to_string();
+                    self.show_diff(
+                        "Context Low Condition",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::ContextLow.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not disable context low warnings");
+                    results.push((PatchKind::ContextLow.label(), PatchOutcome::PatternNotFound));
+                }
             }
         }
 
         // 3. ESC interrupt display
-        match self.find_esc_interrupt_condition(root) {
-            Some(loc) => {
-                let original_condition = loc.variable_name.clone().unwrap_or_default();
-                println!(
-                    "Replacing condition '{}' with '(false)' at position {}-{}",
-                    original_condition, loc.start_index, loc.end_index
-                );
-                let replacement = "(false)".to_string();
-                self.show_diff(
-                    "ESC Interrupt",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("ESC interrupt display", true));
-            }
-            None => {
-                println!("⚠️ Could not disable esc/interrupt display");
-                results.push(("ESC interrupt display", false));
+        if selected.contains(&PatchKind::EscInterrupt) {
+            match self.find_esc_interrupt_condition(root) {
+                Some(loc) => {
+                    let original_condition = loc.variable_name.clone().unwrap_or_default();
+                    println!(
+                        "Replacing condition '{}' with '(false)' at position {}-{}",
+                        original_condition, loc.start_index, loc.end_index
+                    );
+                    let replacement = "(false)".to_string();
+                    self.show_diff(
+                        "ESC Interrupt",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::EscInterrupt.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not disable esc/interrupt display");
+                    results.push((
+                        PatchKind::EscInterrupt.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
         }
 
         // 4. Chrome subscription check
-        match self.find_chrome_subscription_check(root) {
-            Some(loc) => {
-                println!(
-                    "Removing '{}' at position {}-{}",
-                    loc.variable_name.as_ref().unwrap_or(&String::new()),
-                    loc.start_index,
-                    loc.end_index
-                );
-                let replacement = "".to_string();
-                self.show_diff(
-                    "Chrome Subscription Check",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("Chrome subscription check", true));
-            }
-            None => {
-                println!("⚠️ Could not bypass Chrome subscription check");
-                results.push(("Chrome subscription check", false));
+        if selected.contains(&PatchKind::ChromeSubscription) {
+            match self.find_chrome_subscription_check(root) {
+                Some(loc) => {
+                    println!(
+                        "Removing '{}' at position {}-{}",
+                        loc.variable_name.as_ref().unwrap_or(&String::new()),
+                        loc.start_index,
+                        loc.end_index
+                    );
+                    let replacement = "".to_string();
+                    self.show_diff(
+                        "Chrome Subscription Check",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::ChromeSubscription.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not bypass Chrome subscription check");
+                    results.push((
+                        PatchKind::ChromeSubscription.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
         }
 
         // 5. /chrome command message
-        match self.find_chrome_command_message(root) {
-            Some(loc) => {
-                println!(
-                    "Replacing '{}' with 'false&&' at position {}-{}",
-                    loc.variable_name.as_ref().unwrap_or(&String::new()),
-                    loc.start_index,
-                    loc.end_index
-                );
-                let replacement = "false&&".to_string();
-                self.show_diff(
-                    "/chrome Command Message",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("/chrome command message", true));
-            }
-            None => {
-                println!("⚠️ Could not remove /chrome command subscription message");
-                results.push(("/chrome command message", false));
+        if selected.contains(&PatchKind::ChromeCommand) {
+            match self.find_chrome_command_message(root) {
+                Some(loc) => {
+                    println!(
+                        "Replacing '{}' with 'false&&' at position {}-{}",
+                        loc.variable_name.as_ref().unwrap_or(&String::new()),
+                        loc.start_index,
+                        loc.end_index
+                    );
+                    let replacement = "false&&".to_string();
+                    self.show_diff(
+                        "/chrome Command Message",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::ChromeCommand.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not remove /chrome command subscription message");
+                    results.push((
+                        PatchKind::ChromeCommand.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
         }
 
         // 6. Chrome startup notification
-        match self.find_chrome_startup_notification_check(root) {
-            Some(loc) => {
-                println!(
-                    "Replacing '{}' with 'false' at position {}-{}",
-                    loc.variable_name.as_ref().unwrap_or(&String::new()),
-                    loc.start_index,
-                    loc.end_index
-                );
-                let replacement = "false".to_string();
-                self.show_diff(
-                    "Chrome Startup Notification",
-                    &replacement,
-                    loc.start_index,
-                    loc.end_index,
-                );
-                patches.push(PatchInfo {
-                    location: loc,
-                    replacement,
-                });
-                results.push(("Chrome startup notification", true));
+        if selected.contains(&PatchKind::ChromeStartup) {
+            match self.find_chrome_startup_notification_check(root) {
+                Some(loc) => {
+                    println!(
+                        "Replacing '{}' with 'false' at position {}-{}",
+                        loc.variable_name.as_ref().unwrap_or(&String::new()),
+                        loc.start_index,
+                        loc.end_index
+                    );
+                    let replacement = "false".to_string();
+                    self.show_diff(
+                        "Chrome Startup Notification",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::ChromeStartup.label(), PatchOutcome::Applied));
+                }
+                None => {
+                    println!("⚠️ Could not remove Chrome startup notification check");
+                    results.push((
+                        PatchKind::ChromeStartup.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
-            None => {
-                println!("⚠️ Could not remove Chrome startup notification check");
-                results.push(("Chrome startup notification", false));
+        }
+
+        // 7. Auto-compact notice
+        if selected.contains(&PatchKind::AutoCompactNotice) {
+            match self.find_auto_compact_function(root) {
+                Some(loc) if !Self::overlaps(&patches, &loc) => {
+                    let replacement = "if(true)return null;".to_string();
+                    self.show_diff(
+                        "Auto-compact Notice",
+                        &replacement,
+                        loc.start_index,
+                        loc.end_index,
+                    );
+                    patches.push(PatchInfo {
+                        location: loc,
+                        replacement,
+                    });
+                    results.push((PatchKind::AutoCompactNotice.label(), PatchOutcome::Applied));
+                }
+                Some(_) => {
+                    println!("⚠️ Auto-compact notice overlaps another patch, skipped");
+                    results.push((
+                        PatchKind::AutoCompactNotice.label(),
+                        PatchOutcome::Overlapping,
+                    ));
+                }
+                None => {
+                    println!("⚠️ Could not disable auto-compact notice");
+                    results.push((
+                        PatchKind::AutoCompactNotice.label(),
+                        PatchOutcome::PatternNotFound,
+                    ));
+                }
             }
         }
 
         // Sort patches by position descending (apply from end to start to avoid offset issues)
-        patches.sort_by(|a, b| b.location.start_index.cmp(&a.location.start_index));
+        patches.sort_by_key(|p| std::cmp::Reverse(p.location.start_index));
 
         // Apply all patches in one pass
         for patch in patches {
@@ -818,18 +1045,25 @@ impl ClaudeCodePatcher {
         results
     }
 
-    /// Print patch results summary
-    pub fn print_summary(results: &[(&str, bool)]) {
-        println!("\n📊 Patch Results:");
-        for (name, success) in results {
-            if *success {
+    /// Print patch results summary, with the detected version and why each patch
+    /// that wasn't applied failed, so breakage can be reported precisely
+    pub fn print_summary(&self, results: &[(&str, PatchOutcome)]) {
+        match self.version {
+            Some((major, minor, patch)) => println!(
+                "\n📊 Patch Results (Claude Code {}.{}.{}):",
+                major, minor, patch
+            ),
+            None => println!("\n📊 Patch Results (Claude Code version unknown):"),
+        }
+        for (name, outcome) in results {
+            if outcome.is_applied() {
                 println!("  ✅ {}", name);
             } else {
-                println!("  ❌ {}", name);
+                println!("  ❌ {}: {}", name, outcome.reason());
             }
         }
 
-        let success_count = results.iter().filter(|(_, s)| *s).count();
+        let success_count = results.iter().filter(|(_, o)| o.is_applied()).count();
         let total_count = results.len();
 
         if success_count == total_count {
@@ -839,6 +1073,13 @@ impl ClaudeCodePatcher {
                 "\n⚠️ {}/{} patches applied successfully",
                 success_count, total_count
             );
+            if self.is_newer_than_tested() {
+                let (major, minor, patch) = LAST_TESTED_VERSION;
+                println!(
+                    "   This release is newer than the last tested one ({}.{}.{}); please include the version above when reporting missing patterns",
+                    major, minor, patch
+                );
+            }
         }
     }
 }
@@ -0,0 +1,71 @@
+/// Largest number of decimals `abbreviate_number` accepts
+pub const MAX_PRECISION: usize = 2;
+
+/// Suffixes for successive powers of 1000, starting at 1
+const UNITS: [&str; 5] = ["", "k", "M", "B", "T"];
+
+/// Shorten large numbers for display: 999 -> "999", 1000 -> "1k", 187432 -> "187k",
+/// 1_500_000 -> "1.5M". `precision` (capped at `MAX_PRECISION`) is the most decimals
+/// shown; trailing zeros are dropped, and values of 100 or more in their unit show none.
+/// Values below 1000 are rounded to whole numbers.
+pub fn abbreviate_number(value: f64, precision: usize) -> String {
+    let precision = precision.min(MAX_PRECISION);
+    let sign = if value < 0.0 { "-" } else { "" };
+    let mut value = value.abs();
+    let mut decimals = 0;
+
+    // Move up a unit while the rounded text would reach 1000, so 999_999 becomes
+    // "1M" rather than "1000k"
+    let mut unit = 0;
+    loop {
+        let text = format!("{:.*}", decimals, value);
+        if unit + 1 == UNITS.len() || text.parse::<f64>().unwrap_or(0.0) < 1000.0 {
+            return format!("{}{}{}", sign, trim_decimals(text), UNITS[unit]);
+        }
+        value /= 1000.0;
+        decimals = if value >= 100.0 { 0 } else { precision };
+        unit += 1;
+    }
+}
+
+/// "1.50" -> "1.5", "2.0" -> "2"
+fn trim_decimals(text: String) -> String {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thousand_boundary() {
+        assert_eq!(abbreviate_number(999.0, 1), "999");
+        assert_eq!(abbreviate_number(999.4, 1), "999");
+        assert_eq!(abbreviate_number(999.6, 1), "1k");
+        assert_eq!(abbreviate_number(1000.0, 1), "1k");
+        assert_eq!(abbreviate_number(1050.0, 1), "1.1k");
+    }
+
+    #[test]
+    fn million_boundary() {
+        assert_eq!(abbreviate_number(1_500_000.0, 1), "1.5M");
+        assert_eq!(abbreviate_number(1_000_000.0, 1), "1M");
+        assert_eq!(abbreviate_number(999_499.0, 1), "999k");
+        // Rounding up to "1000k" moves to the next unit instead
+        assert_eq!(abbreviate_number(999_500.0, 1), "1M");
+        assert_eq!(abbreviate_number(999_999.0, 2), "1M");
+    }
+
+    #[test]
+    fn precision_and_sign() {
+        assert_eq!(abbreviate_number(187_432.0, 2), "187k");
+        assert_eq!(abbreviate_number(12_345.0, 0), "12k");
+        assert_eq!(abbreviate_number(12_345.0, 2), "12.35k");
+        assert_eq!(abbreviate_number(12_345.0, 9), "12.35k");
+        assert_eq!(abbreviate_number(-1_500.0, 1), "-1.5k");
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+/// Sections of an INI file, each mapping keys to values.
+/// Keys before the first section header land in the "" section.
+pub type IniSections = HashMap<String, HashMap<String, String>>;
+
+/// Parse the INI dialect used by AWS and gcloud config files:
+/// `[section]` headers, `key = value` pairs, `#`/`;` comment lines
+pub fn parse_ini(content: &str) -> IniSections {
+    let mut sections = IniSections::new();
+    let mut current = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = name.trim().to_string();
+            sections.entry(current.clone()).or_default();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(current.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
@@ -1,4 +1,13 @@
+pub mod ansi;
 pub mod claude_code_patcher;
 pub mod credentials;
+pub mod format;
+pub mod ini;
+pub mod process;
+pub mod terminal;
 
-pub use claude_code_patcher::{ClaudeCodePatcher, LocationResult};
+pub use claude_code_patcher::{
+    ClaudeCodePatcher, LocationResult, PatchKind, PatchOutcome, LAST_TESTED_VERSION,
+};
+pub use format::abbreviate_number;
+pub use process::OutputWithTimeout;
@@ -0,0 +1,57 @@
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running child is polled while waiting for it to exit
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Run a command to completion like `Command::output`, but kill it once the timeout elapses
+pub trait OutputWithTimeout {
+    fn output_with_timeout(&mut self, timeout: Duration) -> io::Result<Output>;
+}
+
+impl OutputWithTimeout for Command {
+    fn output_with_timeout(&mut self, timeout: Duration) -> io::Result<Output> {
+        let mut child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Drain pipes on separate threads so a chatty child can't block on a full pipe
+        let stdout = child.stdout.take().map(spawn_reader);
+        let stderr = child.stderr.take().map(spawn_reader);
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "command timed out"));
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        Ok(Output {
+            status,
+            stdout: stdout.map(join_reader).unwrap_or_default(),
+            stderr: stderr.map(join_reader).unwrap_or_default(),
+        })
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn join_reader(handle: thread::JoinHandle<Vec<u8>>) -> Vec<u8> {
+    handle.join().unwrap_or_default()
+}
@@ -0,0 +1,77 @@
+use super::ansi::truncate_to_width;
+
+/// Line width used when the terminal width can't be determined
+/// (60% of a 120 column terminal)
+const FALLBACK_LINE_WIDTH: usize = 72;
+
+/// Get terminal width using multiple fallback methods
+pub fn get_terminal_width() -> Option<usize> {
+    use std::io::IsTerminal;
+
+    // Method 1: Try terminal_size on stderr (stderr is usually still connected to terminal)
+    let stderr = std::io::stderr();
+    if stderr.is_terminal() {
+        if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size_of(&stderr) {
+            return Some(w as usize);
+        }
+    }
+
+    // Method 2: Try COLUMNS environment variable
+    if let Ok(cols) = std::env::var("COLUMNS") {
+        if let Ok(w) = cols.parse::<usize>() {
+            return Some(w);
+        }
+    }
+
+    // Method 3: Try terminal_size on stdout (fallback)
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size() {
+        return Some(w as usize);
+    }
+
+    None
+}
+
+/// Columns a statusline may use: `percent` of the terminal width, leaving
+/// `reserved_width` columns free
+pub fn max_line_width(term_width: usize, percent: usize, reserved_width: usize) -> usize {
+    let available = term_width.saturating_sub(reserved_width);
+    // Use the smaller of: percentage-based limit or available space
+    std::cmp::min((term_width * percent) / 100, available)
+}
+
+/// Truncate statusline to fit within `percent` of the terminal width, leaving
+/// `reserved_width` columns free, and end the cut line with `marker`
+pub fn truncate_to_terminal_width(
+    text: &str,
+    percent: usize,
+    reserved_width: usize,
+    marker: &str,
+) -> String {
+    let max_width = get_terminal_width()
+        .map(|term_width| max_line_width(term_width, percent, reserved_width))
+        .unwrap_or(FALLBACK_LINE_WIDTH);
+    truncate_to_width(text, max_width, marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_limits_wide_terminals() {
+        // 60% of 200 is 120, well inside the 160 left after reserving 40
+        assert_eq!(max_line_width(200, 60, 40), 120);
+    }
+
+    #[test]
+    fn reserved_width_limits_narrow_terminals() {
+        // 60% of 80 is 48, but only 40 columns remain after reserving 40
+        assert_eq!(max_line_width(80, 60, 40), 40);
+    }
+
+    #[test]
+    fn reserved_width_wider_than_terminal_leaves_nothing() {
+        assert_eq!(max_line_width(30, 60, 40), 0);
+        assert_eq!(max_line_width(100, 100, 0), 100);
+    }
+}